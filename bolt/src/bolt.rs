@@ -1,45 +1,227 @@
+mod cancel;
+mod chunk;
+mod config;
+mod pool;
+mod retry;
+mod routing;
+mod stream;
+mod tls;
+mod transaction;
 mod transport;
+#[cfg(test)]
+mod stub;
 
+use smol::channel::{self, Receiver, Sender};
 use smol::lock::Mutex;
 use transport::Transport;
+pub use cancel::CancelHandle;
+pub use config::{Config, ConfigBuilder, DEFAULT_USER_AGENT};
+pub use transport::{VersionRange, DEFAULT_VERSIONS};
+pub use stream::RecordStream;
+pub use transaction::{AccessMode, Transaction, TransactionConfig};
+pub use pool::{Pool, PoolConfig, PoolMetrics, PooledClient};
+pub use retry::{RetryPolicy, WorkFuture};
+pub use routing::{Router, RoutingTable};
+pub use tls::TlsConfig;
 use async_net::{TcpStream, AsyncToSocketAddrs};
 use semver::Version;
 use crate::error::{BoltError, BoltResult};
 use futures_lite::{AsyncWriteExt, AsyncReadExt};
 use bytes::BytesMut;
 use super::response::Response;
-use packstream_serde::message::Run;
+use packstream_serde::message::{
+    AckFailure, AuthToken, Begin, Commit, Record, Reset, Rollback, Run, Success, SummaryMessage,
+};
+use packstream_serde::packstream::{EmptyPackstreamStructure, PackstreamStructure};
+use packstream_serde::{from_bytes, to_bytes, Map, Value};
+use std::time::{Duration, Instant};
+
+/// Number of records requested at once by [`Client::run`] since Bolt 4.
+pub const DEFAULT_FETCH_SIZE: i64 = 1000;
 
 #[derive(Debug)]
 pub struct Client {
     transport: Transport,
+    fetch_size: i64,
+    state: ConnectionState,
+    // Number of requests sent which summary was not received yet.
+    outstanding: usize,
+    transaction: TransactionStatus,
+    retry_policy: RetryPolicy,
+    database: Option<String>,
+    query_timeout: Option<Duration>,
+    // Time by which the result of the running query must be received.
+    deadline: Option<Instant>,
+    // Cancellation requests of the `CancelHandle`s, at most one is pending.
+    canceller: Sender<()>,
+    cancelled: Receiver<()>,
 }
 
-// 1. Run Message -> (A)Success (B)Failure (C)Ignored
-//      A: Either -> (A1)PullAll (A2)Discard
-//          A1: PullAll -> Rows -> Success
-//          A2: Discard -> Success
-//      B: AckFailure
-//          B1: Success -> next
-//          B2: Ignored -> Reset
-//      C: Reset if not recoverable
+/// Explicit transaction open on the connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TransactionStatus {
+    None,
+    Open,
+    /// The `Transaction` was dropped, the transaction is rolled back before the next request.
+    Dropped,
+}
+
+/// States of the connection defined by the Bolt specification.
+//
+// READY --RUN--> STREAMING --last summary--> READY
+//   |               |
+//   +---FAILURE-----+--> FAILED --ACK_FAILURE/RESET--> READY
 //
-//  2. Send Message -> (A)Success (B)Failure (C)Ignored
-//      A: DiscardAll
-//      B: AckFailure
-//      C: Reset if not recoverable
+// RESET moves any state to INTERRUPTED until its SUCCESS arrives.
+// Unexpected or malformed messages, IO errors and reads or writes dropped unfinished
+// make the connection DEFUNCT.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// Ready to accept a request.
+    Ready,
+    /// Records of a result are being received or held by the server.
+    Streaming,
+    /// A request failed, the failure is acknowledged before the next request.
+    Failed,
+    /// RESET was sent, replies of the preceding requests are ignored.
+    Interrupted,
+    /// The connection cannot be used anymore.
+    Defunct,
+}
+
+/// Message received from the server.
+enum Message {
+    /// Undecoded RECORD message.
+    Record(BytesMut),
+    Summary(SummaryMessage),
+}
+
+/// Message of a result received by [`Client::next_message`].
+pub(crate) enum Streamed {
+    /// Undecoded RECORD message.
+    Record(BytesMut),
+    /// SUCCESS of the last request of the result.
+    Summary(Success),
+}
 
 impl Client {
-    pub async fn connect<A: AsyncToSocketAddrs, U: AsRef<str>, P: AsRef<str>>(addr: A, usr: U, pwd: P) -> BoltResult<Self> {
-        use packstream_serde::message::BasicAuth;
+    pub async fn connect<A: AsyncToSocketAddrs>(addr: A, auth: AuthToken) -> BoltResult<Self> {
+        let mut transport = Transport::new(addr, auth).await?;
+
+        Ok(Self::new(transport))
+    }
+
+    /// Connects proposing the given protocol versions instead of [`DEFAULT_VERSIONS`].
+    pub async fn connect_with_versions<A: AsyncToSocketAddrs>(
+        addr: A,
+        auth: AuthToken,
+        versions: &[VersionRange],
+    ) -> BoltResult<Self> {
+        let transport = Transport::with_versions(addr, auth, versions).await?;
+
+        Ok(Self::new(transport))
+    }
+
+    /// Connects with the address, encryption, authentication and other options of the configuration.
+    pub async fn connect_with_config(config: &Config) -> BoltResult<Self> {
+        let transport = Transport::connect(config, &DEFAULT_VERSIONS).await?;
+        let mut client = Self::new(transport);
+        client.fetch_size = config.fetch_size();
+        client.database = config.database().map(String::from);
+        client.query_timeout = config.query_timeout();
+
+        Ok(client)
+    }
+
+    fn new(transport: Transport) -> Self {
+        let (canceller, cancelled) = channel::bounded(1);
+
+        Self {
+            transport,
+            fetch_size: DEFAULT_FETCH_SIZE,
+            state: ConnectionState::Ready,
+            outstanding: 0,
+            transaction: TransactionStatus::None,
+            retry_policy: RetryPolicy::default(),
+            database: None,
+            query_timeout: None,
+            deadline: None,
+            canceller,
+            cancelled,
+        }
+    }
+
+    /// Sets the number of records requested at once by [`Client::run`], `-1` requests all records.
+    pub fn set_fetch_size(&mut self, fetch_size: i64) {
+        self.fetch_size = fetch_size;
+    }
+
+    /// Sets the database of the requests since Bolt 4, `None` selects the default database of the server.
+    pub fn set_database(&mut self, database: Option<String>) {
+        self.database = database;
+    }
+
+    /// Sets the time within which the whole result of each query must be received.
+    ///
+    /// A query which exceeds it fails with a timeout error and leaves the connection
+    /// defunct. Since Bolt 3 the timeout is also sent as `tx_timeout` of auto-commit
+    /// queries, so the server terminates them too. `None` waits forever.
+    pub fn set_query_timeout(&mut self, timeout: Option<Duration>) {
+        self.query_timeout = timeout;
+    }
+
+    /// Returns handle which cancels the query running on the connection.
+    pub fn cancel_handle(&self) -> CancelHandle {
+        CancelHandle::new(self.canceller.clone())
+    }
+
+    /// Makes the existing handles stop affecting the connection, e.g. before it is
+    /// handed to another user.
+    pub(super) fn revoke_cancel_handles(&mut self) {
+        (self.canceller, self.cancelled) = channel::bounded(1);
+    }
+
+    /// Bolt protocol version agreed with the server.
+    pub fn version(&self) -> &Version {
+        self.transport.version()
+    }
 
-        let mut transport = Transport::new(addr, BasicAuth {
-            principal: String::from(usr.as_ref()),
-            credentials: String::from(pwd.as_ref()),
-            scheme: String::from("basic"),
-        }).await?;
+    pub fn state(&self) -> ConnectionState {
+        if self.transport.is_broken() {
+            ConnectionState::Defunct
+        } else {
+            self.state
+        }
+    }
 
-        Ok(Self { transport })
+    /// Runs the statement and returns stream of its records.
+    pub async fn run(
+        &mut self,
+        statement: impl Into<String>,
+        parameters: Map<String, Value>,
+    ) -> BoltResult<RecordStream<'_>> {
+        self.ready().await?;
+        self.start_deadline();
+
+        let run = Run { statement: statement.into(), parameters };
+        self.request(&[
+            &self.transport.encode_run(&run, self.run_extra())?,
+            &self.transport.encode_pull(self.fetch_size)?,
+        ]).await?;
+
+        let mut success = self.run_summary().await?;
+        let fields = match success.metadata.remove("fields") {
+            Some(Value::List(fields)) => fields
+                .into_iter()
+                .map(|field| match field {
+                    Value::String(name) => Ok(name),
+                    _ => Err(self.violation("field names are not strings")),
+                })
+                .collect::<BoltResult<Vec<String>>>()?,
+            _ => Vec::new(),
+        };
+
+        Ok(RecordStream::new(self, fields))
     }
 
     pub async fn send
@@ -47,101 +229,391 @@ impl Client {
         (&mut self, message: &Run, pull_all: bool)
         -> BoltResult<Response<T>>
     {
-        use packstream_serde::{
-            packstream::{PackstreamStructure, EmptyPackstreamStructure},
-            message::{Ignored, PullAll, DiscardAll, Success, Failure, Reset, Record, AckFailure, SummaryMessage},
-            constants::marker::TINY_STRUCT,
-            from_bytes,
-            to_bytes,
-            Value,
-        };
+        self.ready().await?;
+        self.start_deadline();
 
         // Send two messages: request message and message telling server what to do
         // with the request results.
-        self.transport.write_batch(&[
-           &to_bytes(&message)?,
-           if pull_all { &PullAll::MSG } else { &DiscardAll::MSG }
+        self.request(&[
+           &self.transport.encode_run(message, self.run_extra())?,
+           &if pull_all { self.transport.encode_pull(-1)? } else { self.transport.encode_discard()? },
         ]).await?;
 
-        // Read the response for the messages sent do server.
-        let response = self.transport.read().await?;
+        let mut response = Response::from(self.run_summary().await?);
 
-        if Failure::check_header(response[0], response[1]) {
+        loop {
+            match self.next_message(false).await? {
+                // Rows which do not match `T` fail the request, remaining rows are
+                // discarded before the next request.
+                Streamed::Record(record) => response.push_row(from_bytes::<Vec<T>>(&record[2..])?),
+                Streamed::Summary(_) => return Ok(response),
+            }
+        }
+    }
 
-            let second_response = self.transport.read().await?;
+    /// Sends RESET, which interrupts the running request and returns the connection to READY.
+    pub async fn reset(&mut self) -> BoltResult<()> {
+        self.deadline = None;
+        self.request(&[&Reset::MSG]).await?;
+        self.state = ConnectionState::Interrupted;
+        self.finish_reset().await
+    }
 
-            // Second message sent is always ignored until ACK_FAILURE is sent in response to
-            // received first message FAILURE.
-            debug_assert!(Ignored::check_header(second_response[0], second_response[1]));
+    /// Receives the replies up to the one of RESET, which returns the connection to READY.
+    async fn finish_reset(&mut self) -> BoltResult<()> {
+        // Replies of the preceding requests arrive first, the last reply belongs to RESET.
+        loop {
+            let (message, _) = self.read_reply(false).await?;
 
-            // Send ACK_FAILURE to server.
-            self.transport.write(&AckFailure::MSG).await?;
+            if self.outstanding == 0 {
+                return match message {
+                    Message::Summary(SummaryMessage::Success(_)) => {
+                        // RESET rolls back the open transaction.
+                        self.state = ConnectionState::Ready;
+                        self.transaction = TransactionStatus::None;
+                        Ok(())
+                    }
+                    _ => Err(self.violation("RESET did not succeed")),
+                };
+            }
+        }
+    }
 
-            let ack_response = self.transport.read().await?;
+    /// Begins an explicit transaction, which is rolled back unless committed.
+    pub async fn begin_transaction(&mut self, config: TransactionConfig) -> BoltResult<Transaction<'_>> {
+        self.ready().await?;
 
-            if Success::check_header(ack_response[0], ack_response[1]) {
-                // TODO: return error with metadata from "response" error.
-                return Err(BoltError::from(from_bytes::<SummaryMessage>(&response[..])?));
-            } else {
-                // ACK_FAILURE failed.
-                // TODO: RESET connection.
-                return Err(BoltError::from(from_bytes::<SummaryMessage>(&response[..])?));
+        if self.version().major < 3 {
+            if !config.is_empty() {
+                return Err(BoltError::create("Transaction timeout and metadata require Bolt 3"));
+            }
+
+            self.run_in_transaction("BEGIN").await?;
+        } else {
+            let mut extra = config.into_extra();
+            extra.extend(self.database_extra());
+            self.request(&[&to_bytes(&Begin { extra })?]).await?;
+            self.summary().await?;
+        }
+
+        self.transaction = TransactionStatus::Open;
+        Ok(Transaction::new(self))
+    }
+
+    /// Commits or rolls back the open transaction.
+    async fn end_transaction(&mut self, commit: bool) -> BoltResult<()> {
+        self.settle().await?;
+
+        if self.transaction == TransactionStatus::None {
+            if commit {
+                return Err(BoltError::create("Transaction was rolled back after a failure"));
             }
-        } else if Ignored::check_header(response[0], response[1]) {
-            let second_response = self.transport.read().await?;
 
-            // Second message sent is always ignored until ACK_FAILURE is sent in response to
-            // received first message FAILURE.
-            debug_assert!(Ignored::check_header(second_response[0], second_response[1]));
+            return Ok(());
+        }
 
-            return Err(BoltError::from("Request failed: Ignored"));
+        if self.version().major < 3 {
+            self.run_in_transaction(if commit { "COMMIT" } else { "ROLLBACK" }).await?;
+        } else {
+            self.request(&[if commit { &Commit::MSG } else { &Rollback::MSG }]).await?;
+            self.summary().await?;
         }
 
-        let success = from_bytes::<Success>(&response).expect("message to be valid Success");
-        let mut response = Response::from(success);
+        self.transaction = TransactionStatus::None;
+        Ok(())
+    }
+
+    /// Runs the transaction control statement of Bolt 1 and 2.
+    async fn run_in_transaction(&mut self, statement: &str) -> BoltResult<()> {
+        let run = Run { statement: String::from(statement), parameters: Map::new() };
+        self.request(&[&self.transport.encode_run(&run, Map::new())?, &self.transport.encode_discard()?]).await?;
+        self.run_summary().await?;
+        self.discard_remaining().await
+    }
+
+    /// Extra of RUN, the database and timeout are set by BEGIN within explicit transactions.
+    fn run_extra(&self) -> Map<String, Value> {
+        if self.transaction == TransactionStatus::Open {
+            return Map::new();
+        }
 
-        if !pull_all {
-            // TODO: read success after discard.
-            self.transport.read().await?;
-            return Ok(response);
+        let mut extra = self.database_extra();
+        if let Some(timeout) = self.query_timeout {
+            extra.extend(TransactionConfig::new().timeout(timeout).into_extra());
         }
 
+        extra
+    }
+
+    fn database_extra(&self) -> Map<String, Value> {
+        match &self.database {
+            Some(database) if self.version().major >= 4 => {
+                Map::from([(String::from("db"), Value::String(database.clone()))])
+            }
+            _ => Map::new(),
+        }
+    }
+
+    /// Brings the connection to READY before a new request, rolling back
+    /// the transaction which `Transaction` was dropped.
+    async fn ready(&mut self) -> BoltResult<()> {
+        self.settle().await?;
+
+        if self.transaction == TransactionStatus::Dropped {
+            self.end_transaction(false).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Brings the connection to READY, the remaining records of the last query
+    /// are discarded within its deadline.
+    async fn settle(&mut self) -> BoltResult<()> {
+        self.check_defunct()?;
+
+        match self.state {
+            ConnectionState::Ready => (),
+            ConnectionState::Streaming => self.discard_remaining().await?,
+            ConnectionState::Failed => self.recover().await?,
+            ConnectionState::Interrupted => self.finish_reset().await?,
+            ConnectionState::Defunct => return Err(BoltError::create("Connection is defunct")),
+        }
+
+        self.deadline = None;
+        // Cancellation requested while no query was running.
+        while self.cancelled.try_recv().is_ok() {}
+
+        Ok(())
+    }
+
+    fn start_deadline(&mut self) {
+        // A timeout too long to be represented never expires.
+        self.deadline = self.query_timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+    }
+
+    /// Reads the summary of RUN, the request is acknowledged on failure.
+    async fn run_summary(&mut self) -> BoltResult<Success> {
+        let success = self.summary().await?;
+        self.state = ConnectionState::Streaming;
+        Ok(success)
+    }
+
+    /// Reads the summary of a request which does not stream records.
+    async fn summary(&mut self) -> BoltResult<Success> {
+        match self.receive().await? {
+            Message::Summary(SummaryMessage::Success(success)) => Ok(success),
+            Message::Summary(summary) => self.fail(summary).await,
+            Message::Record(_) => Err(self.violation("unexpected RECORD")),
+        }
+    }
+
+    /// Reads the next record or the summary of the result. When the server has more
+    /// records, pulls the next batch if `pull_more` is set or returns the summary of
+    /// the last batch otherwise.
+    pub(crate) async fn next_message(&mut self, pull_more: bool) -> BoltResult<Streamed> {
         loop {
-            let msg = self.transport.read().await?;
-            let marker = msg[0];
-            let signature = msg[1];
-
-            if Record::check_header(marker, signature) {
-                match from_bytes::<Vec<T>>(&msg[2..]) {
-                    Ok(values) => response.push_row(values),
-                    Err(err) => unimplemented!(),
+            let message = self.receive().await?;
+
+            if self.state != ConnectionState::Streaming {
+                return Err(self.violation("message received outside of a result"));
+            }
+
+            match message {
+                Message::Record(record) => return Ok(Streamed::Record(record)),
+                Message::Summary(SummaryMessage::Success(success)) if has_more(&success) => {
+                    if !pull_more {
+                        return Ok(Streamed::Summary(success));
+                    }
+
+                    self.request(&[&self.transport.encode_pull(self.fetch_size)?]).await?;
                 }
+                Message::Summary(SummaryMessage::Success(success)) => {
+                    self.state = ConnectionState::Ready;
+                    return Ok(Streamed::Summary(success));
+                }
+                Message::Summary(summary) => return self.fail(summary).await,
+            }
+        }
+    }
+
+    /// Decodes the RECORD message, malformed records make the connection defunct.
+    pub(crate) fn decode_record(&mut self, message: &[u8]) -> BoltResult<Record> {
+        from_bytes::<Record>(message).map_err(|error| self.violation(format!("malformed RECORD: {}", error)))
+    }
+
+    /// Discards records of a result which was not consumed.
+    async fn discard_remaining(&mut self) -> BoltResult<()> {
+        while self.state == ConnectionState::Streaming {
+            // The server holds records of the result which were not pulled.
+            if self.outstanding == 0 {
+                self.request(&[&self.transport.encode_discard()?]).await?;
+            }
+
+            self.next_message(false).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Moves to FAILED, recovers and returns the summary as the error.
+    async fn fail<T>(&mut self, summary: SummaryMessage) -> BoltResult<T> {
+        self.state = ConnectionState::Failed;
+        self.recover().await?;
+
+        Err(BoltError::from(summary))
+    }
+
+    /// Acknowledges the failure with ACK_FAILURE, or with RESET since Bolt 3
+    /// or when ACK_FAILURE did not succeed.
+    async fn recover(&mut self) -> BoltResult<()> {
+        // Requests sent after the failed one are IGNORED.
+        while self.outstanding > 0 {
+            self.receive().await?;
+        }
+
+        if self.version().major < 3 {
+            self.request(&[&AckFailure::MSG]).await?;
+
+            match self.receive().await? {
+                Message::Summary(SummaryMessage::Success(_)) => {
+                    self.state = ConnectionState::Ready;
+                    return Ok(());
+                }
+                Message::Summary(_) => (),
+                Message::Record(_) => return Err(self.violation("RECORD received in reply to ACK_FAILURE")),
+            }
+        }
+
+        self.reset().await
+    }
+
+    /// Fails when the connection is defunct. A read or write which was dropped
+    /// unfinished, e.g. by dropping a `RecordStream` waiting for a record, leaves
+    /// the framing of the stream lost and makes the connection defunct.
+    fn check_defunct(&mut self) -> BoltResult<()> {
+        if self.transport.is_broken() {
+            self.state = ConnectionState::Defunct;
+        }
+
+        if self.state == ConnectionState::Defunct {
+            return Err(BoltError::create("Connection is defunct"));
+        }
+
+        Ok(())
+    }
+
+    /// Writes the requests, the connection becomes defunct when writing fails.
+    async fn request(&mut self, messages: &[&[u8]]) -> BoltResult<()> {
+        self.check_defunct()?;
+
+        if let Err(error) = self.transport.write_batch(messages).await {
+            self.state = ConnectionState::Defunct;
+            return Err(error);
+        }
+
+        self.outstanding += messages.len();
+        Ok(())
+    }
+
+    /// Reads the next message, checking that a request is waiting for it.
+    ///
+    /// On request of a `CancelHandle` the requests are interrupted with RESET and
+    /// the query fails once the connection is READY again.
+    async fn receive(&mut self) -> BoltResult<Message> {
+        let interruptible = self.state != ConnectionState::Interrupted;
+        let (message, reset) = self.read_reply(interruptible).await?;
+
+        if reset {
+            self.finish_reset().await?;
+            return Err(BoltError::cancelled());
+        }
+
+        Ok(message)
+    }
+
+    /// Reads the next message, writing RESET on cancellation when `interruptible`.
+    /// Returns whether RESET was written.
+    async fn read_reply(&mut self, interruptible: bool) -> BoltResult<(Message, bool)> {
+        self.check_defunct()?;
+
+        let cancelled = &self.cancelled;
+        let interrupt = async move {
+            if interruptible {
+                let _ = cancelled.recv().await;
             } else {
-                break;
+                smol::future::pending::<()>().await;
             }
+        };
+
+        let read = self.transport.read_or_reset(interrupt);
+        let message = match self.deadline {
+            Some(deadline) => {
+                let timeout = self.query_timeout.unwrap_or_default();
+                transport::timeout(Some(deadline.saturating_duration_since(Instant::now())), read, |_| {
+                    BoltError::query_timeout(format!("Query did not complete within {:?}", timeout))
+                }).await
+            }
+            None => read.await,
+        };
+
+        // A message interrupted by the deadline cannot be read anymore.
+        let (message, reset) = match message {
+            Ok(message) => message,
+            Err(error) => {
+                self.state = ConnectionState::Defunct;
+                return Err(error);
+            }
+        };
+
+        if reset {
+            self.state = ConnectionState::Interrupted;
+            self.outstanding += 1;
+        }
+
+        if self.outstanding == 0 {
+            return Err(self.violation("message received without a request"));
+        }
+
+        if Record::check_header(message[0], message[1]) {
+            return Ok((Message::Record(message), reset));
         }
 
-        Ok(response)
+        match from_bytes::<SummaryMessage>(&message) {
+            Ok(summary) => {
+                self.outstanding -= 1;
+                Ok((Message::Summary(summary), reset))
+            }
+            Err(error) => Err(self.violation(format!("unexpected message: {}", error))),
+        }
+    }
+
+    /// Marks the connection as defunct.
+    fn violation(&mut self, reason: impl std::fmt::Display) -> BoltError {
+        self.state = ConnectionState::Defunct;
+        BoltError::create(format!("Protocol violation: {}", reason))
     }
 }
 
+fn has_more(success: &Success) -> bool {
+    success.metadata.get("has_more") == Some(&Value::Bool(true))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::sync::Arc;
     use packstream_serde::message::Run;
-    use packstream_serde::Value;
-    use std::collections::HashMap;
+    use packstream_serde::{Map, Value};
     use packstream_serde::{to_bytes, from_bytes};
 
     fn test_connection () -> impl std::future::Future<Output = BoltResult<Client>> {
-        Client::connect("localhost:7687", "neo4j", "bolt-rs")
+        Client::connect("localhost:7687", AuthToken::basic("neo4j", "bolt-rs"))
     }
 
     fn run_message (msg: impl Into<String>) -> Run {
         Run {
             statement: msg.into(),
-            parameters: HashMap::new(),
+            parameters: Map::new(),
         }
     }
 
@@ -227,6 +699,525 @@ mod tests {
         });
     }
     
+    #[test]
+    fn stream_records() {
+        use futures_lite::StreamExt;
+
+        smol::block_on(async {
+            let mut bolt = test_connection().await.unwrap();
+            bolt.set_fetch_size(2);
+
+            let mut stream = bolt.run("UNWIND range(1, 5) AS x RETURN x", Map::new()).await.unwrap();
+            assert_eq!(stream.fields(), ["x"]);
+
+            let mut count = 0;
+            while let Some(record) = stream.next().await {
+                count += 1;
+                assert_eq!(record.unwrap().fields, [Value::I64(count)]);
+            }
+            assert_eq!(count, 5);
+            assert!(stream.summary().is_some());
+            drop(stream);
+
+            // Remaining records of the dropped stream are discarded.
+            let mut stream = bolt.run("UNWIND range(1, 5) AS x RETURN x", Map::new()).await.unwrap();
+            assert!(stream.next().await.unwrap().is_ok());
+            drop(stream);
+
+            let stream = bolt.run("RETURN 1 AS y", Map::new()).await.unwrap();
+            assert_eq!(stream.fields(), ["y"]);
+            assert!(stream.consume().await.is_ok());
+        });
+    }
+
+    mod scripted {
+        use super::*;
+        use super::super::stub::{failure, hello, ignored, record, success, Step, Stub};
+        use packstream_serde::constants::message::*;
+        use futures_lite::StreamExt;
+
+        fn connect(stub: &Stub) -> Client {
+            smol::block_on(Client::connect(stub.addr(), AuthToken::none())).unwrap()
+        }
+
+        fn result(field: &str, values: &[i64]) -> Vec<Step> {
+            let mut steps = vec![Step::Recv(RUN), Step::Recv(PULL), success(&format!("{{fields: [{:?}]}}", field))];
+            steps.extend(values.iter().map(|value| record(vec![Value::I64(*value)])));
+            steps.push(success("{}"));
+            steps
+        }
+
+        #[test]
+        fn failure_is_reset() {
+            let mut script = hello();
+            script.extend(vec![
+                Step::Recv(RUN), Step::Recv(PULL),
+                failure("Neo.ClientError.Statement.SyntaxError", "Invalid input"),
+                ignored(),
+                Step::Recv(RESET), success("{}"),
+            ]);
+            script.extend(result("x", &[1]));
+            let stub = Stub::start([4, 4], script);
+
+            smol::block_on(async {
+                let mut bolt = connect(&stub);
+                let error = bolt.run("NRUTER 1", Map::new()).await.unwrap_err();
+                assert!(error.is_client_error());
+                assert_eq!(error.neo4j().unwrap().title, "SyntaxError");
+                assert_eq!(bolt.state(), ConnectionState::Ready);
+
+                let records: Vec<_> = bolt.run("RETURN 1 AS x", Map::new()).await.unwrap().collect().await;
+                assert_eq!(records.len(), 1);
+                assert_eq!(bolt.state(), ConnectionState::Ready);
+            });
+            stub.finish();
+        }
+
+        #[test]
+        fn ack_failure_falls_back_to_reset() {
+            let stub = Stub::start([1, 0], vec![
+                Step::Recv(INIT), success("{}"),
+                Step::Recv(RUN), Step::Recv(PULL_ALL),
+                failure("Neo.ClientError.Statement.SyntaxError", "Invalid input"),
+                ignored(),
+                Step::Recv(ACK_FAILURE), ignored(),
+                Step::Recv(RESET), success("{}"),
+                Step::Recv(RUN), Step::Recv(PULL_ALL),
+                failure("Neo.ClientError.Statement.SyntaxError", "Invalid input"),
+                ignored(),
+                Step::Recv(ACK_FAILURE), success("{}"),
+            ]);
+
+            smol::block_on(async {
+                let versions = [VersionRange::new(1, 0)];
+                let mut bolt = Client::connect_with_versions(stub.addr(), AuthToken::none(), &versions).await.unwrap();
+                assert_eq!(bolt.version(), &Version::new(1, 0, 0));
+
+                assert!(bolt.send::<Value>(&run_message("NRUTER 1"), true).await.is_err());
+                assert_eq!(bolt.state(), ConnectionState::Ready);
+                assert!(bolt.send::<Value>(&run_message("NRUTER 1"), true).await.is_err());
+                assert_eq!(bolt.state(), ConnectionState::Ready);
+            });
+            stub.finish();
+        }
+
+        #[test]
+        fn logon_since_5_1() {
+            let stub = Stub::start([5, 1], vec![
+                Step::Recv(HELLO), success(r#"{server: "Neo4j/5.1.0"}"#),
+                Step::Recv(LOGON), success("{}"),
+            ]);
+
+            smol::block_on(async {
+                let versions = [VersionRange::new(5, 1)];
+                let auth = AuthToken::basic("neo4j", "password");
+                let bolt = Client::connect_with_versions(stub.addr(), auth, &versions).await.unwrap();
+                assert_eq!(bolt.version(), &Version::new(5, 1, 0));
+            });
+            stub.finish();
+        }
+
+        #[test]
+        fn dropped_stream_is_discarded() {
+            let mut script = hello();
+            script.extend(vec![
+                Step::Recv(RUN), Step::Recv(PULL), success(r#"{fields: ["x"]}"#),
+                record(vec![Value::I64(1)]),
+                success("{has_more: true}"),
+                Step::Recv(DISCARD), success("{}"),
+            ]);
+            script.extend(result("y", &[2]));
+            let stub = Stub::start([4, 4], script);
+
+            smol::block_on(async {
+                let mut bolt = connect(&stub);
+                bolt.set_fetch_size(1);
+
+                let mut stream = bolt.run("UNWIND [1, 2] AS x RETURN x", Map::new()).await.unwrap();
+                assert!(stream.next().await.unwrap().is_ok());
+                drop(stream);
+                assert_eq!(bolt.state(), ConnectionState::Streaming);
+
+                let response = bolt.send::<i64>(&run_message("RETURN 2 AS y"), true).await.unwrap();
+                assert_eq!(response.into_rows(), [[2]]);
+            });
+            stub.finish();
+        }
+
+        #[test]
+        fn mismatched_row_does_not_break_connection() {
+            let mut script = hello();
+            script.extend(result("x", &[1, 2]));
+            script.extend(result("x", &[3]));
+            let stub = Stub::start([4, 4], script);
+
+            smol::block_on(async {
+                let mut bolt = connect(&stub);
+
+                assert!(bolt.send::<String>(&run_message("RETURN 1 AS x"), true).await.is_err());
+                assert_eq!(bolt.state(), ConnectionState::Streaming);
+
+                let response = bolt.send::<i64>(&run_message("RETURN 3 AS x"), true).await.unwrap();
+                assert_eq!(response.into_rows(), [[3]]);
+            });
+            stub.finish();
+        }
+
+        #[test]
+        fn transaction() {
+            let mut script = hello();
+            script.extend(vec![Step::Recv(BEGIN), success("{}")]);
+            script.extend(result("x", &[1]));
+            script.extend(vec![Step::Recv(COMMIT), success(r#"{bookmark: "b:1"}"#)]);
+            // Dropped transaction is rolled back before the next request.
+            script.extend(vec![Step::Recv(BEGIN), success("{}")]);
+            script.extend(vec![Step::Recv(ROLLBACK), success("{}")]);
+            script.extend(result("y", &[]));
+            let stub = Stub::start([4, 4], script);
+
+            smol::block_on(async {
+                let mut bolt = connect(&stub);
+
+                let config = TransactionConfig::new()
+                    .timeout(Duration::from_secs(5))
+                    .metadata(Map::from([(String::from("app"), Value::String(String::from("test")))]));
+                let extra = config.clone().into_extra();
+                assert_eq!(extra.get("tx_timeout"), Some(&Value::I64(5000)));
+
+                let mut tx = bolt.begin_transaction(config).await.unwrap();
+                let records: Vec<_> = tx.run("RETURN 1 AS x", Map::new()).await.unwrap().collect().await;
+                assert_eq!(records.len(), 1);
+                tx.commit().await.unwrap();
+
+                let tx = bolt.begin_transaction(TransactionConfig::new()).await.unwrap();
+                drop(tx);
+                assert!(bolt.send::<i64>(&run_message("RETURN 1 AS y"), true).await.is_ok());
+            });
+            stub.finish();
+        }
+
+        #[test]
+        fn failed_transaction() {
+            let mut script = hello();
+            script.extend(vec![
+                Step::Recv(BEGIN), success("{}"),
+                Step::Recv(RUN), Step::Recv(PULL),
+                failure("Neo.ClientError.Statement.SyntaxError", "Invalid input"),
+                ignored(),
+                Step::Recv(RESET), success("{}"),
+            ]);
+            let stub = Stub::start([4, 4], script);
+
+            smol::block_on(async {
+                let mut bolt = connect(&stub);
+
+                let mut tx = bolt.begin_transaction(TransactionConfig::new()).await.unwrap();
+                assert!(tx.run("NRUTER 1", Map::new()).await.is_err());
+                assert!(tx.run("RETURN 1", Map::new()).await.is_err());
+                assert!(tx.commit().await.is_err());
+                assert_eq!(bolt.state(), ConnectionState::Ready);
+            });
+            stub.finish();
+        }
+
+        #[test]
+        fn transaction_v1() {
+            let stub = Stub::start([1, 0], vec![
+                Step::Recv(INIT), success("{}"),
+                Step::Recv(RUN), Step::Recv(DISCARD_ALL), success("{fields: []}"), success("{}"),
+                Step::Recv(RUN), Step::Recv(DISCARD_ALL), success("{fields: []}"), success("{}"),
+            ]);
+
+            smol::block_on(async {
+                let versions = [VersionRange::new(1, 0)];
+                let mut bolt = Client::connect_with_versions(stub.addr(), AuthToken::none(), &versions).await.unwrap();
+
+                let config = TransactionConfig::new().timeout(Duration::from_secs(1));
+                assert!(bolt.begin_transaction(config).await.is_err());
+
+                let tx = bolt.begin_transaction(TransactionConfig::new()).await.unwrap();
+                tx.rollback().await.unwrap();
+            });
+            stub.finish();
+        }
+
+        fn fast_retries() -> RetryPolicy {
+            RetryPolicy::new().initial_delay(Duration::from_millis(1))
+        }
+
+        #[test]
+        fn managed_transaction_retries_transient_errors() {
+            let mut script = hello();
+            script.extend(vec![
+                Step::Recv(BEGIN), success("{}"),
+                Step::Recv(RUN), Step::Recv(PULL),
+                failure("Neo.TransientError.Transaction.DeadlockDetected", "Deadlock"),
+                ignored(),
+                Step::Recv(RESET), success("{}"),
+                Step::Recv(BEGIN), success("{}"),
+            ]);
+            script.extend(result("x", &[1]));
+            script.extend(vec![Step::Recv(COMMIT), success("{}")]);
+            let stub = Stub::start([4, 4], script);
+
+            smol::block_on(async {
+                let mut bolt = connect(&stub);
+                bolt.set_retry_policy(fast_retries());
+
+                let attempts = std::sync::atomic::AtomicUsize::new(0);
+                let count = bolt.read_transaction(TransactionConfig::new(), |tx| {
+                    attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                    Box::pin(async move {
+                        let stream = tx.run("RETURN 1 AS x", Map::new()).await?;
+                        Ok(stream.collect::<Vec<_>>().await.len())
+                    })
+                }).await.unwrap();
+
+                assert_eq!(count, 1);
+                assert_eq!(attempts.into_inner(), 2);
+            });
+            stub.finish();
+        }
+
+        #[test]
+        fn managed_transaction_does_not_retry() {
+            let mut script = hello();
+            script.extend(vec![
+                Step::Recv(BEGIN), success("{}"),
+                Step::Recv(RUN), Step::Recv(PULL),
+                failure("Neo.ClientError.Statement.SyntaxError", "Invalid input"),
+                ignored(),
+                Step::Recv(RESET), success("{}"),
+                // Error returned by the unit of work rolls the transaction back.
+                Step::Recv(BEGIN), success("{}"),
+                Step::Recv(ROLLBACK), success("{}"),
+                // Retries are not started after the maximum retry time.
+                Step::Recv(BEGIN), success("{}"),
+                Step::Recv(RUN), Step::Recv(PULL),
+                failure("Neo.TransientError.Transaction.DeadlockDetected", "Deadlock"),
+                ignored(),
+                Step::Recv(RESET), success("{}"),
+            ]);
+            let stub = Stub::start([4, 4], script);
+
+            smol::block_on(async {
+                let mut bolt = connect(&stub);
+                bolt.set_retry_policy(fast_retries());
+
+                fn run<'t>(tx: &'t mut Transaction<'_>) -> WorkFuture<'t, ()> {
+                    Box::pin(async move {
+                        tx.run("RETURN 1", Map::new()).await?;
+                        Ok(())
+                    })
+                }
+
+                let error = bolt.write_transaction(TransactionConfig::new(), run).await.unwrap_err();
+                assert!(error.is_client_error());
+
+                let result: BoltResult<()> = bolt.write_transaction(TransactionConfig::new(), |_| {
+                    Box::pin(async { Err(BoltError::create("Rejected")) })
+                }).await;
+                assert!(result.is_err());
+
+                bolt.set_retry_policy(fast_retries().max_retry_time(Duration::ZERO));
+                let error = bolt.write_transaction(TransactionConfig::new(), run).await.unwrap_err();
+                assert!(error.is_transient());
+            });
+            stub.finish();
+        }
+
+        #[test]
+        fn protocol_violation_is_defunct() {
+            let mut script = hello();
+            script.extend(vec![
+                Step::Recv(RUN), Step::Recv(PULL),
+                Step::Send(vec![0xB1, 0x55, 0x01]),
+            ]);
+            let stub = Stub::start([4, 4], script);
+
+            smol::block_on(async {
+                let mut bolt = connect(&stub);
+
+                assert!(bolt.run("RETURN 1", Map::new()).await.is_err());
+                assert_eq!(bolt.state(), ConnectionState::Defunct);
+                assert!(bolt.run("RETURN 1", Map::new()).await.is_err());
+            });
+            stub.finish();
+        }
+
+        #[test]
+        fn dropped_fetch_is_defunct() {
+            let mut script = hello();
+            script.extend(vec![
+                Step::Recv(RUN), Step::Recv(PULL),
+                success(r#"{fields: ["x"]}"#),
+                Step::Sleep(Duration::from_millis(200)),
+            ]);
+            let stub = Stub::start([4, 4], script);
+
+            smol::block_on(async {
+                let mut bolt = connect(&stub);
+
+                let mut stream = bolt.run("RETURN 1 AS x", Map::new()).await.unwrap();
+                let next = smol::future::or(async { Some(stream.next().await) }, async {
+                    smol::Timer::after(Duration::from_millis(50)).await;
+                    None
+                });
+                assert!(next.await.is_none());
+                drop(stream);
+
+                assert_eq!(bolt.state(), ConnectionState::Defunct);
+                let error = bolt.run("RETURN 1", Map::new()).await.unwrap_err();
+                assert_eq!(error.to_string(), "Connection is defunct");
+            });
+            stub.finish();
+        }
+
+        #[test]
+        fn unsolicited_record_is_defunct() {
+            let mut script = hello();
+            script.extend(result("x", &[]));
+            script.push(record(vec![Value::I64(1)]));
+            let stub = Stub::start([4, 4], script);
+
+            smol::block_on(async {
+                let mut bolt = connect(&stub);
+
+                let response = bolt.send::<i64>(&run_message("RETURN 1 AS x"), true).await.unwrap();
+                assert!(response.into_rows().is_empty());
+                assert!(bolt.reset().await.is_err());
+                assert_eq!(bolt.state(), ConnectionState::Defunct);
+            });
+            stub.finish();
+        }
+
+        #[test]
+        fn oversized_message_is_defunct() {
+            let mut script = hello();
+            script.extend(vec![
+                Step::Recv(RUN), Step::Recv(PULL),
+                success(r#"{fields: ["a_rather_long_field_name"]}"#),
+            ]);
+            let stub = Stub::start([4, 4], script);
+
+            smol::block_on(async {
+                let config = Config::builder(stub.addr().to_string()).max_message_size(16).build();
+                let mut bolt = Client::connect_with_config(&config).await.unwrap();
+
+                let error = bolt.run("RETURN 1", Map::new()).await.unwrap_err();
+                assert_eq!(error.to_string(), "Message exceeds the maximum size of 16 bytes");
+                assert_eq!(bolt.state(), ConnectionState::Defunct);
+            });
+            stub.finish();
+        }
+
+        #[test]
+        fn connect_timeout() {
+            // The listener completes the TCP connection but never replies to the handshake.
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            let addr = listener.local_addr().unwrap().to_string();
+
+            let config = Config::builder(addr.as_str()).connect_timeout(Duration::from_millis(50)).build();
+            let error = smol::block_on(Client::connect_with_config(&config)).unwrap_err();
+            assert!(error.is_timeout());
+            assert_eq!(error.to_string(), format!("Failed to connect to {} within 50ms", addr));
+        }
+
+        #[test]
+        fn query_timeout_is_defunct() {
+            let mut script = hello();
+            script.extend(result("x", &[1]));
+            script.extend(vec![Step::Recv(RUN), Step::Recv(PULL), Step::Sleep(Duration::from_millis(200))]);
+            let stub = Stub::start([4, 4], script);
+
+            smol::block_on(async {
+                let mut bolt = connect(&stub);
+                bolt.set_query_timeout(Some(Duration::from_millis(50)));
+                assert_eq!(bolt.run_extra().get("tx_timeout"), Some(&Value::I64(50)));
+
+                let response = bolt.send::<i64>(&run_message("RETURN 1 AS x"), true).await.unwrap();
+                assert_eq!(response.into_rows(), [[1]]);
+
+                let error = bolt.send::<i64>(&run_message("CALL apoc.util.sleep(1000)"), true).await.unwrap_err();
+                assert!(error.is_timeout());
+                assert_eq!(error.to_string(), "Query did not complete within 50ms");
+                assert_eq!(bolt.state(), ConnectionState::Defunct);
+            });
+            stub.finish();
+        }
+
+        #[test]
+        fn huge_query_timeout_never_expires() {
+            let mut script = hello();
+            script.extend(result("x", &[1]));
+            let stub = Stub::start([4, 4], script);
+
+            smol::block_on(async {
+                let mut bolt = connect(&stub);
+                bolt.set_query_timeout(Some(Duration::MAX));
+
+                let response = bolt.send::<i64>(&run_message("RETURN 1 AS x"), true).await.unwrap();
+                assert_eq!(response.into_rows(), [[1]]);
+            });
+            stub.finish();
+        }
+
+        #[test]
+        fn cancel_running_query() {
+            let mut script = hello();
+            script.extend(result("x", &[1]));
+            script.extend(vec![
+                Step::Recv(RUN), Step::Recv(PULL),
+                success(r#"{fields: ["x"]}"#), record(vec![Value::I64(1)]),
+                Step::Recv(RESET), ignored(), success("{}"),
+            ]);
+            script.extend(result("y", &[2]));
+            let stub = Stub::start([4, 4], script);
+
+            smol::block_on(async {
+                let mut bolt = connect(&stub);
+                let handle = bolt.cancel_handle();
+
+                // Requested while idle, dropped before the query.
+                handle.cancel();
+                let response = bolt.send::<i64>(&run_message("RETURN 1 AS x"), true).await.unwrap();
+                assert_eq!(response.into_rows(), [[1]]);
+
+                let canceller = std::thread::spawn(move || {
+                    std::thread::sleep(Duration::from_millis(50));
+                    handle.cancel();
+                });
+                let error = bolt.send::<i64>(&run_message("UNWIND range(1, 1000000) AS x RETURN x"), true).await.unwrap_err();
+                canceller.join().unwrap();
+                assert!(error.is_cancelled());
+                assert_eq!(bolt.state(), ConnectionState::Ready);
+
+                let response = bolt.send::<i64>(&run_message("RETURN 2 AS y"), true).await.unwrap();
+                assert_eq!(response.into_rows(), [[2]]);
+            });
+            stub.finish();
+        }
+
+        #[test]
+        fn read_timeout_is_defunct() {
+            let mut script = hello();
+            script.extend(vec![Step::Recv(RUN), Step::Recv(PULL), Step::Sleep(Duration::from_millis(200))]);
+            let stub = Stub::start([4, 4], script);
+
+            smol::block_on(async {
+                let config = Config::builder(stub.addr().to_string()).read_timeout(Duration::from_millis(50)).build();
+                let mut bolt = Client::connect_with_config(&config).await.unwrap();
+
+                let error = bolt.run("RETURN 1", Map::new()).await.unwrap_err();
+                assert!(error.is_timeout());
+                assert_eq!(error.to_string(), "No message received from the server within 50ms");
+                assert_eq!(bolt.state(), ConnectionState::Defunct);
+                assert!(bolt.run("RETURN 1", Map::new()).await.is_err());
+            });
+            stub.finish();
+        }
+    }
+
     //#[test]
     //fn ack_failure_retry_multiple () {
     //}
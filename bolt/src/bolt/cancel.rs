@@ -0,0 +1,26 @@
+use smol::channel::Sender;
+
+/// Cancels the query running on a [`Client`](super::Client) from another task or thread.
+///
+/// The handle is cheap to clone and obtained with [`Client::cancel_handle`](super::Client::cancel_handle)
+/// before the query is started, as the client stays borrowed while it runs. The
+/// client sends RESET while waiting for the reply, receives the IGNORED and FAILURE
+/// replies of the interrupted requests and fails the query with a cancelled error.
+/// The connection remains usable afterwards.
+#[derive(Debug, Clone)]
+pub struct CancelHandle {
+    sender: Sender<()>,
+}
+
+impl CancelHandle {
+    pub(super) fn new(sender: Sender<()>) -> Self {
+        Self { sender }
+    }
+
+    /// Requests cancellation of the running query. Requests made while the client is
+    /// idle are dropped before its next request.
+    pub fn cancel(&self) {
+        // A full channel already holds a request.
+        let _ = self.sender.try_send(());
+    }
+}
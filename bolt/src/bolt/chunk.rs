@@ -0,0 +1,141 @@
+//! Chunked message framing.
+//!
+//! Every message is split into chunks of at most `u16::MAX` bytes, each prefixed with
+//! its two byte length. The message ends with an empty chunk, the `0x0000` end marker.
+use crate::error::{BoltError, BoltResult};
+use bytes::{BytesMut, BufMut};
+use futures_lite::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+// u16::MAX
+pub const MAX_CHUNK_SIZE: usize = 65_535;
+
+/// Appends `message` split into chunks of at most `max_chunk_size` bytes, followed by the end marker.
+pub fn encode (buffer: &mut BytesMut, message: &[u8], max_chunk_size: usize) {
+   debug_assert!(max_chunk_size > 0 && max_chunk_size <= MAX_CHUNK_SIZE);
+
+   buffer.reserve(message.len() + 2 * (message.len() / max_chunk_size + 2));
+
+   for chunk in message.chunks(max_chunk_size) {
+      // Fits in two bytes, chunks are never larger than MAX_CHUNK_SIZE.
+      buffer.put_u16(chunk.len() as u16);
+      buffer.extend_from_slice(chunk);
+   }
+
+   buffer.put_u16(0);
+}
+
+/// Encodes all `messages` into the buffer and writes them with a single write.
+pub async fn write_messages<W: AsyncWrite + Unpin> (
+   stream: &mut W,
+   buffer: &mut BytesMut,
+   messages: &[&[u8]],
+   max_chunk_size: usize,
+) -> BoltResult<()> {
+   buffer.clear();
+
+   for message in messages {
+      encode(buffer, message, max_chunk_size);
+   }
+
+   stream.write_all(buffer).await?;
+   stream.flush().await?;
+   buffer.clear();
+
+   Ok(())
+}
+
+/// Reads chunks until the end marker and returns the joined message.
+///
+/// End markers received before any data are NOOP chunks and are skipped.
+/// Messages longer than `max_size` are rejected before their data is read.
+pub async fn read_message<R: AsyncRead + Unpin> (stream: &mut R, max_size: usize) -> BoltResult<BytesMut> {
+   let mut message = BytesMut::new();
+   let mut header: [u8; 2] = [0; 2];
+
+   loop {
+      stream.read_exact(&mut header).await?;
+      let len = usize::from(u16::from_be_bytes(header));
+
+      if len == 0 {
+         if message.is_empty() {
+            continue;
+         }
+
+         return Ok(message);
+      }
+
+      let start = message.len();
+      if start + len > max_size {
+         return Err(BoltError::create(format!("Message exceeds the maximum size of {} bytes", max_size)));
+      }
+
+      message.resize(start + len, 0);
+      stream.read_exact(&mut message[start..]).await?;
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   fn encoded (message: &[u8], max_chunk_size: usize) -> Vec<u8> {
+      let mut buffer = BytesMut::new();
+      encode(&mut buffer, message, max_chunk_size);
+      buffer.to_vec()
+   }
+
+   fn read (mut bytes: &[u8]) -> BoltResult<BytesMut> {
+      smol::block_on(read_message(&mut bytes, usize::MAX))
+   }
+
+   #[test]
+   fn small_message () {
+      assert_eq!(encoded(&[0xB0, 0x0F], MAX_CHUNK_SIZE), [0x00, 0x02, 0xB0, 0x0F, 0x00, 0x00]);
+      assert_eq!(encoded(&[1, 2, 3], 2), [0x00, 0x02, 1, 2, 0x00, 0x01, 3, 0x00, 0x00]);
+      assert_eq!(encoded(&[1, 2], 2), [0x00, 0x02, 1, 2, 0x00, 0x00]);
+   }
+
+   #[test]
+   fn large_message () {
+      let message: Vec<u8> = (0..200_000u32).map(|i| i as u8).collect();
+      let bytes = encoded(&message, MAX_CHUNK_SIZE);
+
+      // Three full chunks, the rest and the end marker.
+      assert_eq!(bytes.len(), message.len() + 5 * 2);
+      assert_eq!(&bytes[0..2], &[0xFF, 0xFF]);
+      assert_eq!(&bytes[65_537..65_539], &[0xFF, 0xFF]);
+      assert_eq!(&bytes[3 * 65_537..3 * 65_537 + 2], &u16::to_be_bytes((200_000 - 3 * 65_535) as u16));
+      assert_eq!(&bytes[bytes.len() - 2..], &[0x00, 0x00]);
+
+      assert_eq!(read(&bytes).unwrap(), &message[..]);
+   }
+
+   #[test]
+   fn batch () {
+      let mut stream: Vec<u8> = Vec::new();
+      let mut buffer = BytesMut::new();
+      let large = vec![7; 70_000];
+      let messages: [&[u8]; 3] = [&[0xB0, 0x0F], &large, &[0xB0, 0x2F]];
+
+      smol::block_on(write_messages(&mut stream, &mut buffer, &messages, 1_000)).unwrap();
+      assert!(buffer.is_empty());
+
+      let mut reader = &stream[..];
+      for message in messages.iter() {
+         assert_eq!(smol::block_on(read_message(&mut reader, usize::MAX)).unwrap(), message);
+      }
+      assert!(reader.is_empty());
+   }
+
+   #[test]
+   fn noop_and_truncated () {
+      assert_eq!(read(&[0x00, 0x00, 0x00, 0x01, 0xAA, 0x00, 0x00]).unwrap(), &[0xAA][..]);
+
+      // Missing end marker.
+      assert!(read(&[0x00, 0x01, 0xAA]).is_err());
+      assert!(read(&[0x00, 0x02, 0xAA]).is_err());
+
+      let mut bytes: &[u8] = &[0x00, 0x01, 0xAA, 0x00, 0x01, 0xBB, 0x00, 0x00];
+      assert!(smol::block_on(read_message(&mut bytes, 1)).is_err());
+   }
+}
@@ -0,0 +1,395 @@
+use super::{Client, Config, ConnectionState};
+use crate::error::{BoltError, BoltResult};
+use packstream_serde::message::AuthToken;
+use smol::lock::{Semaphore, SemaphoreGuardArc};
+use smol::Timer;
+use std::collections::VecDeque;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Options of the [`Pool`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolConfig {
+    max_size: usize,
+    acquisition_timeout: Duration,
+    idle_timeout: Option<Duration>,
+    max_lifetime: Option<Duration>,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 100,
+            acquisition_timeout: Duration::from_secs(60),
+            idle_timeout: None,
+            max_lifetime: Some(Duration::from_secs(3600)),
+        }
+    }
+}
+
+impl PoolConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maximum number of connections, idle or in use, held by the pool.
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size.max(1);
+        self
+    }
+
+    /// Time to get a live connection, including the wait when all of them are in use.
+    pub fn acquisition_timeout(mut self, acquisition_timeout: Duration) -> Self {
+        self.acquisition_timeout = acquisition_timeout;
+        self
+    }
+
+    /// Time after which an idle connection is closed instead of being reused.
+    pub fn idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Time after which a connection is closed once it is returned to the pool.
+    pub fn max_lifetime(mut self, max_lifetime: Duration) -> Self {
+        self.max_lifetime = Some(max_lifetime);
+        self
+    }
+}
+
+/// Counters of the connections managed by the [`Pool`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PoolMetrics {
+    /// Connections checked out at the moment.
+    pub in_use: usize,
+    /// Connections waiting in the pool at the moment.
+    pub idle: usize,
+    /// Connections opened since the pool was created.
+    pub created: u64,
+    /// Connections closed because they were defunct, idle or too old.
+    pub closed: u64,
+    /// Successful acquisitions.
+    pub acquired: u64,
+    /// Acquisitions which failed to get a connection in time.
+    pub timed_out: u64,
+    /// Total time spent by successful acquisitions.
+    pub acquisition_time: Duration,
+}
+
+/// Pool of authenticated connections to a single server.
+///
+/// The pool is cheap to clone and all clones share the connections, so it can be
+/// passed to any number of tasks. Connections returned to the pool are checked
+/// with RESET before they are handed out again.
+#[derive(Debug, Clone)]
+pub struct Pool {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    connection: Config,
+    config: PoolConfig,
+    // Permit per connection, idle or in use.
+    permits: Arc<Semaphore>,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    idle: VecDeque<Entry>,
+    metrics: PoolMetrics,
+}
+
+#[derive(Debug)]
+struct Entry {
+    client: Client,
+    created: Instant,
+    idle_since: Instant,
+}
+
+impl Entry {
+    fn is_too_old(&self, config: &PoolConfig, now: Instant) -> bool {
+        config.max_lifetime.is_some_and(|lifetime| now - self.created >= lifetime)
+    }
+
+    fn is_expired(&self, config: &PoolConfig, now: Instant) -> bool {
+        self.is_too_old(config, now)
+            || config.idle_timeout.is_some_and(|timeout| now - self.idle_since >= timeout)
+    }
+}
+
+impl Pool {
+    pub fn new(addr: impl Into<String>, auth: AuthToken, config: PoolConfig) -> Self {
+        Self::with_config(Config::builder(addr).auth(auth).build(), config)
+    }
+
+    /// Creates pool of connections opened with the options of the `connection` configuration.
+    pub fn with_config(connection: Config, config: PoolConfig) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                connection,
+                config,
+                permits: Arc::new(Semaphore::new(config.max_size)),
+                state: Mutex::new(State::default()),
+            }),
+        }
+    }
+
+    /// Checks out an idle connection which is still alive or opens a new one.
+    ///
+    /// The acquisition timeout limits the whole acquisition, including the wait when the pool
+    /// is full, the RESET of idle connections and the connecting to the server.
+    pub async fn acquire(&self) -> BoltResult<PooledClient> {
+        let start = Instant::now();
+        let timeout = self.inner.config.acquisition_timeout;
+
+        let permitted = AtomicBool::new(false);
+
+        let acquisition = async {
+            let permit = self.inner.permits.acquire_arc().await;
+            permitted.store(true, Ordering::Relaxed);
+            let entry = self.checkout().await?;
+            Ok((permit, entry))
+        };
+        let acquired = smol::future::or(acquisition, async {
+            Timer::after(timeout).await;
+            self.state().metrics.timed_out += 1;
+            // Only a full pool is reported as such, a server which does not answer
+            // is as unavailable as one which refuses connections.
+            Err(if permitted.load(Ordering::Relaxed) {
+                BoltError::timeout(format!("Failed to get a live connection to the server within {:?}", timeout))
+            } else {
+                BoltError::pool_timeout(format!("Failed to acquire a connection from the pool within {:?}", timeout))
+            })
+        }).await;
+
+        let (permit, mut entry) = acquired?;
+        // The previous user of the connection may have changed its options or kept a cancel handle.
+        entry.client.set_database(self.inner.connection.database().map(String::from));
+        entry.client.set_query_timeout(self.inner.connection.query_timeout());
+        entry.client.revoke_cancel_handles();
+
+        let mut state = self.state();
+        state.metrics.acquired += 1;
+        state.metrics.acquisition_time += start.elapsed();
+        state.metrics.in_use += 1;
+        drop(state);
+
+        Ok(PooledClient { entry: Some(entry), pool: self.clone(), _permit: permit })
+    }
+
+    pub fn metrics(&self) -> PoolMetrics {
+        let state = self.state();
+        PoolMetrics { idle: state.idle.len(), ..state.metrics }
+    }
+
+    /// Finds an idle connection which passes RESET, opening a new one if there is none.
+    async fn checkout(&self) -> BoltResult<Entry> {
+        while let Some(mut entry) = self.pop_idle() {
+            match entry.client.reset().await {
+                Ok(()) => return Ok(entry),
+                Err(error) => {
+                    log::debug!("Closing pooled connection which failed RESET: {}", error);
+                    self.state().metrics.closed += 1;
+                }
+            }
+        }
+
+        let client = Client::connect_with_config(&self.inner.connection).await?;
+        self.state().metrics.created += 1;
+
+        let now = Instant::now();
+        Ok(Entry { client, created: now, idle_since: now })
+    }
+
+    /// Takes the most recently used idle connection, closing the expired ones.
+    fn pop_idle(&self) -> Option<Entry> {
+        let now = Instant::now();
+        let mut state = self.state();
+
+        let before = state.idle.len();
+        state.idle.retain(|entry| !entry.is_expired(&self.inner.config, now));
+        state.metrics.closed += (before - state.idle.len()) as u64;
+
+        state.idle.pop_back()
+    }
+
+    fn release(&self, mut entry: Entry) {
+        let now = Instant::now();
+        let mut state = self.state();
+        state.metrics.in_use -= 1;
+
+        if entry.client.state() == ConnectionState::Defunct || entry.is_too_old(&self.inner.config, now) {
+            state.metrics.closed += 1;
+            return;
+        }
+
+        entry.idle_since = now;
+        state.idle.push_back(entry);
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        // The state is left consistent by every critical section, a panic in one
+        // of them does not make it unusable.
+        self.inner.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Connection checked out of the [`Pool`], returned to it when dropped.
+///
+/// Defunct connections and connections older than the maximum lifetime are closed
+/// instead of being returned.
+#[derive(Debug)]
+pub struct PooledClient {
+    entry: Option<Entry>,
+    pool: Pool,
+    // Released after the connection is back among the idle ones.
+    _permit: SemaphoreGuardArc,
+}
+
+impl PooledClient {
+    /// Address of the server the connection is open to.
+    pub fn address(&self) -> &str {
+        self.pool.inner.connection.address()
+    }
+}
+
+impl Deref for PooledClient {
+    type Target = Client;
+
+    fn deref(&self) -> &Client {
+        &self.entry.as_ref().expect("connection is present until dropped").client
+    }
+}
+
+impl DerefMut for PooledClient {
+    fn deref_mut(&mut self) -> &mut Client {
+        &mut self.entry.as_mut().expect("connection is present until dropped").client
+    }
+}
+
+impl Drop for PooledClient {
+    fn drop(&mut self) {
+        if let Some(entry) = self.entry.take() {
+            self.pool.release(entry);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bolt::stub::{hello, success, Step, Stub};
+    use crate::error::ErrorCode;
+    use packstream_serde::constants::message::*;
+
+    fn pool(stub: &Stub, config: PoolConfig) -> Pool {
+        Pool::new(stub.addr().to_string(), AuthToken::none(), config)
+    }
+
+    #[test]
+    fn reuses_connections() {
+        let mut script = hello();
+        script.extend(vec![Step::Recv(RESET), success("{}")]);
+        let stub = Stub::start([4, 4], script);
+
+        smol::block_on(async {
+            let pool = pool(&stub, PoolConfig::new());
+
+            let client = pool.acquire().await.unwrap();
+            assert_eq!(pool.metrics().in_use, 1);
+            drop(client);
+            assert_eq!(pool.metrics().idle, 1);
+
+            let client = pool.acquire().await.unwrap();
+            assert_eq!(client.state(), ConnectionState::Ready);
+
+            let metrics = pool.metrics();
+            assert_eq!((metrics.created, metrics.acquired, metrics.in_use, metrics.idle), (1, 2, 1, 0));
+        });
+        stub.finish();
+    }
+
+    #[test]
+    fn acquisition_timeout() {
+        let stub = Stub::start([4, 4], hello());
+
+        smol::block_on(async {
+            let config = PoolConfig::new().max_size(1).acquisition_timeout(Duration::from_millis(10));
+            let pool = pool(&stub, config);
+
+            let client = pool.acquire().await.unwrap();
+            let error = pool.acquire().await.unwrap_err();
+            assert!(matches!(error.code(), ErrorCode::PoolTimeout(_)));
+            assert_eq!(pool.metrics().timed_out, 1);
+            drop(client);
+        });
+        stub.finish();
+    }
+
+    #[test]
+    fn acquisition_timeout_limits_reset() {
+        let mut script = hello();
+        script.extend(vec![Step::Recv(RESET), Step::Sleep(Duration::from_millis(200))]);
+        let stub = Stub::start([4, 4], script);
+
+        smol::block_on(async {
+            let pool = pool(&stub, PoolConfig::new().acquisition_timeout(Duration::from_millis(50)));
+            drop(pool.acquire().await.unwrap());
+
+            let start = Instant::now();
+            let error = pool.acquire().await.unwrap_err();
+            assert!(matches!(error.code(), ErrorCode::Timeout(_)));
+            assert!(start.elapsed() < Duration::from_millis(200));
+            assert_eq!(pool.metrics().timed_out, 1);
+        });
+        stub.finish();
+    }
+
+    #[test]
+    fn discards_broken_and_idle_connections() {
+        // The first connection is closed by the server, the second one is closed
+        // by the pool after the idle timeout.
+        let stub = Stub::start_many([4, 4], vec![hello(), hello(), hello()]);
+
+        smol::block_on(async {
+            let pool = pool(&stub, PoolConfig::new().idle_timeout(Duration::from_millis(50)));
+
+            drop(pool.acquire().await.unwrap());
+            let client = pool.acquire().await.unwrap();
+            drop(client);
+
+            Timer::after(Duration::from_millis(50)).await;
+            let client = pool.acquire().await.unwrap();
+            assert_eq!(client.state(), ConnectionState::Ready);
+
+            let metrics = pool.metrics();
+            assert_eq!((metrics.created, metrics.closed), (3, 2));
+        });
+        stub.finish();
+    }
+
+    #[test]
+    fn shared_between_tasks() {
+        let stub = Stub::start_many([4, 4], vec![hello(), hello()]);
+
+        smol::block_on(async {
+            let pool = pool(&stub, PoolConfig::new().max_size(2));
+
+            let task = {
+                let pool = pool.clone();
+                smol::spawn(async move { pool.acquire().await })
+            };
+            let first = task.await.unwrap();
+            let second = pool.acquire().await.unwrap();
+
+            assert_eq!(pool.metrics().in_use, 2);
+            drop((first, second));
+            assert_eq!(pool.metrics().idle, 2);
+        });
+        stub.finish();
+    }
+}
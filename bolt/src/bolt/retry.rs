@@ -0,0 +1,182 @@
+use super::{AccessMode, Client, ConnectionState, Transaction, TransactionConfig};
+use crate::error::BoltResult;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+
+/// Future of the unit of work run by [`Client::read_transaction`] and [`Client::write_transaction`].
+pub type WorkFuture<'t, T> = Pin<Box<dyn Future<Output = BoltResult<T>> + Send + 't>>;
+
+/// Retries of managed transactions with exponential backoff.
+///
+/// The delay before the next attempt is multiplied by `multiplier` after every attempt,
+/// up to `max_delay`, and randomized by up to `jitter` of its value in both directions.
+/// No attempt is started after `max_retry_time` since the first one, and no delay
+/// extends past it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    max_retry_time: Duration,
+    initial_delay: Duration,
+    max_delay: Duration,
+    multiplier: f64,
+    jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retry_time: Duration::from_secs(30),
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_retry_time(mut self, max_retry_time: Duration) -> Self {
+        self.max_retry_time = max_retry_time;
+        self
+    }
+
+    pub fn initial_delay(mut self, initial_delay: Duration) -> Self {
+        self.initial_delay = initial_delay;
+        self
+    }
+
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier.max(1.0);
+        self
+    }
+
+    /// Fraction of the delay, between 0 and 1, by which the delay is randomized.
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    fn jittered(&self, delay: Duration) -> Duration {
+        let factor = 1.0 + self.jitter * (2.0 * fastrand::f64() - 1.0);
+        self.scaled(delay, factor)
+    }
+
+    fn next_delay(&self, delay: Duration) -> Duration {
+        self.scaled(delay, self.multiplier)
+    }
+
+    /// Multiplies the delay, limiting the result to `max_delay`.
+    fn scaled(&self, delay: Duration, factor: f64) -> Duration {
+        Duration::try_from_secs_f64(delay.as_secs_f64() * factor)
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+}
+
+impl Client {
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
+    /// Runs the unit of work in a read transaction, see [`Client::write_transaction`].
+    pub async fn read_transaction<T, F>(&mut self, config: TransactionConfig, work: F) -> BoltResult<T>
+    where
+        F: for<'t, 'c> FnMut(&'t mut Transaction<'c>) -> WorkFuture<'t, T>,
+    {
+        self.managed_transaction(config.mode(AccessMode::Read), work).await
+    }
+
+    /// Runs the unit of work in a write transaction, which is committed if the work
+    /// returns `Ok` and rolled back otherwise. The whole transaction is retried
+    /// according to the [`RetryPolicy`] when it fails with a retryable error.
+    ///
+    /// ```no_run
+    /// # use bolt::Client;
+    /// # use bolt::TransactionConfig;
+    /// # async fn example(client: &mut Client) -> bolt::error::BoltResult<()> {
+    /// let count = client.write_transaction(TransactionConfig::new(), |tx| Box::pin(async move {
+    ///     tx.run("CREATE (:Person)", Default::default()).await?.consume().await?;
+    ///     Ok(1)
+    /// })).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_transaction<T, F>(&mut self, config: TransactionConfig, work: F) -> BoltResult<T>
+    where
+        F: for<'t, 'c> FnMut(&'t mut Transaction<'c>) -> WorkFuture<'t, T>,
+    {
+        self.managed_transaction(config.mode(AccessMode::Write), work).await
+    }
+
+    async fn managed_transaction<T, F>(&mut self, config: TransactionConfig, mut work: F) -> BoltResult<T>
+    where
+        F: for<'t, 'c> FnMut(&'t mut Transaction<'c>) -> WorkFuture<'t, T>,
+    {
+        let policy = self.retry_policy;
+        let start = Instant::now();
+        let mut delay = policy.initial_delay;
+
+        loop {
+            match self.attempt(config.clone(), &mut work).await {
+                Err(error)
+                    if error.is_retryable()
+                        && self.state() != ConnectionState::Defunct
+                        && start.elapsed() < policy.max_retry_time =>
+                {
+                    let remaining = policy.max_retry_time.saturating_sub(start.elapsed());
+                    let delay_with_jitter = policy.jittered(delay).min(remaining);
+                    log::warn!("Transaction failed with {}, retrying in {:?}", error, delay_with_jitter);
+                    smol::Timer::after(delay_with_jitter).await;
+                    delay = policy.next_delay(delay);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn attempt<T, F>(&mut self, config: TransactionConfig, work: &mut F) -> BoltResult<T>
+    where
+        F: for<'t, 'c> FnMut(&'t mut Transaction<'c>) -> WorkFuture<'t, T>,
+    {
+        let mut tx = self.begin_transaction(config).await?;
+
+        match work(&mut tx).await {
+            Ok(value) => {
+                tx.commit().await?;
+                Ok(value)
+            }
+            Err(error) => {
+                // The error of the unit of work is returned even if the rollback fails,
+                // the connection recovers before the next request either way.
+                let _ = tx.rollback().await;
+                Err(error)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delay_is_limited() {
+        let policy = RetryPolicy::new().multiplier(1e300).max_delay(Duration::from_secs(5));
+
+        assert_eq!(policy.next_delay(Duration::from_secs(1)), Duration::from_secs(5));
+        assert_eq!(policy.next_delay(Duration::MAX), Duration::from_secs(5));
+        assert!(policy.jittered(Duration::from_secs(5)) <= Duration::from_secs(5));
+
+        let policy = RetryPolicy::new().multiplier(2.0).jitter(0.0);
+        assert_eq!(policy.next_delay(Duration::from_secs(1)), Duration::from_secs(2));
+        assert_eq!(policy.jittered(Duration::from_secs(1)), Duration::from_secs(1));
+    }
+}
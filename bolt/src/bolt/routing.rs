@@ -0,0 +1,495 @@
+use super::{AccessMode, Client, Config, Pool, PoolConfig, PooledClient};
+use crate::error::{BoltError, BoltResult, ErrorCode, Neo4jError};
+use futures_lite::StreamExt;
+use packstream_serde::message::{AuthToken, Route};
+use packstream_serde::{to_bytes, Map, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Servers of a cluster which serve a database, valid for the time to live.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutingTable {
+    pub routers: Vec<String>,
+    pub readers: Vec<String>,
+    pub writers: Vec<String>,
+    pub ttl: Duration,
+    fetched: Instant,
+}
+
+impl RoutingTable {
+    /// Reads the table from the `ttl` and `servers` entries sent by the server.
+    fn from_map(mut table: Map<String, Value>) -> BoltResult<Self> {
+        let ttl = match table.remove("ttl") {
+            Some(Value::I64(ttl)) => Duration::from_secs(u64::try_from(ttl).unwrap_or(0)),
+            _ => return Err(BoltError::create("Routing table has no ttl")),
+        };
+        let servers = match table.remove("servers") {
+            Some(Value::List(servers)) => servers,
+            _ => return Err(BoltError::create("Routing table has no servers")),
+        };
+
+        let mut routing_table = Self {
+            routers: Vec::new(),
+            readers: Vec::new(),
+            writers: Vec::new(),
+            ttl,
+            fetched: Instant::now(),
+        };
+
+        for server in servers {
+            let mut server = match server {
+                Value::Map(server) => server,
+                value => return Err(BoltError::create(format!("Expected server map, got {}", value))),
+            };
+
+            let addresses = match server.remove("addresses") {
+                Some(Value::List(addresses)) => addresses
+                    .into_iter()
+                    .map(|address| match address {
+                        Value::String(address) => Ok(address),
+                        value => Err(BoltError::create(format!("Expected server address, got {}", value))),
+                    })
+                    .collect::<BoltResult<Vec<String>>>()?,
+                _ => return Err(BoltError::create("Server has no addresses")),
+            };
+
+            match server.remove("role") {
+                Some(Value::String(role)) if role == "ROUTE" => routing_table.routers.extend(addresses),
+                Some(Value::String(role)) if role == "READ" => routing_table.readers.extend(addresses),
+                Some(Value::String(role)) if role == "WRITE" => routing_table.writers.extend(addresses),
+                // Roles added by future versions are not used.
+                _ => (),
+            }
+        }
+
+        Ok(routing_table)
+    }
+
+    pub fn servers(&self, mode: AccessMode) -> &[String] {
+        match mode {
+            AccessMode::Read => &self.readers,
+            AccessMode::Write => &self.writers,
+        }
+    }
+
+    /// Whether the table expired or lacks the servers needed for the access mode.
+    pub fn is_stale(&self, mode: AccessMode) -> bool {
+        self.fetched.elapsed() >= self.ttl || self.routers.is_empty() || self.servers(mode).is_empty()
+    }
+
+    fn forget(&mut self, address: &str) {
+        self.routers.retain(|server| server != address);
+        self.readers.retain(|server| server != address);
+        self.writers.retain(|server| server != address);
+    }
+}
+
+impl Client {
+    /// Fetches the routing table of the database, or of the default database when `None`.
+    ///
+    /// Uses ROUTE since Bolt 4.3 and the routing procedure before. The context is sent
+    /// to the server as is, it usually holds the `address` the driver was created with.
+    pub async fn routing_table(
+        &mut self,
+        context: Map<String, Value>,
+        database: Option<&str>,
+    ) -> BoltResult<RoutingTable> {
+        let version = (self.version().major, self.version().minor);
+
+        if version >= (4, 3) {
+            let database = database.map(|database| Value::String(String::from(database)));
+            let db = match (version >= (4, 4), database) {
+                (true, Some(database)) => Value::Map(Map::from([(String::from("db"), database)])),
+                (true, None) => Value::Map(Map::new()),
+                (false, database) => database.unwrap_or(Value::Null),
+            };
+
+            self.ready().await?;
+            self.request(&[&to_bytes(&Route { routing: context, bookmarks: Vec::new(), db })?]).await?;
+
+            return match self.summary().await?.metadata.remove("rt") {
+                Some(Value::Map(table)) => RoutingTable::from_map(table),
+                _ => Err(self.violation("ROUTE did not return a routing table")),
+            };
+        }
+
+        let mut parameters = Map::from([(String::from("context"), Value::Map(context))]);
+        let statement = if version >= (4, 0) {
+            let database = database.map_or(Value::Null, |database| Value::String(String::from(database)));
+            parameters.insert(String::from("database"), database);
+            "CALL dbms.routing.getRoutingTable($context, $database)"
+        } else if database.is_some() {
+            return Err(BoltError::create("Databases require Bolt 4"));
+        } else {
+            "CALL dbms.cluster.routing.getRoutingTable($context)"
+        };
+
+        let mut stream = self.run(statement, parameters).await?;
+        let fields = stream.fields().to_vec();
+        let record = match stream.next().await {
+            Some(record) => record?,
+            None => return Err(BoltError::create("Routing procedure returned no table")),
+        };
+        stream.consume().await?;
+
+        RoutingTable::from_map(fields.into_iter().zip(record.fields).collect())
+    }
+}
+
+/// Routes sessions to the servers of a cluster, as `neo4j://` URIs do.
+///
+/// Routing tables are fetched per database from the routers and kept for their
+/// time to live. Reads go to the least busy reader and writes to the leader.
+/// Servers which cannot be connected to or do not answer within the connect and read
+/// timeouts are removed from every table, the table is fetched again once it has no
+/// servers left for the access mode.
+///
+/// The router does not see errors of queries run on the acquired connections,
+/// pass them to [`Router::handle_error`] so that the tables stay up to date.
+#[derive(Debug, Clone)]
+pub struct Router {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    connection: Config,
+    config: PoolConfig,
+    context: Map<String, Value>,
+    tables: Mutex<HashMap<Option<String>, RoutingTable>>,
+    pools: Mutex<HashMap<String, Pool>>,
+}
+
+impl Router {
+    /// Creates router which fetches the first routing table from the `seed` server.
+    pub fn new(seed: impl Into<String>, auth: AuthToken, config: PoolConfig) -> Self {
+        Self::with_config(Config::builder(seed).auth(auth).build(), config)
+    }
+
+    /// Creates router which fetches the first routing table from the address of the
+    /// `connection` configuration and sends its routing context with every request.
+    /// The database of the configuration is used when none is given.
+    pub fn with_config(connection: Config, config: PoolConfig) -> Self {
+        let mut context = connection.routing_context().clone();
+        context.insert(String::from("address"), Value::String(String::from(connection.address())));
+
+        Self {
+            inner: Arc::new(Inner {
+                connection,
+                config,
+                context,
+                tables: Mutex::new(HashMap::new()),
+                pools: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    /// Acquires connection to a server of the database which serves the access mode.
+    pub async fn acquire(&self, mode: AccessMode, database: Option<&str>) -> BoltResult<PooledClient> {
+        let database = database.or_else(|| self.inner.connection.database());
+        let mut servers = self.routing_table(mode, database).await?.servers(mode).to_vec();
+
+        if mode == AccessMode::Read {
+            // Stable sort keeps the order of the table among equally busy readers.
+            let pools = self.pools();
+            servers.sort_by_key(|server| pools.get(server).map_or(0, |pool| pool.metrics().in_use));
+        }
+
+        let mut last_error = None;
+        for server in servers {
+            match self.pool(&server).acquire().await {
+                Ok(mut client) => {
+                    client.set_database(database.map(String::from));
+                    return Ok(client);
+                }
+                Err(error) if is_unavailable(&error) => {
+                    log::warn!("Removing unavailable server {}: {}", server, error);
+                    self.forget(&server);
+                    last_error = Some(error);
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            let database = database.unwrap_or("<default>");
+            BoltError::create(format!("No server of database {} serves {:?}", database, mode))
+        }))
+    }
+
+    /// Returns the routing table of the database, fetching it again when it is stale.
+    pub async fn routing_table(&self, mode: AccessMode, database: Option<&str>) -> BoltResult<RoutingTable> {
+        let database = database.or_else(|| self.inner.connection.database());
+        let key = database.map(String::from);
+
+        let routers = match self.tables().get(&key) {
+            Some(table) if !table.is_stale(mode) => return Ok(table.clone()),
+            Some(table) => table.routers.clone(),
+            None => Vec::new(),
+        };
+
+        let table = self.fetch(routers, database).await?;
+        self.tables().insert(key, table.clone());
+        Ok(table)
+    }
+
+    /// Removes the server from every routing table and closes its connections.
+    pub fn forget(&self, address: &str) {
+        for table in self.tables().values_mut() {
+            table.forget(address);
+        }
+        self.pools().remove(address);
+    }
+
+    /// Updates the routing tables after a request to the server failed with the error.
+    ///
+    /// Unreachable servers are removed from every table and servers which are not
+    /// the leader anymore are removed from the writers.
+    pub fn handle_error(&self, address: &str, error: &BoltError) {
+        match error.neo4j() {
+            Some(neo4j) if is_not_leader(neo4j) => self.forget_writer(address),
+            Some(_) => {}
+            None if is_unavailable(error) => self.forget(address),
+            None => {}
+        }
+    }
+
+    /// Removes the server from the writers, as it is not the leader anymore.
+    pub fn forget_writer(&self, address: &str) {
+        for table in self.tables().values_mut() {
+            table.writers.retain(|server| server != address);
+        }
+    }
+
+    /// Asks the routers, and the seed server at last, for the routing table.
+    async fn fetch(&self, mut routers: Vec<String>, database: Option<&str>) -> BoltResult<RoutingTable> {
+        let seed = self.inner.connection.address();
+        if !routers.iter().any(|router| router == seed) {
+            routers.push(String::from(seed));
+        }
+
+        let mut last_error = None;
+        for router in routers {
+            let result = match self.pool(&router).acquire().await {
+                Ok(mut client) => client.routing_table(self.inner.context.clone(), database).await,
+                Err(error) => Err(error),
+            };
+
+            match result {
+                Ok(table) => return Ok(table),
+                // Failures such as a missing database are the same on every router.
+                Err(error) if error.neo4j().is_some() && !error.is_retryable() => return Err(error),
+                Err(error) => {
+                    log::warn!("Failed to fetch routing table from {}: {}", router, error);
+                    self.forget(&router);
+                    last_error = Some(error);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| BoltError::create("No router is available")))
+    }
+
+    fn pool(&self, address: &str) -> Pool {
+        self.pools()
+            .entry(String::from(address))
+            .or_insert_with(|| Pool::with_config(self.inner.connection.with_address(address), self.inner.config))
+            .clone()
+    }
+
+    fn tables(&self) -> MutexGuard<'_, HashMap<Option<String>, RoutingTable>> {
+        self.inner.tables.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn pools(&self) -> MutexGuard<'_, HashMap<String, Pool>> {
+        self.inner.pools.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Whether the server could not be reached or did not answer in time, as opposed to
+/// refusing the request. Pools with all connections in use and slow queries do not
+/// make the server unavailable.
+fn is_unavailable(error: &BoltError) -> bool {
+    matches!(error.code(), ErrorCode::IO(_) | ErrorCode::Timeout(_))
+}
+
+/// Whether the server refused a write as it is not the leader.
+fn is_not_leader(error: &Neo4jError) -> bool {
+    matches!(
+        error.code.as_str(),
+        "Neo.ClientError.Cluster.NotALeader" | "Neo.ClientError.General.ForbiddenOnReadOnlyDatabase"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bolt::stub::{hello, record, success, Step, Stub};
+    use packstream_serde::constants::message::*;
+    use packstream_serde::from_text;
+    use std::net::{SocketAddr, TcpListener};
+
+    fn servers(routers: &[SocketAddr], readers: &[SocketAddr], writers: &[SocketAddr]) -> String {
+        let server = |addresses: &[SocketAddr], role: &str| {
+            let addresses: Vec<String> = addresses.iter().map(|address| format!("{:?}", address.to_string())).collect();
+            format!("{{addresses: [{}], role: {:?}}}", addresses.join(", "), role)
+        };
+
+        format!("[{}, {}, {}]", server(routers, "ROUTE"), server(readers, "READ"), server(writers, "WRITE"))
+    }
+
+    /// Address which refuses connections, as no server can listen on port 0.
+    fn unavailable() -> SocketAddr {
+        SocketAddr::from(([127, 0, 0, 1], 0))
+    }
+
+    #[test]
+    fn route_message() {
+        let reader = Stub::start([4, 4], hello());
+        let writer = Stub::start([4, 4], hello());
+
+        let table = servers(&[writer.addr()], &[reader.addr()], &[writer.addr()]);
+        let mut script = hello();
+        script.extend(vec![
+            Step::Recv(ROUTE),
+            success(&format!(r#"{{rt: {{ttl: 300, db: "movies", servers: {}}}}}"#, table)),
+        ]);
+        let seed = Stub::start([4, 4], script);
+
+        smol::block_on(async {
+            let router = Router::new(seed.addr().to_string(), AuthToken::none(), PoolConfig::new());
+
+            let client = router.acquire(AccessMode::Read, Some("movies")).await.unwrap();
+            assert_eq!(client.address(), reader.addr().to_string());
+
+            let client = router.acquire(AccessMode::Write, Some("movies")).await.unwrap();
+            assert_eq!(client.address(), writer.addr().to_string());
+
+            let table = router.routing_table(AccessMode::Write, Some("movies")).await.unwrap();
+            assert_eq!(table.routers, vec![writer.addr().to_string()]);
+            assert_eq!(table.ttl, Duration::from_secs(300));
+        });
+
+        seed.finish();
+        reader.finish();
+        writer.finish();
+    }
+
+    #[test]
+    fn routing_procedure() {
+        let reader = Stub::start([4, 1], hello());
+
+        let table = servers(&[reader.addr()], &[reader.addr()], &[]);
+        let mut script = hello();
+        script.extend(vec![
+            Step::Recv(RUN), Step::Recv(PULL),
+            success(r#"{fields: ["ttl", "servers"]}"#),
+            record(vec![Value::I64(300), from_text(&table).unwrap()]),
+            success("{}"),
+        ]);
+        let seed = Stub::start([4, 1], script);
+
+        smol::block_on(async {
+            let router = Router::new(seed.addr().to_string(), AuthToken::none(), PoolConfig::new());
+
+            let client = router.acquire(AccessMode::Read, None).await.unwrap();
+            assert_eq!(client.address(), reader.addr().to_string());
+
+            let table = router.routing_table(AccessMode::Read, None).await.unwrap();
+            assert!(table.writers.is_empty());
+            assert!(table.is_stale(AccessMode::Write));
+        });
+
+        seed.finish();
+        reader.finish();
+    }
+
+    #[test]
+    fn unavailable_servers_are_forgotten() {
+        let reader = Stub::start([4, 4], hello());
+        let unavailable = unavailable();
+
+        let table = servers(&[reader.addr()], &[unavailable, reader.addr()], &[reader.addr()]);
+        let mut script = hello();
+        script.extend(vec![Step::Recv(ROUTE), success(&format!("{{rt: {{ttl: 300, servers: {}}}}}", table))]);
+        let seed = Stub::start([4, 4], script);
+
+        smol::block_on(async {
+            let router = Router::new(seed.addr().to_string(), AuthToken::none(), PoolConfig::new());
+
+            let client = router.acquire(AccessMode::Read, None).await.unwrap();
+            assert_eq!(client.address(), reader.addr().to_string());
+
+            let table = router.routing_table(AccessMode::Read, None).await.unwrap();
+            assert_eq!(table.readers, vec![reader.addr().to_string()]);
+        });
+
+        seed.finish();
+        reader.finish();
+    }
+
+    #[test]
+    fn silent_servers_are_forgotten() {
+        // Accepts connections into the backlog but never answers the handshake.
+        let silent = TcpListener::bind("127.0.0.1:0").unwrap();
+        let silent_addr = silent.local_addr().unwrap();
+        let reader = Stub::start([4, 4], hello());
+
+        let table = servers(&[reader.addr()], &[silent_addr, reader.addr()], &[reader.addr()]);
+        let mut script = hello();
+        script.extend(vec![Step::Recv(ROUTE), success(&format!("{{rt: {{ttl: 300, servers: {}}}}}", table))]);
+        let seed = Stub::start([4, 4], script);
+
+        smol::block_on(async {
+            let config = Config::builder(seed.addr().to_string())
+                .auth(AuthToken::none())
+                .connect_timeout(Duration::from_millis(50))
+                .build();
+            let router = Router::with_config(config, PoolConfig::new());
+
+            let client = router.acquire(AccessMode::Read, None).await.unwrap();
+            assert_eq!(client.address(), reader.addr().to_string());
+
+            let table = router.routing_table(AccessMode::Read, None).await.unwrap();
+            assert_eq!(table.readers, vec![reader.addr().to_string()]);
+        });
+
+        seed.finish();
+        reader.finish();
+    }
+
+    #[test]
+    fn query_errors_update_tables() {
+        let server = Stub::start([4, 4], hello());
+        let (address, closed) = (server.addr().to_string(), unavailable().to_string());
+
+        let table = servers(&[server.addr()], &[server.addr(), unavailable()], &[server.addr()]);
+        let mut script = hello();
+        script.extend(vec![Step::Recv(ROUTE), success(&format!("{{rt: {{ttl: 300, servers: {}}}}}", table))]);
+        let seed = Stub::start([4, 4], script);
+
+        smol::block_on(async {
+            let router = Router::new(seed.addr().to_string(), AuthToken::none(), PoolConfig::new());
+            let client = router.acquire(AccessMode::Write, None).await.unwrap();
+            drop(client);
+
+            let not_leader = BoltError::create(Neo4jError::new("Neo.ClientError.Cluster.NotALeader", ""));
+            router.handle_error(&address, &not_leader);
+            let syntax = BoltError::create(Neo4jError::new("Neo.ClientError.Statement.SyntaxError", ""));
+            router.handle_error(&address, &syntax);
+            router.handle_error(&address, &BoltError::query_timeout("Query did not complete"));
+            router.handle_error(&address, &BoltError::pool_timeout("All connections are in use"));
+            let refused = BoltError::create(std::io::Error::from(std::io::ErrorKind::ConnectionRefused));
+            router.handle_error(&closed, &refused);
+
+            let table = router.inner.tables.lock().unwrap()[&None].clone();
+            assert!(table.writers.is_empty());
+            assert_eq!(table.readers, vec![address.clone()]);
+            assert_eq!(table.routers, vec![address.clone()]);
+        });
+
+        seed.finish();
+        server.finish();
+    }
+}
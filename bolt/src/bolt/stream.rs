@@ -0,0 +1,112 @@
+use super::{Client, Streamed};
+use crate::error::BoltResult;
+use futures_lite::stream::{Stream, StreamExt};
+use packstream_serde::message::Record;
+use packstream_serde::{Map, Value};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+type Fetch<'a> = Pin<Box<dyn Future<Output = (&'a mut Client, BoltResult<Event>)> + Send + 'a>>;
+
+enum State<'a> {
+    Ready(&'a mut Client),
+    Fetching(Fetch<'a>),
+    Done,
+}
+
+/// Records of a query returned by [`Client::run`], received as they arrive.
+///
+/// The client stays borrowed until the stream is dropped. Records which were not
+/// consumed are discarded before the next request of the client.
+///
+/// Dropping the stream while it waits for a record, e.g. when `next()` loses a race
+/// against a timer, interrupts the read of the message and leaves the client
+/// [`Defunct`](super::ConnectionState::Defunct).
+pub struct RecordStream<'a> {
+    state: State<'a>,
+    fields: Vec<String>,
+    summary: Option<Map<String, Value>>,
+}
+
+impl<'a> RecordStream<'a> {
+    pub(super) fn new(client: &'a mut Client, fields: Vec<String>) -> Self {
+        Self {
+            state: State::Ready(client),
+            fields,
+            summary: None,
+        }
+    }
+
+    /// Names of the fields of every record.
+    pub fn fields(&self) -> &[String] {
+        &self.fields
+    }
+
+    /// Metadata of the final SUCCESS message, available once the stream ended.
+    pub fn summary(&self) -> Option<&Map<String, Value>> {
+        self.summary.as_ref()
+    }
+
+    /// Discards the remaining records and returns the summary metadata.
+    pub async fn consume(mut self) -> BoltResult<Map<String, Value>> {
+        while let Some(record) = self.next().await {
+            record?;
+        }
+
+        Ok(self.summary.take().unwrap_or_default())
+    }
+}
+
+enum Event {
+    Record(Record),
+    Summary(Map<String, Value>),
+}
+
+async fn fetch(client: &mut Client) -> (&mut Client, BoltResult<Event>) {
+    let event = match client.next_message(true).await {
+        Ok(Streamed::Record(record)) => client.decode_record(&record).map(Event::Record),
+        Ok(Streamed::Summary(success)) => Ok(Event::Summary(success.metadata)),
+        Err(error) => Err(error),
+    };
+    (client, event)
+}
+
+impl<'a> Stream for RecordStream<'a> {
+    type Item = BoltResult<Record>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match std::mem::replace(&mut self.state, State::Done) {
+                State::Ready(client) => self.state = State::Fetching(Box::pin(fetch(client))),
+                State::Fetching(mut future) => {
+                    return match future.as_mut().poll(cx) {
+                        Poll::Pending => {
+                            self.state = State::Fetching(future);
+                            Poll::Pending
+                        }
+                        Poll::Ready((client, Ok(Event::Record(record)))) => {
+                            self.state = State::Ready(client);
+                            Poll::Ready(Some(Ok(record)))
+                        }
+                        Poll::Ready((_, Ok(Event::Summary(metadata)))) => {
+                            self.summary = Some(metadata);
+                            Poll::Ready(None)
+                        }
+                        Poll::Ready((_, Err(error))) => Poll::Ready(Some(Err(error))),
+                    };
+                }
+                State::Done => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+impl<'a> std::fmt::Debug for RecordStream<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("RecordStream")
+            .field("fields", &self.fields)
+            .field("summary", &self.summary)
+            .finish()
+    }
+}
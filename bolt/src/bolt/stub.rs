@@ -0,0 +1,147 @@
+//! Scripted server used by tests which do not need a database.
+use packstream_serde::constants::message::HELLO;
+use packstream_serde::message::{Failure, Ignored, Record, Success};
+use packstream_serde::packstream::EmptyPackstreamStructure;
+use packstream_serde::{from_text, to_bytes, Value};
+use futures_rustls::rustls::{ServerConfig, ServerConnection, StreamOwned};
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Step of the script played by the server.
+pub enum Step {
+    /// Expects a message with the signature from the client.
+    Recv(u8),
+    /// Sends the message to the client.
+    Send(Vec<u8>),
+    /// Stays silent for the duration, e.g. to let a timeout of the client expire.
+    Sleep(Duration),
+}
+
+pub struct Stub {
+    addr: SocketAddr,
+    handle: JoinHandle<()>,
+}
+
+impl Stub {
+    /// Accepts a single connection, agrees on the `[major, minor]` version
+    /// and plays the script. The connection is closed once the script ends.
+    pub fn start(version: [u8; 2], script: Vec<Step>) -> Self {
+        Self::start_many(version, vec![script])
+    }
+
+    /// Plays the scripts on connections accepted one after another.
+    pub fn start_many(version: [u8; 2], scripts: Vec<Vec<Step>>) -> Self {
+        Self::spawn(version, scripts, None)
+    }
+
+    /// Plays the script over TLS with the server configuration.
+    pub fn start_tls(version: [u8; 2], script: Vec<Step>, config: ServerConfig) -> Self {
+        Self::spawn(version, vec![script], Some(Arc::new(config)))
+    }
+
+    fn spawn(version: [u8; 2], scripts: Vec<Vec<Step>>, tls: Option<Arc<ServerConfig>>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("stub to bind");
+        let addr = listener.local_addr().expect("stub to have an address");
+
+        let handle = thread::spawn(move || {
+            for script in scripts {
+                let (stream, _) = listener.accept().expect("stub to accept a connection");
+
+                match &tls {
+                    Some(config) => {
+                        let connection = ServerConnection::new(config.clone()).expect("TLS connection");
+                        play(&mut StreamOwned::new(connection, stream), version, script);
+                    }
+                    None => play(&mut &stream, version, script),
+                }
+            }
+        });
+
+        Self { addr, handle }
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Waits for the script to end, failing if the client did not follow it.
+    pub fn finish(self) {
+        self.handle.join().expect("client to follow the script");
+    }
+}
+
+fn play(stream: &mut (impl Read + Write), version: [u8; 2], script: Vec<Step>) {
+    let mut handshake = [0; 20];
+    stream.read_exact(&mut handshake).expect("handshake");
+    assert_eq!(&handshake[0..4], &[0x60, 0x60, 0xB0, 0x17]);
+    stream.write_all(&[0, 0, version[1], version[0]]).expect("handshake reply");
+
+    for step in script {
+        match step {
+            Step::Recv(signature) => {
+                let message = read_message(stream);
+                assert_eq!(message[1], signature, "unexpected message {:x?}", message);
+            }
+            Step::Send(message) => write_message(stream, &message),
+            Step::Sleep(duration) => thread::sleep(duration),
+        }
+    }
+}
+
+fn read_message(stream: &mut impl Read) -> Vec<u8> {
+    let mut message = Vec::new();
+
+    loop {
+        let mut header = [0; 2];
+        stream.read_exact(&mut header).expect("chunk header");
+        let len = usize::from(u16::from_be_bytes(header));
+
+        if len == 0 {
+            return message;
+        }
+
+        let start = message.len();
+        message.resize(start + len, 0);
+        stream.read_exact(&mut message[start..]).expect("chunk");
+    }
+}
+
+fn write_message(stream: &mut impl Write, message: &[u8]) {
+    let mut bytes = (message.len() as u16).to_be_bytes().to_vec();
+    bytes.extend_from_slice(message);
+    bytes.extend_from_slice(&[0, 0]);
+    stream.write_all(&bytes).expect("message to be written");
+}
+
+fn metadata(text: &str) -> packstream_serde::Map<String, Value> {
+    match from_text(text).expect("valid metadata") {
+        Value::Map(map) => map,
+        value => panic!("expected map, got {}", value),
+    }
+}
+
+/// HELLO of the client answered by a 4.4 server.
+pub fn hello() -> Vec<Step> {
+    vec![Step::Recv(HELLO), success(r#"{server: "Neo4j/4.4.0"}"#)]
+}
+
+/// SUCCESS with metadata written in the text notation.
+pub fn success(text: &str) -> Step {
+    Step::Send(to_bytes(&Success { metadata: metadata(text) }).unwrap())
+}
+
+pub fn failure(code: &str, message: &str) -> Step {
+    let metadata = metadata(&format!("{{code: {:?}, message: {:?}}}", code, message));
+    Step::Send(to_bytes(&Failure { metadata }).unwrap())
+}
+
+pub fn ignored() -> Step {
+    Step::Send(Ignored::MSG.to_vec())
+}
+
+pub fn record(fields: Vec<Value>) -> Step {
+    Step::Send(to_bytes(&Record { fields }).unwrap())
+}
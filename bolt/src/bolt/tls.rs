@@ -0,0 +1,295 @@
+use crate::error::{BoltError, BoltResult};
+use async_net::TcpStream;
+use futures_rustls::client::TlsStream;
+use futures_rustls::rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use futures_rustls::rustls::crypto::{self, CryptoProvider};
+use futures_rustls::rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use futures_rustls::rustls::{ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+use futures_rustls::TlsConnector;
+use std::fmt;
+use std::sync::Arc;
+
+/// Encryption of the connection, as used by the `+s` and `+ssc` URI schemes.
+///
+/// By default the server certificate must be signed by a CA trusted by the system.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TlsConfig {
+    trust: Trust,
+    server_name: Option<String>,
+    client_certificate: Option<ClientCertificate>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+enum Trust {
+    #[default]
+    System,
+    /// PEM encoded CA certificates.
+    Custom(Vec<u8>),
+    All,
+}
+
+#[derive(Clone, PartialEq, Eq)]
+struct ClientCertificate {
+    chain: Vec<u8>,
+    key: Vec<u8>,
+}
+
+impl fmt::Debug for ClientCertificate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // The private key is not printed.
+        f.debug_struct("ClientCertificate").finish_non_exhaustive()
+    }
+}
+
+impl TlsConfig {
+    /// Trusts the CA certificates of the system.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trusts any certificate, including self-signed ones. The connection is encrypted
+    /// but the server is not authenticated.
+    pub fn trust_all() -> Self {
+        Self {
+            trust: Trust::All,
+            ..Self::default()
+        }
+    }
+
+    /// Trusts only the PEM encoded CA certificates instead of the system ones.
+    pub fn custom_ca(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.trust = Trust::Custom(pem.into());
+        self
+    }
+
+    /// Name verified against the server certificate instead of the host of the address.
+    pub fn server_name(mut self, server_name: impl Into<String>) -> Self {
+        self.server_name = Some(server_name.into());
+        self
+    }
+
+    /// Authenticates the client with the PEM encoded certificate chain and private key.
+    pub fn client_certificate(mut self, chain: impl Into<Vec<u8>>, key: impl Into<Vec<u8>>) -> Self {
+        self.client_certificate = Some(ClientCertificate { chain: chain.into(), key: key.into() });
+        self
+    }
+
+    /// Performs the TLS handshake over the stream connected to `addr`.
+    pub(crate) async fn connect(&self, stream: TcpStream, addr: &str) -> BoltResult<TlsStream<TcpStream>> {
+        let server_name = self.server_name.as_deref().unwrap_or_else(|| host(addr));
+        let server_name = ServerName::try_from(String::from(server_name))
+            .map_err(|_| BoltError::create(format!("Invalid TLS server name {}", server_name)))?;
+
+        let connector = TlsConnector::from(Arc::new(self.client_config()?));
+        Ok(connector.connect(server_name, stream).await?)
+    }
+
+    fn client_config(&self) -> BoltResult<ClientConfig> {
+        let provider = Arc::new(crypto::ring::default_provider());
+        let builder = ClientConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .map_err(tls_error)?;
+
+        let builder = match &self.trust {
+            Trust::System => builder.with_root_certificates(system_roots()?),
+            Trust::Custom(pem) => {
+                let mut roots = RootCertStore::empty();
+                for certificate in certificates(pem)? {
+                    roots.add(certificate).map_err(tls_error)?;
+                }
+                builder.with_root_certificates(roots)
+            }
+            Trust::All => builder.dangerous().with_custom_certificate_verifier(Arc::new(TrustAll(provider))),
+        };
+
+        match &self.client_certificate {
+            Some(ClientCertificate { chain, key }) => {
+                let key = rustls_pemfile::private_key(&mut key.as_slice())?
+                    .ok_or_else(|| BoltError::create("Client key contains no private key"))?;
+                builder.with_client_auth_cert(certificates(chain)?, key).map_err(tls_error)
+            }
+            None => Ok(builder.with_no_client_auth()),
+        }
+    }
+}
+
+/// Host part of `host:port`, without the brackets of an IPv6 address.
+fn host(addr: &str) -> &str {
+    let host = addr.rsplit_once(':').map_or(addr, |(host, _)| host);
+    host.trim_start_matches('[').trim_end_matches(']')
+}
+
+fn certificates(pem: &[u8]) -> BoltResult<Vec<CertificateDer<'static>>> {
+    let certificates = rustls_pemfile::certs(&mut &pem[..]).collect::<Result<Vec<_>, _>>()?;
+
+    if certificates.is_empty() {
+        return Err(BoltError::create("PEM contains no certificates"));
+    }
+
+    Ok(certificates)
+}
+
+fn system_roots() -> BoltResult<RootCertStore> {
+    let native = rustls_native_certs::load_native_certs();
+    let mut roots = RootCertStore::empty();
+    roots.add_parsable_certificates(native.certs);
+
+    if roots.is_empty() {
+        let errors: Vec<String> = native.errors.iter().map(ToString::to_string).collect();
+        return Err(BoltError::create(format!("No system CA certificates found: {}", errors.join(", "))));
+    }
+
+    Ok(roots)
+}
+
+fn tls_error(error: futures_rustls::rustls::Error) -> BoltError {
+    BoltError::create(format!("TLS error: {}", error))
+}
+
+/// Accepts any server certificate, signatures of the handshake are still verified.
+#[derive(Debug)]
+struct TrustAll(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for TrustAll {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, futures_rustls::rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        certificate: &CertificateDer<'_>,
+        signature: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, futures_rustls::rustls::Error> {
+        crypto::verify_tls12_signature(message, certificate, signature, &self.0.signature_verification_algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        certificate: &CertificateDer<'_>,
+        signature: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, futures_rustls::rustls::Error> {
+        crypto::verify_tls13_signature(message, certificate, signature, &self.0.signature_verification_algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bolt::stub::{hello, Stub};
+    use crate::bolt::{Client, Config};
+    use futures_rustls::rustls::pki_types::{PrivateKeyDer, PrivatePkcs8KeyDer};
+    use futures_rustls::rustls::server::WebPkiClientVerifier;
+    use futures_rustls::rustls::ServerConfig;
+    use rcgen::{BasicConstraints, Certificate, CertificateParams, IsCa, KeyPair};
+
+    struct Issued {
+        certificate: Certificate,
+        key: KeyPair,
+    }
+
+    fn authority() -> Issued {
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::new(Vec::new()).unwrap();
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        Issued { certificate: params.self_signed(&key).unwrap(), key }
+    }
+
+    fn issue(name: &str, issuer: Option<&Issued>) -> Issued {
+        let key = KeyPair::generate().unwrap();
+        let params = CertificateParams::new(vec![String::from(name)]).unwrap();
+        let certificate = match issuer {
+            Some(issuer) => params.signed_by(&key, &issuer.certificate, &issuer.key).unwrap(),
+            None => params.self_signed(&key).unwrap(),
+        };
+        Issued { certificate, key }
+    }
+
+    fn server_config(server: &Issued, client_ca: Option<&Issued>) -> ServerConfig {
+        let provider = Arc::new(crypto::ring::default_provider());
+        let builder = ServerConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .unwrap();
+
+        let builder = match client_ca {
+            Some(client_ca) => {
+                let mut roots = RootCertStore::empty();
+                roots.add(client_ca.certificate.der().clone()).unwrap();
+                let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                    .build()
+                    .unwrap();
+                builder.with_client_cert_verifier(verifier)
+            }
+            None => builder.with_no_client_auth(),
+        };
+
+        let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(server.key.serialize_der()));
+        builder.with_single_cert(vec![server.certificate.der().clone()], key).unwrap()
+    }
+
+    fn connect(stub: &Stub, tls: &TlsConfig) -> BoltResult<Client> {
+        let config = Config::builder(stub.addr().to_string()).tls(tls.clone()).build();
+        smol::block_on(Client::connect_with_config(&config))
+    }
+
+    #[test]
+    fn host_of_address() {
+        assert_eq!(host("localhost:7687"), "localhost");
+        assert_eq!(host("[::1]:7687"), "::1");
+        assert_eq!(host("localhost"), "localhost");
+    }
+
+    #[test]
+    fn custom_ca_and_server_name() {
+        let ca = authority();
+        let stub = Stub::start_tls([4, 4], hello(), server_config(&issue("localhost", Some(&ca)), None));
+
+        let tls = TlsConfig::new().custom_ca(ca.certificate.pem()).server_name("localhost");
+        connect(&stub, &tls).unwrap();
+        stub.finish();
+    }
+
+    #[test]
+    fn untrusted_certificate_is_rejected() {
+        let ca = authority();
+        let stub = Stub::start_tls([4, 4], hello(), server_config(&issue("localhost", Some(&ca)), None));
+
+        let tls = TlsConfig::new().custom_ca(authority().certificate.pem()).server_name("localhost");
+        assert!(connect(&stub, &tls).is_err());
+    }
+
+    #[test]
+    fn trust_all_accepts_self_signed() {
+        let stub = Stub::start_tls([4, 4], hello(), server_config(&issue("neo4j.local", None), None));
+
+        connect(&stub, &TlsConfig::trust_all()).unwrap();
+        stub.finish();
+    }
+
+    #[test]
+    fn client_certificate() {
+        let ca = authority();
+        let client = issue("client", Some(&ca));
+        let config = server_config(&issue("localhost", Some(&ca)), Some(&ca));
+        let stub = Stub::start_tls([4, 4], hello(), config);
+
+        let tls = TlsConfig::new()
+            .custom_ca(ca.certificate.pem())
+            .server_name("localhost")
+            .client_certificate(client.certificate.pem(), client.key.serialize_pem());
+        connect(&stub, &tls).unwrap();
+        stub.finish();
+    }
+}
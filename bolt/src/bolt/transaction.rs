@@ -0,0 +1,113 @@
+use super::{Client, RecordStream, TransactionStatus};
+use crate::error::{BoltError, BoltResult};
+use packstream_serde::{Map, Value};
+use std::time::Duration;
+
+/// Kind of work done by the transaction, which lets a cluster pick the server.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AccessMode {
+    Read,
+    #[default]
+    Write,
+}
+
+/// Options of an explicit transaction. Timeout and metadata require Bolt 3.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TransactionConfig {
+    timeout: Option<Duration>,
+    metadata: Map<String, Value>,
+    mode: AccessMode,
+}
+
+impl TransactionConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Time after which the server terminates the transaction, rounded to milliseconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Metadata attached to the transaction, visible in `dbms.listTransactions`.
+    pub fn metadata(mut self, metadata: Map<String, Value>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    pub fn mode(mut self, mode: AccessMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn access_mode(&self) -> AccessMode {
+        self.mode
+    }
+
+    /// Whether neither timeout nor metadata is set.
+    pub(super) fn is_empty(&self) -> bool {
+        self.timeout.is_none() && self.metadata.is_empty()
+    }
+
+    pub(super) fn into_extra(self) -> Map<String, Value> {
+        let mut extra = Map::new();
+
+        if let Some(timeout) = self.timeout {
+            let millis = i64::try_from(timeout.as_millis()).unwrap_or(i64::MAX);
+            extra.insert(String::from("tx_timeout"), Value::I64(millis));
+        }
+        if !self.metadata.is_empty() {
+            extra.insert(String::from("tx_metadata"), Value::Map(self.metadata));
+        }
+        if self.mode == AccessMode::Read {
+            extra.insert(String::from("mode"), Value::String(String::from("r")));
+        }
+
+        extra
+    }
+}
+
+/// Explicit transaction returned by [`Client::begin_transaction`].
+///
+/// Dropping the transaction without committing rolls it back before the next request
+/// of the client.
+#[derive(Debug)]
+pub struct Transaction<'a> {
+    client: &'a mut Client,
+}
+
+impl<'a> Transaction<'a> {
+    pub(super) fn new(client: &'a mut Client) -> Self {
+        Self { client }
+    }
+
+    /// Runs the statement within the transaction.
+    pub async fn run(
+        &mut self,
+        statement: impl Into<String>,
+        parameters: Map<String, Value>,
+    ) -> BoltResult<RecordStream<'_>> {
+        if self.client.transaction != TransactionStatus::Open {
+            return Err(BoltError::create("Transaction was rolled back after a failure"));
+        }
+
+        self.client.run(statement, parameters).await
+    }
+
+    pub async fn commit(self) -> BoltResult<()> {
+        self.client.end_transaction(true).await
+    }
+
+    pub async fn rollback(self) -> BoltResult<()> {
+        self.client.end_transaction(false).await
+    }
+}
+
+impl<'a> Drop for Transaction<'a> {
+    fn drop(&mut self) {
+        if self.client.transaction == TransactionStatus::Open {
+            self.client.transaction = TransactionStatus::Dropped;
+        }
+    }
+}
@@ -1,144 +1,412 @@
 use async_net::{TcpStream, AsyncToSocketAddrs};
 use crate::error::{BoltResult, BoltError};
-use bytes::{BytesMut, BufMut};
-use futures_lite::{AsyncWriteExt, AsyncReadExt};
+use bytes::BytesMut;
+use super::chunk::{self, MAX_CHUNK_SIZE};
+use super::config::{Config, DEFAULT_USER_AGENT};
+use futures_lite::{AsyncRead, AsyncWrite, AsyncWriteExt, AsyncReadExt};
+use futures_rustls::client::TlsStream;
+use packstream_serde::{to_bytes, from_bytes, Map, Value};
+use packstream_serde::message::{
+   AuthToken, Discard, DiscardAll, Hello, Init, Logon, Pull, PullAll, Reset, Run,
+   RunWithMetadata, SummaryMessage,
+};
+use packstream_serde::packstream::EmptyPackstreamStructure;
 use semver::Version;
+use smol::io::{ReadHalf, WriteHalf};
+use smol::Timer;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+const MAGIC_PREAMBLE: [u8; 4] = [0x60, 0x60, 0xB0, 0x17];
+
+/// Versions proposed by default, newest first.
+///
+/// Bolt 5 is not proposed, as the structures of its nodes, relationships and UTC date
+/// times are not supported yet.
+pub const DEFAULT_VERSIONS: [VersionRange; 3] = [
+   VersionRange::new(4, 4).with_range(2),
+   VersionRange::new(4, 1),
+   VersionRange::new(3, 0),
+];
 
-// u16::MAX
-const MAX_CHUNK_SIZE: usize = 65_535;
-const CLIENT_NAME: &str = "rust-bolt/0.0.1";
+/// Protocol version proposed during the handshake.
+///
+/// Since Bolt 4.3 a single proposal may cover `range` preceding minor versions,
+/// e.g. `VersionRange::new(4, 4).with_range(2)` proposes 4.4, 4.3 and 4.2.
+/// Servers older than 4.3 only consider the exact `major.minor` version.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VersionRange {
+   pub major: u8,
+   pub minor: u8,
+   pub range: u8,
+}
 
-const HANDSHAKE: [u8; 20] = [
-   0x60, 0x60, 0xB0, 0x17,
-   0x00, 0x00, 0x00, 0x01,
-   0x00, 0x00, 0x00, 0x00,
-   0x00, 0x00, 0x00, 0x00,
-   0x00, 0x00, 0x00, 0x00,
-];
+impl VersionRange {
+   pub const fn new (major: u8, minor: u8) -> Self {
+      Self { major, minor, range: 0 }
+   }
+
+   pub const fn with_range (mut self, range: u8) -> Self {
+      self.range = range;
+      self
+   }
+
+   /// Returns the four handshake bytes of the proposal.
+   pub fn encode (&self) -> [u8; 4] {
+      [0x00, self.range, self.minor, self.major]
+   }
+
+   pub fn contains (&self, version: &Version) -> bool {
+      version.major == u64::from(self.major)
+         && version.minor <= u64::from(self.minor)
+         && version.minor + u64::from(self.range) >= u64::from(self.minor)
+   }
+}
 
+/// Stream of a connection to the server, encrypted or not.
 #[derive(Debug)]
-pub struct Transport {
-   stream: TcpStream,
-   buffer: BytesMut,
+pub enum BoltStream {
+   Plain(TcpStream),
+   Tls(Box<TlsStream<TcpStream>>),
 }
 
-impl Transport {
-   pub async fn negotiate_version (stream: &mut TcpStream) -> BoltResult<Version> {
-      let n = stream.write(&HANDSHAKE).await?;
+impl AsyncRead for BoltStream {
+   fn poll_read (self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+      match self.get_mut() {
+         Self::Plain(stream) => Pin::new(stream).poll_read(cx, buf),
+         Self::Tls(stream) => Pin::new(stream).poll_read(cx, buf),
+      }
+   }
+}
 
-      let mut picked_version: [u8; 4] = [0; 4];
-      stream.read(&mut picked_version).await?;
+impl AsyncWrite for BoltStream {
+   fn poll_write (self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+      match self.get_mut() {
+         Self::Plain(stream) => Pin::new(stream).poll_write(cx, buf),
+         Self::Tls(stream) => Pin::new(stream).poll_write(cx, buf),
+      }
+   }
 
-      let [_, patch, minor, major] = picked_version;
+   fn poll_flush (self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+      match self.get_mut() {
+         Self::Plain(stream) => Pin::new(stream).poll_flush(cx),
+         Self::Tls(stream) => Pin::new(stream).poll_flush(cx),
+      }
+   }
 
-      if major == 1 {
-         Ok(Version::new(
-               major.into(),
-               minor.into(),
-               patch.into(),
-               ))
-      } else {
-         Err(BoltError::create("Version negotiation failed"))
+   fn poll_close (self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+      match self.get_mut() {
+         Self::Plain(stream) => Pin::new(stream).poll_close(cx),
+         Self::Tls(stream) => Pin::new(stream).poll_close(cx),
       }
    }
+}
 
-   pub async fn new<A: AsyncToSocketAddrs> (addr: A, auth: packstream_serde::message::BasicAuth) -> BoltResult<Self> {
-      use packstream_serde::{to_bytes, from_bytes, from_value};
-      use packstream_serde::message::{Init, BasicAuth, SummaryMessage};
+/// Bolt connection over any byte stream, [`BoltStream`] unless created with [`Transport::with_stream`].
+#[derive(Debug)]
+pub struct Transport<S = BoltStream> {
+   // Halves of the stream, so RESET can be written while a message is being read.
+   reader: ReadHalf<S>,
+   writer: WriteHalf<S>,
+   buffer: BytesMut,
+   max_chunk_size: usize,
+   max_message_size: usize,
+   read_timeout: Option<Duration>,
+   write_timeout: Option<Duration>,
+   version: Version,
+   // Set while a message is being written or read, remains set if the future was dropped.
+   broken: bool,
+}
 
-      let mut stream = TcpStream::connect(addr).await?;
-      let version = Self::negotiate_version(&mut stream).await?;
-      let mut transport = Self { stream, buffer: BytesMut::with_capacity(MAX_CHUNK_SIZE) };
+impl Transport {
+   pub async fn new<A: AsyncToSocketAddrs> (addr: A, auth: AuthToken) -> BoltResult<Self> {
+      Self::with_versions(addr, auth, &DEFAULT_VERSIONS).await
+   }
 
-      transport.write(&to_bytes(&Init {
-         auth,
-         client: String::from(CLIENT_NAME),
-      })?).await?;
+   pub async fn with_versions<A: AsyncToSocketAddrs> (addr: A, auth: AuthToken, versions: &[VersionRange]) -> BoltResult<Self> {
+      let stream = TcpStream::connect(addr).await?;
+      Self::with_stream(BoltStream::Plain(stream), auth, DEFAULT_USER_AGENT, versions).await
+   }
 
-      match from_bytes::<SummaryMessage>(&transport.read().await?)? {
-         SummaryMessage::Success(mut success) => Ok(transport),
-         _ => Err(BoltError::create("unable to authorize")),
-      }
+   /// Connects with the options of the configuration, within its connect timeout.
+   pub async fn connect (config: &Config, versions: &[VersionRange]) -> BoltResult<Self> {
+      let connect = async {
+         let stream = TcpStream::connect(config.address()).await?;
+         let stream = match config.tls() {
+            Some(tls) => BoltStream::Tls(Box::new(tls.connect(stream, config.address()).await?)),
+            None => BoltStream::Plain(stream),
+         };
+
+         let mut transport = Self::with_stream(stream, config.auth().clone(), config.user_agent(), versions).await?;
+         if let Some(size) = config.max_message_size() {
+            transport.set_max_message_size(size);
+         }
+         transport.set_read_timeout(config.read_timeout());
+         transport.set_write_timeout(config.write_timeout());
+
+         Ok(transport)
+      };
+
+      timeout(config.connect_timeout(), connect, |timeout| {
+         BoltError::timeout(format!("Failed to connect to {} within {:?}", config.address(), timeout))
+      }).await
    }
+}
 
-   // Assumes that the buffer is cleared.
-   pub fn append_message<'a> (&mut self, message: &'a [u8]) -> Option<&'a [u8]> {
-      use std::convert::TryFrom;
+impl<S: AsyncRead + AsyncWrite + Unpin> Transport<S> {
+   /// Proposes up to four `versions` to the server and returns the agreed one.
+   pub async fn negotiate_version (stream: &mut S, versions: &[VersionRange]) -> BoltResult<Version> {
+      if versions.is_empty() || versions.len() > 4 {
+         return Err(BoltError::create("Between one and four versions must be proposed"));
+      }
 
-      let available = MAX_CHUNK_SIZE - self.buffer.len();
-      let msg_len = message.len();
-      let chunk_len = std::cmp::min(msg_len, available - 4);
+      let mut handshake = [0; 20];
+      handshake[0..4].copy_from_slice(&MAGIC_PREAMBLE);
+      for (index, proposal) in versions.iter().enumerate() {
+         handshake[4 * index + 4..4 * index + 8].copy_from_slice(&proposal.encode());
+      }
+      stream.write_all(&handshake).await?;
 
-      self.buffer.put_u16(u16::try_from(msg_len).expect("Chunk length to fit in two bytes"));
-      self.buffer.extend_from_slice(&message[0..chunk_len]);
+      let mut picked_version: [u8; 4] = [0; 4];
+      stream.read_exact(&mut picked_version).await?;
 
-      if chunk_len == msg_len {
-         self.buffer.put_u16(0);
+      let [_, _, minor, major] = picked_version;
+      let version = Version::new(major.into(), minor.into(), 0);
 
-         return None;
+      if major == 0 {
+         Err(BoltError::create("Version negotiation failed: server supports none of the proposed versions"))
+      } else if versions.iter().any(|proposal| proposal.contains(&version)) {
+         Ok(version)
+      } else {
+         Err(BoltError::create(format!("Version negotiation failed: server picked unproposed version {}", version)))
       }
+   }
 
-      Some(&message[chunk_len..])
+   /// Performs the handshake and authenticates over the connected stream.
+   pub async fn with_stream (mut stream: S, auth: AuthToken, user_agent: &str, versions: &[VersionRange]) -> BoltResult<Self> {
+      let version = Self::negotiate_version(&mut stream, versions).await?;
+      let (reader, writer) = smol::io::split(stream);
+      let mut transport = Self {
+         reader,
+         writer,
+         buffer: BytesMut::with_capacity(MAX_CHUNK_SIZE),
+         max_chunk_size: MAX_CHUNK_SIZE,
+         max_message_size: usize::MAX,
+         read_timeout: None,
+         write_timeout: None,
+         version,
+         broken: false,
+      };
+
+      transport.authenticate(auth, user_agent).await?;
+
+      Ok(transport)
    }
 
-   pub async fn write (&mut self, message: &[u8]) -> BoltResult<()> {
-      self.buffer.clear();
+   /// Version agreed with the server during the handshake.
+   pub fn version (&self) -> &Version {
+      &self.version
+   }
 
-      let mut to_write = message;
-      while let Some(remaining) = self.append_message(to_write) {
-         self.stream.write_all(&self.buffer).await?;
-         self.buffer.clear();
-         to_write = remaining; 
+   async fn authenticate (&mut self, auth: AuthToken, user_agent: &str) -> BoltResult<()> {
+      match self.version.major {
+         1 | 2 => {
+            let init = to_bytes(&Init { auth, client: String::from(user_agent) })?;
+            self.write(&init).await?;
+         }
+         // Since 5.1 the credentials are sent separately with LOGON.
+         5 if self.version.minor >= 1 => {
+            let hello = to_bytes(&Hello::new(user_agent, None))?;
+            self.write(&hello).await?;
+            self.expect_success().await?;
+            self.write(&to_bytes(&Logon { auth })?).await?;
+         }
+         _ => {
+            let hello = to_bytes(&Hello::new(user_agent, Some(auth)))?;
+            self.write(&hello).await?;
+         }
       }
 
-      self.stream.write_all(&self.buffer).await?;
+      self.expect_success().await
+   }
 
-      Ok(())
+   async fn expect_success (&mut self) -> BoltResult<()> {
+      match from_bytes::<SummaryMessage>(&self.read().await?)? {
+         SummaryMessage::Success(_) => Ok(()),
+         message => Err(BoltError::create(message)),
+      }
    }
 
-   pub async fn write_batch (&mut self, messages: &[&[u8]]) -> BoltResult<()> {
-      self.buffer.clear();
+   /// Encodes the statement with RUN of the agreed version, `extra` is sent since Bolt 3.
+   pub fn encode_run (&self, run: &Run, extra: Map<String, Value>) -> BoltResult<Vec<u8>> {
+      if self.version.major < 3 {
+         return Ok(to_bytes(run)?);
+      }
+
+      Ok(to_bytes(&RunWithMetadata {
+         statement: run.statement.clone(),
+         parameters: run.parameters.clone(),
+         extra,
+      })?)
+   }
 
-      let mut to_write: &[u8] = &[0; 0];
-      for message in messages {
-         to_write = *message;
+   /// Encodes request for `n` records of the last result, `-1` requests all records.
+   /// Versions before 4.0 always pull all records.
+   pub fn encode_pull (&self, n: i64) -> BoltResult<Vec<u8>> {
+      if self.version.major < 4 {
+         Ok(PullAll::MSG.to_vec())
+      } else {
+         Ok(to_bytes(&Pull::new(n, None))?)
+      }
+   }
 
-         while let Some(remaining) = self.append_message(to_write) {
-            self.stream.write_all(&self.buffer).await?;
-            self.buffer.clear();
-            to_write = remaining;
-         }
+   /// Encodes request to discard all records of the last result.
+   pub fn encode_discard (&self) -> BoltResult<Vec<u8>> {
+      if self.version.major < 4 {
+         Ok(DiscardAll::MSG.to_vec())
+      } else {
+         Ok(to_bytes(&Discard::all())?)
       }
+   }
 
-      self.stream.write_all(&self.buffer).await?;
+   /// Sets the maximum size of the chunks written to the server, at most `u16::MAX` bytes.
+   pub fn set_max_chunk_size (&mut self, size: usize) -> BoltResult<()> {
+      if size == 0 || size > MAX_CHUNK_SIZE {
+         return Err(BoltError::create(format!("Chunk size must be between 1 and {} bytes", MAX_CHUNK_SIZE)));
+      }
 
+      self.max_chunk_size = size;
       Ok(())
    }
 
-   pub async fn read(&mut self) -> BoltResult<BytesMut> {
-      let mut response = BytesMut::new();
-      let mut chunk = BytesMut::new();
+   /// Sets the size of the largest message accepted from the server.
+   pub fn set_max_message_size (&mut self, size: usize) {
+      self.max_message_size = size;
+   }
+
+   /// Sets the time to wait for a message from the server, `None` waits forever.
+   pub fn set_read_timeout (&mut self, timeout: Option<Duration>) {
+      self.read_timeout = timeout;
+   }
+
+   /// Sets the time to write a batch of messages, `None` waits forever.
+   pub fn set_write_timeout (&mut self, timeout: Option<Duration>) {
+      self.write_timeout = timeout;
+   }
 
-      let mut header_bytes: [u8; 2] = [0; 2];
-      self.stream.read_exact(&mut header_bytes).await?;
-      let mut len = usize::from(u16::from_be_bytes(header_bytes));
+   pub async fn write (&mut self, message: &[u8]) -> BoltResult<()> {
+      self.write_batch(&[message]).await
+   }
 
+   /// Writes all messages at once, small messages share the same write.
+   ///
+   /// The connection is left broken when the write timeout expires.
+   pub async fn write_batch (&mut self, messages: &[&[u8]]) -> BoltResult<()> {
+      self.check_broken()?;
+      self.broken = true;
+      let write = chunk::write_messages(&mut self.writer, &mut self.buffer, messages, self.max_chunk_size);
+      timeout(self.write_timeout, write, |timeout| {
+         BoltError::timeout(format!("Failed to write to the server within {:?}", timeout))
+      }).await?;
+      self.broken = false;
 
-      loop {
-         chunk.resize(len + 2, 0);
-         self.stream.read_exact(&mut chunk).await?;
+      Ok(())
+   }
 
-         let marker = u16::from_be_bytes([chunk[len], chunk[len + 1]]);
-         response.extend_from_slice(&chunk[0..len]);
+   /// Reads the next message, the connection is left broken when the read timeout expires.
+   pub async fn read (&mut self) -> BoltResult<BytesMut> {
+      let (message, _) = self.read_or_reset(smol::future::pending()).await?;
+      Ok(message)
+   }
 
-         if marker == 0 {
-            break;
-         } else {
-            len = usize::from(marker);
+   /// Reads the next message, writing RESET once `interrupt` completes while waiting
+   /// for it. The message being read is not affected, its reading continues.
+   ///
+   /// Returns whether RESET was written.
+   pub async fn read_or_reset (&mut self, interrupt: impl Future<Output = ()>) -> BoltResult<(BytesMut, bool)> {
+      self.check_broken()?;
+      self.broken = true;
+      let read = chunk::read_message(&mut self.reader, self.max_message_size);
+      let read = timeout(self.read_timeout, read, |timeout| {
+         BoltError::timeout(format!("No message received from the server within {:?}", timeout))
+      });
+      futures_lite::pin!(read);
+
+      let interrupted = smol::future::or(
+         async { Some(read.as_mut().await) },
+         async {
+            interrupt.await;
+            None
+         },
+      ).await;
+
+      let (message, reset) = match interrupted {
+         Some(message) => (message?, false),
+         None => {
+            let write = chunk::write_messages(&mut self.writer, &mut self.buffer, &[&Reset::MSG], self.max_chunk_size);
+            timeout(self.write_timeout, write, |timeout| {
+               BoltError::timeout(format!("Failed to write to the server within {:?}", timeout))
+            }).await?;
+            (read.await?, true)
          }
+      };
+      self.broken = false;
+
+      if message.len() < 2 {
+         return Err(BoltError::create("Received message is too short"));
       }
 
-      Ok(response)
+      Ok((message, reset))
    }
+
+   /// Whether a write or read was interrupted, the framing of the stream is lost then.
+   pub fn is_broken (&self) -> bool {
+      self.broken
+   }
+
+   fn check_broken (&self) -> BoltResult<()> {
+      if self.broken {
+         Err(BoltError::create("Connection is broken, a message was only partially written or read"))
+      } else {
+         Ok(())
+      }
+   }
+}
+
+/// Runs the future, failing with the `error` when it takes longer than `duration`.
+pub(crate) async fn timeout<T> (
+   duration: Option<Duration>,
+   future: impl Future<Output = BoltResult<T>>,
+   error: impl FnOnce(Duration) -> BoltError,
+) -> BoltResult<T> {
+   let duration = match duration {
+      Some(duration) => duration,
+      None => return future.await,
+   };
+
+   smol::future::or(future, async {
+      Timer::after(duration).await;
+      Err(error(duration))
+   }).await
 }
 
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   #[test]
+   fn version_range () {
+      let range = VersionRange::new(4, 4).with_range(2);
+
+      assert_eq!(range.encode(), [0x00, 0x02, 0x04, 0x04]);
+      assert!(range.contains(&Version::new(4, 4, 0)));
+      assert!(range.contains(&Version::new(4, 2, 0)));
+      assert!(!range.contains(&Version::new(4, 1, 0)));
+      assert!(!range.contains(&Version::new(5, 4, 0)));
+      assert!(VersionRange::new(3, 0).contains(&Version::new(3, 0, 0)));
+      assert!(!DEFAULT_VERSIONS.iter().any(|range| range.contains(&Version::new(5, 0, 0))));
+   }
+}
@@ -1,2 +1 @@
 pub(crate) const CHUNK_DEFAULT_SIZE: usize = 1400;
-pub(crate) const CLIENT_NAME: &str = "rust-bolt";
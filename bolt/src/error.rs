@@ -3,7 +3,8 @@ use std::fmt;
 use std::io;
 
 use packstream_serde::error::PackstreamError;
-use packstream_serde::message::SummaryMessage;
+use packstream_serde::message::{Failure, SummaryMessage};
+use packstream_serde::Value;
 
 pub type BoltResult<T> = std::result::Result<T, BoltError>;
 
@@ -18,6 +19,63 @@ impl BoltError {
             err: Box::new(msg.into()),
         }
     }
+
+    /// Error of connecting, reading or writing which did not complete in time.
+    pub fn timeout(msg: impl Into<String>) -> Self {
+        Self::create(ErrorCode::Timeout(msg.into()))
+    }
+
+    /// Error of a query which did not complete within the query timeout.
+    pub fn query_timeout(msg: impl Into<String>) -> Self {
+        Self::create(ErrorCode::QueryTimeout(msg.into()))
+    }
+
+    /// Error of a pool which had no connection available in time.
+    pub fn pool_timeout(msg: impl Into<String>) -> Self {
+        Self::create(ErrorCode::PoolTimeout(msg.into()))
+    }
+
+    /// Error of a query cancelled with a [`CancelHandle`](crate::bolt::CancelHandle).
+    pub fn cancelled() -> Self {
+        Self::create(ErrorCode::Cancelled)
+    }
+
+    pub fn code(&self) -> &ErrorCode {
+        &self.err
+    }
+
+    /// Whether any operation, including a query or a pool acquisition, did not complete in time.
+    pub fn is_timeout(&self) -> bool {
+        matches!(*self.err, ErrorCode::Timeout(_) | ErrorCode::QueryTimeout(_) | ErrorCode::PoolTimeout(_))
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        matches!(*self.err, ErrorCode::Cancelled)
+    }
+
+    /// Failure reported by the server.
+    pub fn neo4j(&self) -> Option<&Neo4jError> {
+        match &*self.err {
+            ErrorCode::Neo4j(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    pub fn is_transient(&self) -> bool {
+        self.neo4j().is_some_and(Neo4jError::is_transient)
+    }
+
+    pub fn is_retryable(&self) -> bool {
+        self.neo4j().is_some_and(Neo4jError::is_retryable)
+    }
+
+    pub fn is_client_error(&self) -> bool {
+        self.neo4j().is_some_and(Neo4jError::is_client_error)
+    }
+
+    pub fn is_authentication_error(&self) -> bool {
+        self.neo4j().is_some_and(Neo4jError::is_authentication_error)
+    }
 }
 
 impl fmt::Display for BoltError {
@@ -46,7 +104,18 @@ pub enum ErrorCode {
     IO(io::Error),
     Message(String),
     Packstream(PackstreamError),
+    /// FAILURE sent by the server.
+    Neo4j(Neo4jError),
+    /// Summary other than FAILURE received instead of SUCCESS.
     Bolt(SummaryMessage),
+    /// Connecting, reading or writing took longer than allowed.
+    Timeout(String),
+    /// The query took longer than the query timeout.
+    QueryTimeout(String),
+    /// All connections of the pool stayed in use for longer than the acquisition timeout.
+    PoolTimeout(String),
+    /// The query was interrupted with RESET on request of the user.
+    Cancelled,
 }
 
 impl fmt::Display for ErrorCode {
@@ -55,7 +124,10 @@ impl fmt::Display for ErrorCode {
             Self::IO(error) => write!(f, "{}", error),
             Self::Message(string) => write!(f, "{}", string),
             Self::Packstream(error) => write!(f, "{}", error),
+            Self::Neo4j(error) => write!(f, "{}", error),
             Self::Bolt(message) => write!(f, "{}", message),
+            Self::Timeout(string) | Self::QueryTimeout(string) | Self::PoolTimeout(string) => write!(f, "{}", string),
+            Self::Cancelled => write!(f, "Query was cancelled"),
         }
     }
 }
@@ -86,6 +158,162 @@ impl From<io::Error> for ErrorCode {
 
 impl From<SummaryMessage> for ErrorCode {
     fn from(message: SummaryMessage) -> Self {
-        Self::Bolt(message)
+        match message {
+            SummaryMessage::Failure(failure) => Self::Neo4j(Neo4jError::from(failure)),
+            message => Self::Bolt(message),
+        }
+    }
+}
+
+impl From<Neo4jError> for ErrorCode {
+    fn from(error: Neo4jError) -> Self {
+        Self::Neo4j(error)
+    }
+}
+
+/// Classification part of the Neo4j status code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Classification {
+    ClientError,
+    ClientNotification,
+    TransientError,
+    DatabaseError,
+    Unknown,
+}
+
+/// Error parsed from the FAILURE metadata.
+///
+/// Status codes are in the `Neo.<classification>.<category>.<title>` format,
+/// e.g. `Neo.ClientError.Statement.SyntaxError`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Neo4jError {
+    pub code: String,
+    pub message: String,
+    pub classification: Classification,
+    pub category: String,
+    pub title: String,
+}
+
+impl Neo4jError {
+    pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+        let code = code.into();
+        let mut parts = code.split('.');
+        let (classification, category, title) = match (parts.next(), parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some("Neo"), Some(classification), Some(category), Some(title), None) => {
+                let classification = match classification {
+                    "ClientError" => Classification::ClientError,
+                    "ClientNotification" => Classification::ClientNotification,
+                    "TransientError" => Classification::TransientError,
+                    "DatabaseError" => Classification::DatabaseError,
+                    _ => Classification::Unknown,
+                };
+                (classification, category.to_owned(), title.to_owned())
+            }
+            _ => (Classification::Unknown, String::new(), String::new()),
+        };
+
+        Self {
+            code,
+            message: message.into(),
+            classification,
+            category,
+            title,
+        }
+    }
+
+    pub fn is_transient(&self) -> bool {
+        self.classification == Classification::TransientError
+    }
+
+    /// Whether the unit of work may succeed when retried. Transient errors caused by
+    /// the termination of the transaction by the user are not retryable, errors of
+    /// a cluster member which stopped being the leader are.
+    pub fn is_retryable(&self) -> bool {
+        match self.code.as_str() {
+            "Neo.TransientError.Transaction.Terminated"
+            | "Neo.TransientError.Transaction.LockClientStopped" => false,
+            "Neo.ClientError.Cluster.NotALeader"
+            | "Neo.ClientError.General.ForbiddenOnReadOnlyDatabase" => true,
+            _ => self.is_transient(),
+        }
+    }
+
+    pub fn is_client_error(&self) -> bool {
+        self.classification == Classification::ClientError
+    }
+
+    pub fn is_database_error(&self) -> bool {
+        self.classification == Classification::DatabaseError
+    }
+
+    /// Whether the credentials were rejected or expired.
+    pub fn is_authentication_error(&self) -> bool {
+        self.classification == Classification::ClientError
+            && self.category == "Security"
+            && matches!(
+                self.title.as_str(),
+                "Unauthorized" | "TokenExpired" | "CredentialsExpired" | "AuthenticationRateLimit"
+            )
+    }
+}
+
+impl From<Failure> for Neo4jError {
+    fn from(mut failure: Failure) -> Self {
+        let mut take = |key: &str| match failure.metadata.remove(key) {
+            Some(Value::String(value)) => value,
+            _ => String::new(),
+        };
+        let code = take("code");
+        let message = take("message");
+
+        Self::new(code, message)
+    }
+}
+
+impl fmt::Display for Neo4jError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for Neo4jError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neo4j_error() {
+        let error = Neo4jError::new("Neo.ClientError.Statement.SyntaxError", "Invalid input");
+        assert_eq!(error.classification, Classification::ClientError);
+        assert_eq!(error.category, "Statement");
+        assert_eq!(error.title, "SyntaxError");
+        assert!(error.is_client_error() && !error.is_transient() && !error.is_authentication_error());
+        assert_eq!(error.to_string(), "Neo.ClientError.Statement.SyntaxError: Invalid input");
+
+        assert!(Neo4jError::new("Neo.ClientError.Security.Unauthorized", "").is_authentication_error());
+        assert!(!Neo4jError::new("Neo.ClientError.Security.Forbidden", "").is_authentication_error());
+
+        let error = Neo4jError::new("Neo.TransientError.Transaction.DeadlockDetected", "");
+        assert!(error.is_transient() && error.is_retryable());
+        let error = Neo4jError::new("Neo.TransientError.Transaction.Terminated", "");
+        assert!(error.is_transient() && !error.is_retryable());
+        assert!(Neo4jError::new("Neo.ClientError.Cluster.NotALeader", "").is_retryable());
+
+        let error = Neo4jError::new("Unexpected", "");
+        assert_eq!(error.classification, Classification::Unknown);
+        assert!(error.category.is_empty());
+    }
+
+    #[test]
+    fn from_failure() {
+        let mut metadata = packstream_serde::Map::new();
+        metadata.insert(String::from("code"), Value::String(String::from("Neo.TransientError.General.DatabaseUnavailable")));
+        metadata.insert(String::from("message"), Value::String(String::from("Unavailable")));
+
+        let error = BoltError::from(SummaryMessage::Failure(Failure { metadata }));
+        assert!(error.is_transient());
+        assert_eq!(error.neo4j().unwrap().message, "Unavailable");
+        assert_eq!(error.to_string(), "Neo.TransientError.General.DatabaseUnavailable: Unavailable");
     }
 }
@@ -6,4 +6,7 @@ pub mod bolt;
 pub mod constants;
 pub mod error;
 
-pub use bolt::Client;
+pub use bolt::{
+    AccessMode, CancelHandle, Client, Config, ConfigBuilder, ConnectionState, Pool, PoolConfig,
+    PooledClient, RecordStream, RetryPolicy, Router, TlsConfig, Transaction, TransactionConfig,
+};
@@ -34,15 +34,10 @@ impl<T> Response<T> {
 }
 
 impl<T> From<Success> for Response<T> {
-    // TODO: check if there is always "fields" key in the success metadata message.
     fn from(mut success: Success) -> Self {
-        if let Some(Value::List(v)) = success.metadata.remove("fields") {
-            Self {
-                fields: v,
-                rows: Vec::new(),
-            }
-        } else {
-            unreachable!("It is assumed that success message has 'fields' key in metadata.");
+        match success.metadata.remove("fields") {
+            Some(Value::List(fields)) => Self { fields, rows: Vec::new() },
+            _ => Self::new(),
         }
     }
 }
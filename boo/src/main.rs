@@ -7,9 +7,12 @@ mod highlighter;
 use textarea::TextArea;
 use print_query::{print_query_response, print_query_error};
 use mode::Mode;
-use bolt::{error::BoltResult, Client, response::Response};
-use packstream_serde::Value;
+use bolt::{error::BoltResult, Client, ConfigBuilder, response::Response};
+use packstream_serde::{Value, message::AuthToken};
 use std::io::{self, Write};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
 use termion::input::TermRead;
 use termion::event::Key;
 use termion::raw::IntoRawMode;
@@ -54,20 +57,26 @@ async fn run_statement(client: &mut Client, statement: String) -> BoltResult<Res
 
     let message = Run {
         statement,
-        parameters: std::collections::HashMap::new(),
+        parameters: packstream_serde::Map::new(),
     };
 
     return client.send(&message, true).await;
 }
 
 fn connect_to_server(stdin: &mut io::Stdin, stdout: &mut io::Stdout) -> BoltResult<Client> {
-    let server_address = get_user_input(stdin, "Server address", "localhost:7687");
-    let auth_username = get_user_input(stdin, "Auth username", "neo4j");
-    let auth_password = get_password(stdin, stdout, "Auth password", "bolt-rs");
-    
-    smol::block_on(async {
-        Client::connect(server_address, auth_username, auth_password).await
-    })
+    let server_uri = get_user_input(stdin, "Server URI", "bolt://localhost:7687");
+    let mut config = ConfigBuilder::parse(&server_uri)?
+        .user_agent(concat!("boo/", env!("CARGO_PKG_VERSION")))
+        .connect_timeout(Duration::from_secs(10));
+
+    // Credentials are only asked for when the URI does not contain them.
+    if config.clone().build().auth() == &AuthToken::None {
+        let auth_username = get_user_input(stdin, "Auth username", "neo4j");
+        let auth_password = get_password(stdin, stdout, "Auth password", "bolt-rs");
+        config = config.auth(AuthToken::basic(auth_username, auth_password));
+    }
+
+    smol::block_on(Client::connect_with_config(&config.build()))
 }
 
 macro_rules! help_str {
@@ -93,6 +102,7 @@ static HELP_TEXT: &'static str = help_str!(
     }
     statement {
        "Press [C-r] to run statement"
+       "Press [C-c] to cancel running statement"
        "Press [C-w] to remove previous word"
        "Press [C-u] to clear statement"
        "Press [Backspace] to remove previous character"
@@ -126,7 +136,20 @@ fn main() -> Result<(), io::Error> {
     // TODO: display mode before prompt.
     let mut mode: Mode = Mode::Normal;
     let mut text_area = TextArea::new();
-    let mut keys = stdin.keys();
+    // Keys are read on another thread, so [C-c] can cancel the statement while it runs.
+    let cancel = client.cancel_handle();
+    let (key_sender, key_receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for key in io::stdin().keys() {
+            if let Ok(Key::Ctrl('c')) = key {
+                cancel.cancel();
+            }
+            if key_sender.send(key).is_err() {
+                break;
+            }
+        }
+    });
+    let mut keys = key_receiver.iter();
     let mut last_response: Option<LastResponse> = None;
 
     stdout.write(HELP_TEXT.as_bytes())?;
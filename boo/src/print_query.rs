@@ -73,8 +73,16 @@ fn get_error_table(width: u16, error: BoltError) -> Table {
         .set_content_arrangement(comfy_table::ContentArrangement::Dynamic)
         .set_table_width(width);
 
-    table.set_header(vec![Cell::new("Error")]);
-    table.add_row(vec![Cell::new(format!("{}", error))]);
+    match error.neo4j() {
+        Some(neo4j_error) => {
+            table.set_header(vec![Cell::new("Code"), Cell::new("Error")]);
+            table.add_row(vec![Cell::new(&neo4j_error.code), Cell::new(&neo4j_error.message)]);
+        }
+        None => {
+            table.set_header(vec![Cell::new("Error")]);
+            table.add_row(vec![Cell::new(format!("{}", error))]);
+        }
+    }
 
     return table;
 }
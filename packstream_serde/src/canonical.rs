@@ -0,0 +1,75 @@
+use crate::prelude::*;
+use super::error::PackstreamResult;
+use super::marker::Marker;
+use super::read::{ByteReader, Unpacker};
+use super::ser::key_content;
+
+/// Checks whether the bytes hold a single value in the canonical packstream encoding,
+/// as produced by [`to_bytes_canonical`](crate::to_bytes_canonical).
+///
+/// Canonical encoding requires that:
+/// * every marker uses the smallest representation of its integer value or size
+/// * list and map streams are not used
+/// * map keys are strings sorted in ascending byte order without duplicates
+/// * strings are valid UTF-8
+pub fn is_canonical(bytes: &[u8]) -> bool {
+    let mut reader = ByteReader::new(bytes);
+    matches!(check_value(&mut reader), Ok(true)) && reader.is_done()
+}
+
+fn check_value(reader: &mut ByteReader) -> PackstreamResult<bool> {
+    let bytes = reader.bytes;
+    let start = reader.index;
+    let marker = reader.peek_marker()?;
+
+    if let Marker::List(usize::MAX) | Marker::Map(usize::MAX) = marker {
+        return Ok(false);
+    }
+
+    let mut minimal = Vec::with_capacity(reader.peeked);
+    marker.append_to_vec(&mut minimal)?;
+    if minimal != bytes[start..start + reader.peeked] {
+        return Ok(false);
+    }
+    reader.scratch_peeked();
+
+    match marker {
+        Marker::String(len) => Ok(core::str::from_utf8(reader.consume_bytes(len)?).is_ok()),
+        Marker::Bytes(len) => reader.consume_bytes(len).map(|_| true),
+        Marker::List(len) => check_values(reader, len),
+        Marker::Struct(len) => {
+            reader.consume_bytes(1)?;
+            check_values(reader, len)
+        }
+        Marker::Map(len) => {
+            let mut previous_key: Option<&[u8]> = None;
+            for _ in 0..len {
+                let key_start = reader.index;
+                if !matches!(reader.peek_marker()?, Marker::String(_)) || !check_value(reader)? {
+                    return Ok(false);
+                }
+
+                let key = key_content(&bytes[key_start..reader.index]);
+                if matches!(previous_key, Some(previous) if previous >= key) {
+                    return Ok(false);
+                }
+                previous_key = Some(key);
+
+                if !check_value(reader)? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        }
+        _ => Ok(true),
+    }
+}
+
+fn check_values(reader: &mut ByteReader, len: usize) -> PackstreamResult<bool> {
+    for _ in 0..len {
+        if !check_value(reader)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
@@ -4,6 +4,7 @@ pub(crate) const STRUCTURE_SIG_KEY_B: &[u8] = b"__BOLT_STRUCTURE_SIGNATURE_KEY__
 pub(crate) const STRUCTURE_FIELDS_KEY: &str = "__BOLT_STRUCTURE_FIELDS_KEY__";
 pub(crate) const STRUCTURE_FIELDS_KEY_B: &[u8] = b"__BOLT_STRUCTURE_FIELDS_KEY__";
 pub(crate) const SIG_KEY: &str = "signature";
+pub(crate) const RAW_VALUE_NAME: &str = "__BOLT_RAW_VALUE_SERDE_NAME__";
 
 pub mod marker {
     pub const TINY_STRING: u8 = 0x80;
@@ -43,11 +44,22 @@ pub mod marker {
 
 pub mod message {
     pub const INIT: u8 = 0x01;
+    pub const HELLO: u8 = 0x01;
+    pub const GOODBYE: u8 = 0x02;
     pub const ACK_FAILURE: u8 = 0x0E;
     pub const RESET: u8 = 0x0F;
     pub const RUN: u8 = 0x10;
+    pub const BEGIN: u8 = 0x11;
+    pub const COMMIT: u8 = 0x12;
+    pub const ROLLBACK: u8 = 0x13;
     pub const DISCARD_ALL: u8 = 0x2F;
+    pub const DISCARD: u8 = 0x2F;
     pub const PULL_ALL: u8 = 0x3F;
+    pub const PULL: u8 = 0x3F;
+    pub const TELEMETRY: u8 = 0x54;
+    pub const ROUTE: u8 = 0x66;
+    pub const LOGON: u8 = 0x6A;
+    pub const LOGOFF: u8 = 0x6B;
     pub const SUCCESS: u8 = 0x70;
     pub const IGNORED: u8 = 0x7E;
     pub const FAILURE: u8 = 0x7F;
@@ -1,14 +1,26 @@
-use super::constants::{STRUCTURE_FIELDS_KEY_B, STRUCTURE_SIG_KEY_B};
+use crate::prelude::*;
+use super::constants::{RAW_VALUE_NAME, STRUCTURE_FIELDS_KEY_B, STRUCTURE_SIG_KEY_B};
 use super::error::{ErrorCode, PackstreamError, PackstreamResult};
 use super::marker::Marker;
+use super::options::{EnumRepr, IntegerPolicy, Options};
 use super::read::{ByteReader, Unpacker};
+use super::ser::key_content;
+use core::marker::PhantomData;
 use serde::de;
 
 pub fn from_bytes<'de, T>(bytes: &'de [u8]) -> PackstreamResult<T>
 where
     T: de::Deserialize<'de>,
 {
-    let mut de: Deserializer<ByteReader> = Deserializer::new(bytes);
+    from_bytes_with_options(bytes, Options::default())
+}
+
+/// Deserializes the value using given [`Options`].
+pub fn from_bytes_with_options<'de, T>(bytes: &'de [u8], options: Options) -> PackstreamResult<T>
+where
+    T: de::Deserialize<'de>,
+{
+    let mut de: Deserializer<ByteReader> = Deserializer::with_options(bytes, options);
     let value = de::Deserialize::deserialize(&mut de)?;
     de.is_done()?;
     Ok(value)
@@ -16,11 +28,22 @@ where
 
 mod errors {
     use super::{Marker, PackstreamError};
+    use alloc::format;
 
     pub(super) fn unexpected_marker(expected: &str, actual: &Marker) -> PackstreamError {
         PackstreamError::create(format!("Expected {}, got {} instead", expected, actual))
     }
 
+    pub(super) fn out_of_range<T: super::Narrow>(value: impl core::fmt::Display) -> PackstreamError {
+        PackstreamError::create(format!(
+            "Integer {} is out of the {} range {}..={}",
+            value,
+            core::any::type_name::<T>(),
+            T::LOWER,
+            T::UPPER
+        ))
+    }
+
     pub(super) fn invalid_length(kind: &str, expected: usize, actual: usize) -> PackstreamError {
         PackstreamError::create(format!(
             "Expected {} length to be equal to {}, got {} instead",
@@ -29,9 +52,27 @@ mod errors {
     }
 }
 
+/// Integer types which the packstream integers are narrowed into.
+trait Narrow: TryFrom<i64> + core::str::FromStr + core::fmt::Display + Copy {
+    const LOWER: Self;
+    const UPPER: Self;
+}
+
+macro_rules! impl_narrow {
+    ($($ty:ty),*) => {
+        $(impl Narrow for $ty {
+            const LOWER: Self = <$ty>::MIN;
+            const UPPER: Self = <$ty>::MAX;
+        })*
+    };
+}
+
+impl_narrow!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
 #[derive(Debug)]
 pub struct Deserializer<U> {
     read: U,
+    options: Options,
 }
 
 impl<'de, U> Deserializer<U>
@@ -39,8 +80,13 @@ where
     U: Unpacker<'de>,
 {
     pub fn new(bytes: &'de [u8]) -> Self {
+        Self::with_options(bytes, Options::default())
+    }
+
+    pub fn with_options(bytes: &'de [u8], options: Options) -> Self {
         Self {
             read: U::new(bytes),
+            options,
         }
     }
 
@@ -60,13 +106,19 @@ where
         }
     }
 
-    fn parse_int<T>(&mut self) -> PackstreamResult<T>
-    where
-        T: std::convert::TryFrom<i64>,
-        <T as std::convert::TryFrom<i64>>::Error: std::error::Error + 'static,
-    {
+    fn parse_int<T: Narrow>(&mut self) -> PackstreamResult<T> {
+        let policy = self.options.integer_policy;
         match self.read.consume_marker()? {
-            Marker::I64(num) => T::try_from(num).map_err(|e| PackstreamError::create(e.to_string())),
+            Marker::I64(num) => match T::try_from(num) {
+                Ok(value) => Ok(value),
+                Err(_) if policy == IntegerPolicy::Saturate && num < 0 => Ok(T::LOWER),
+                Err(_) if policy == IntegerPolicy::Saturate => Ok(T::UPPER),
+                Err(_) => Err(errors::out_of_range::<T>(num)),
+            },
+            Marker::String(len) if policy == IntegerPolicy::String => {
+                let text = core::str::from_utf8(self.read.consume_bytes(len)?)?;
+                text.parse().map_err(|_| errors::out_of_range::<T>(format_args!("'{}'", text)))
+            }
             m => Err(errors::unexpected_marker("Marker::I64", &m)),
         }
     }
@@ -78,6 +130,18 @@ where
         }
     }
 
+    fn parse_f32(&mut self) -> PackstreamResult<f32> {
+        let num = self.parse_f64()?;
+        let narrowed = num as f32;
+        if self.options.strict_f32 && !num.is_nan() && f64::from(narrowed) != num {
+            return Err(PackstreamError::create(format!(
+                "Float {} cannot be represented as f32 without loss",
+                num
+            )));
+        }
+        Ok(narrowed)
+    }
+
     fn parse_char(&mut self) -> PackstreamResult<char> {
         match self.read.consume_marker()? {
             Marker::String(len) if len == 1 => Ok(self.read.consume_bytes(1)?[0] as char),
@@ -87,7 +151,7 @@ where
 
     fn parse_str(&mut self) -> PackstreamResult<&'de str> {
         match self.read.consume_marker()? {
-            Marker::String(len) => Ok(std::str::from_utf8(self.read.consume_bytes(len)?)?),
+            Marker::String(len) => Ok(core::str::from_utf8(self.read.consume_bytes(len)?)?),
             m => Err(errors::unexpected_marker("Marker::String", &m)),
         }
     }
@@ -147,6 +211,43 @@ where
             m => Err(errors::unexpected_marker("Marker::Map(1)", &m)),
         }
     }
+
+    /// Reads the map of an internally or adjacently tagged enum.
+    ///
+    /// Entries are borrowed undecoded, so the tag does not have to be the first key.
+    fn parse_tagged(&mut self, tag: &str, content: Option<&str>) -> PackstreamResult<TaggedAccess<'de, U>> {
+        let len = self.parse_map()?;
+        let mut variant = None;
+        let mut entries = Vec::with_capacity(len);
+        for _ in 0..len {
+            let key = self.read.skip_value()?;
+            let value = self.read.skip_value()?;
+            if key_content(key) == tag.as_bytes() {
+                variant = Some(value);
+            } else {
+                entries.push((key, value));
+            }
+        }
+
+        let variant = variant
+            .ok_or_else(|| PackstreamError::create(format!("Expected enum tag key '{}'", tag)))?;
+        let content = match content {
+            None => TaggedContent::Entries(entries),
+            Some(content) => TaggedContent::Value(
+                entries
+                    .into_iter()
+                    .find(|(key, _)| key_content(key) == content.as_bytes())
+                    .map(|(_, value)| value),
+            ),
+        };
+
+        Ok(TaggedAccess {
+            variant,
+            content,
+            options: self.options,
+            read: PhantomData,
+        })
+    }
 }
 
 impl<'de, 'a, U> de::Deserializer<'de> for &'a mut Deserializer<U>
@@ -235,11 +336,25 @@ where
         visitor.visit_u64(self.parse_int()?)
     }
 
+    fn deserialize_i128<V>(self, visitor: V) -> PackstreamResult<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_i128(self.parse_int()?)
+    }
+
+    fn deserialize_u128<V>(self, visitor: V) -> PackstreamResult<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_u128(self.parse_int()?)
+    }
+
     fn deserialize_f32<V>(self, visitor: V) -> PackstreamResult<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        visitor.visit_f32(self.parse_f64()? as f32)
+        visitor.visit_f32(self.parse_f32()?)
     }
 
     fn deserialize_f64<V>(self, visitor: V) -> PackstreamResult<V::Value>
@@ -310,10 +425,13 @@ where
         self.deserialize_unit(visitor)
     }
 
-    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> PackstreamResult<V::Value>
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> PackstreamResult<V::Value>
     where
         V: de::Visitor<'de>,
     {
+        if name == RAW_VALUE_NAME {
+            return visitor.visit_borrowed_bytes(self.read.skip_value()?);
+        }
         visitor.visit_newtype_struct(self)
     }
 
@@ -398,6 +516,20 @@ where
     where
         V: de::Visitor<'de>,
     {
+        match self.options.enum_repr {
+            EnumRepr::External => (),
+            EnumRepr::UnitAsString => {
+                if let Marker::String(_) = self.read.peek_marker()? {
+                    let variant = self.parse_str()?;
+                    return visitor.visit_enum(de::value::BorrowedStrDeserializer::new(variant));
+                }
+            }
+            EnumRepr::Internal { tag } => return visitor.visit_enum(self.parse_tagged(tag, None)?),
+            EnumRepr::Adjacent { tag, content } => {
+                return visitor.visit_enum(self.parse_tagged(tag, Some(content))?)
+            }
+        }
+
         self.parse_enum()?;
         visitor.visit_enum(VariantAccess { de: self })
     }
@@ -535,6 +667,177 @@ where
     }
 }
 
+enum TaggedContent<'de> {
+    /// Entries of the internally tagged map other than the tag.
+    Entries(Vec<(&'de [u8], &'de [u8])>),
+    /// Value of the adjacently tagged content key.
+    Value(Option<&'de [u8]>),
+}
+
+/// Access to the internally or adjacently tagged enum, holding borrowed encoded values.
+struct TaggedAccess<'de, U> {
+    variant: &'de [u8],
+    content: TaggedContent<'de>,
+    options: Options,
+    read: PhantomData<U>,
+}
+
+impl<'de, U> TaggedAccess<'de, U>
+where
+    U: Unpacker<'de>,
+{
+    fn deserializer(&self, bytes: &'de [u8]) -> Deserializer<U> {
+        Deserializer::with_options(bytes, self.options)
+    }
+
+    fn content(&self) -> PackstreamResult<&'de [u8]> {
+        match self.content {
+            TaggedContent::Value(Some(value)) => Ok(value),
+            TaggedContent::Value(None) => Err(PackstreamError::create("Expected enum content key")),
+            TaggedContent::Entries(_) => Err(PackstreamError::create(
+                "Internally tagged enum supports only unit, newtype and struct variants",
+            )),
+        }
+    }
+}
+
+impl<'de, U> de::EnumAccess<'de> for TaggedAccess<'de, U>
+where
+    U: Unpacker<'de>,
+{
+    type Error = PackstreamError;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> PackstreamResult<(V::Value, Self)>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let value = seed.deserialize(&mut self.deserializer(self.variant))?;
+        Ok((value, self))
+    }
+}
+
+impl<'de, U> de::VariantAccess<'de> for TaggedAccess<'de, U>
+where
+    U: Unpacker<'de>,
+{
+    type Error = PackstreamError;
+
+    fn unit_variant(self) -> PackstreamResult<()> {
+        match self.content {
+            TaggedContent::Entries(_) | TaggedContent::Value(None) => Ok(()),
+            TaggedContent::Value(Some(value)) => de::Deserialize::deserialize(&mut self.deserializer(value)),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> PackstreamResult<T::Value>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match self.content {
+            TaggedContent::Entries(entries) => seed.deserialize(EntriesDeserializer {
+                entries,
+                options: self.options,
+                read: PhantomData::<U>,
+            }),
+            _ => seed.deserialize(&mut self.deserializer(self.content()?)),
+        }
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> PackstreamResult<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        de::Deserializer::deserialize_seq(&mut self.deserializer(self.content()?), visitor)
+    }
+
+    fn struct_variant<V>(self, fields: &'static [&'static str], visitor: V) -> PackstreamResult<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.content {
+            TaggedContent::Entries(entries) => visitor.visit_map(EntriesAccess::<U> {
+                entries: entries.into_iter(),
+                value: None,
+                options: self.options,
+                read: PhantomData,
+            }),
+            _ => de::Deserializer::deserialize_struct(&mut self.deserializer(self.content()?), "", fields, visitor),
+        }
+    }
+}
+
+/// Deserializes remaining entries of the internally tagged map as a map.
+struct EntriesDeserializer<'de, U> {
+    entries: Vec<(&'de [u8], &'de [u8])>,
+    options: Options,
+    read: PhantomData<U>,
+}
+
+impl<'de, U> de::Deserializer<'de> for EntriesDeserializer<'de, U>
+where
+    U: Unpacker<'de>,
+{
+    type Error = PackstreamError;
+
+    fn deserialize_any<V>(self, visitor: V) -> PackstreamResult<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_map(EntriesAccess::<U> {
+            entries: self.entries.into_iter(),
+            value: None,
+            options: self.options,
+            read: PhantomData,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct EntriesAccess<'de, U> {
+    entries: alloc::vec::IntoIter<(&'de [u8], &'de [u8])>,
+    value: Option<&'de [u8]>,
+    options: Options,
+    read: PhantomData<U>,
+}
+
+impl<'de, U> de::MapAccess<'de> for EntriesAccess<'de, U>
+where
+    U: Unpacker<'de>,
+{
+    type Error = PackstreamError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> PackstreamResult<Option<K::Value>>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(&mut Deserializer::<U>::with_options(key, self.options))
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> PackstreamResult<V::Value>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| PackstreamError::impl_err("next_key must be called before next_value"))?;
+        seed.deserialize(&mut Deserializer::<U>::with_options(value, self.options))
+    }
+}
+
 enum StructureAccessState {
     Signature,
     Fields,
@@ -1,7 +1,8 @@
+use crate::prelude::*;
 use serde::{de, ser};
-use std::fmt;
+use core::fmt;
 
-pub type PackstreamResult<T> = std::result::Result<T, PackstreamError>;
+pub type PackstreamResult<T> = core::result::Result<T, PackstreamError>;
 
 #[derive(Debug)]
 pub struct PackstreamError {
@@ -48,20 +49,32 @@ impl de::Error for PackstreamError {
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for PackstreamError {
     fn cause(&self) -> Option<&(dyn std::error::Error + 'static)> {
         Some(self)
     }
 }
 
-impl From<std::str::Utf8Error> for PackstreamError {
-    fn from(m: std::str::Utf8Error) -> Self {
+/// Serde requires its own error trait in place of `std::error::Error` without `std`.
+#[cfg(not(feature = "std"))]
+impl de::StdError for PackstreamError {}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for PackstreamError {
+    fn from(m: std::io::Error) -> Self {
+        Self::create(m.to_string())
+    }
+}
+
+impl From<core::str::Utf8Error> for PackstreamError {
+    fn from(m: core::str::Utf8Error) -> Self {
         Self::create(m.to_string())
     }
 }
 
-impl From<std::string::FromUtf8Error> for PackstreamError {
-    fn from(m: std::string::FromUtf8Error) -> Self {
+impl From<alloc::string::FromUtf8Error> for PackstreamError {
+    fn from(m: alloc::string::FromUtf8Error) -> Self {
         Self::create(m.to_string())
     }
 }
@@ -51,9 +51,35 @@
 //! # }
 //! #
 //! ```
+//!
+//! # Features
+//! - `std` (default) - implements `std::error::Error` and `io::Error` conversions for
+//!   [`PackstreamError`](error::PackstreamError). Without it the crate is `no_std` and
+//!   requires only `alloc`.
+//! - `geo-types` - conversions between points and `geo_types` types.
+//!
 //! [7687.org]: https://7687.org/.
 //! [`Value`]: value::Value
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+/// Items of the `alloc` crate missing from the `no_std` prelude.
+#[allow(unused_imports)]
+mod prelude {
+    pub(crate) use alloc::borrow::ToOwned;
+    pub(crate) use alloc::boxed::Box;
+    pub(crate) use alloc::string::{String, ToString};
+    pub(crate) use alloc::vec::Vec;
+    pub(crate) use alloc::{format, vec};
+}
+
+/// Map used for [`Value::Map`] and properties of structures and messages.
+///
+/// It is a `BTreeMap` with and without the `std` feature, so the feature never changes public types.
+pub type Map<K, V> = alloc::collections::BTreeMap<K, V>;
+
 pub mod packstream;
 pub use packstream::{PackstreamStructure, EmptyPackstreamStructure};
 
@@ -66,15 +92,25 @@ pub mod marker;
 pub mod read;
 pub mod value;
 #[doc(inline)]
-pub use value::{from_value, to_value, Structure, Value, structure};
+pub use value::{from_value, to_value, to_value_with_options, from_text, to_text, Structure, Value, structure};
 
 pub mod message;
 pub use message::{RequestMessage, SummaryMessage, Record};
 
 mod de;
 #[doc(inline)]
-pub use de::from_bytes;
+pub use de::{from_bytes, from_bytes_with_options};
 
 mod ser;
 #[doc(inline)]
-pub use ser::to_bytes;
+pub use ser::{to_bytes, to_bytes_canonical, to_bytes_with_options};
+
+pub mod options;
+pub mod schema;
+
+mod raw;
+pub use raw::{RawValue, RawValueBuf};
+
+mod canonical;
+#[doc(inline)]
+pub use canonical::is_canonical;
@@ -81,7 +81,7 @@ macro_rules! serialize_length {
 macro_rules! value_map {
     ($($key:literal => $value:expr,)*) => {
         {
-            let mut __map = ::std::collections::HashMap::new();
+            let mut __map = $crate::Map::new();
             __map.insert(String::from($crate::constants::SIG_KEY), $crate::value::Value::I64(<Self as $crate::packstream::PackstreamStructure>::SIG as i64));
             $(__map.insert(String::from($key), $value);)*
             $crate::value::Value::Map(__map)
@@ -1,6 +1,7 @@
+use crate::prelude::*;
 use super::constants::marker::*;
 use super::error::{PackstreamError, PackstreamResult};
-use std::fmt;
+use core::fmt;
 
 /// Represents the marker byte of the core packstream data types.
 ///
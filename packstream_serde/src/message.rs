@@ -2,27 +2,55 @@
 //!  [List of Bolt Messages](https://7687.org/bolt/bolt-protocol-message-specification-1.html#messages)
 //!
 
-use std::fmt;
+use crate::prelude::*;
+use core::fmt;
 use crate::packstream::PackstreamStructure;
-use serde::de::Error;
+use crate::Value;
+use serde::de::{DeserializeOwned, Error};
 
+mod auth_token;
 mod init;
+mod hello;
+mod goodbye;
 mod ack_failure;
 mod reset;
 mod run;
+mod run_with_metadata;
+mod begin;
+mod commit;
+mod rollback;
 mod discard_all;
+mod discard;
 mod pull_all;
+mod pull;
+mod logon;
+mod logoff;
+mod telemetry;
+mod route;
 mod success;
 mod ignored;
 mod failure;
 mod record;
 
+pub use auth_token::AuthToken;
 pub use init::{BasicAuth, Init};
+pub use hello::Hello;
+pub use goodbye::Goodbye;
 pub use ack_failure::AckFailure;
 pub use reset::Reset;
 pub use run::Run;
+pub use run_with_metadata::RunWithMetadata;
+pub use begin::Begin;
+pub use commit::Commit;
+pub use rollback::Rollback;
 pub use discard_all::DiscardAll;
+pub use discard::Discard;
 pub use pull_all::PullAll;
+pub use pull::Pull;
+pub use logon::Logon;
+pub use logoff::Logoff;
+pub use telemetry::Telemetry;
+pub use route::Route;
 pub use success::Success;
 pub use ignored::Ignored;
 pub use failure::Failure;
@@ -35,11 +63,23 @@ mod de;
 #[derive(PartialEq)]
 pub enum RequestMessage {
     Init(Init),
+    Hello(Hello),
+    Goodbye(Goodbye),
     AckFailure(AckFailure),
     Reset(Reset),
     Run(Run),
+    RunWithMetadata(RunWithMetadata),
+    Begin(Begin),
+    Commit(Commit),
+    Rollback(Rollback),
     DiscardAll(DiscardAll),
+    Discard(Discard),
     PullAll(PullAll),
+    Pull(Pull),
+    Logon(Logon),
+    Logoff(Logoff),
+    Telemetry(Telemetry),
+    Route(Route),
 }
 
 impl RequestMessage {
@@ -48,10 +88,22 @@ impl RequestMessage {
         V: serde::de::MapAccess<'de>,
     {
         match map_access.next_value::<u8>()? {
+            // Messages sharing signatures are told apart by the number of fields.
             Init::SIG => {
-                let (client, auth) = structure_access!(map_access, Init, no_sig_key);
+                let fields = next_fields(map_access)?;
+                if fields.len() == usize::from(Hello::LEN) {
+                    let fields = from_fields::<V, Hello>(fields)?;
+                    return Ok(Self::from(Hello {
+                        extra: fields.value(),
+                    }));
+                }
+                let (client, auth) = from_fields::<V, Init>(fields)?;
                 Ok(Self::from(Init { client, auth }))
             }
+            Goodbye::SIG => {
+                structure_access!(map_access, Goodbye, no_sig_key);
+                Ok(Self::from(Goodbye))
+            }
             AckFailure::SIG => {
                 structure_access!(map_access, AckFailure, no_sig_key);
                 Ok(Self::from(AckFailure))
@@ -61,20 +113,81 @@ impl RequestMessage {
                 Ok(Self::from(Reset))
             }
             Run::SIG => {
-                let (statement, parameters) = structure_access!(map_access, Run, no_sig_key);
+                let fields = next_fields(map_access)?;
+                if fields.len() == usize::from(RunWithMetadata::LEN) {
+                    let (statement, parameters, extra) = from_fields::<V, RunWithMetadata>(fields)?;
+                    return Ok(Self::from(RunWithMetadata {
+                        statement,
+                        parameters,
+                        extra,
+                    }));
+                }
+                let (statement, parameters) = from_fields::<V, Run>(fields)?;
                 Ok(Self::from(Run {
                     statement,
                     parameters,
                 }))
             }
+            Begin::SIG => {
+                let fields = structure_access!(map_access, Begin, no_sig_key);
+                Ok(Self::from(Begin {
+                    extra: fields.value(),
+                }))
+            }
+            Commit::SIG => {
+                structure_access!(map_access, Commit, no_sig_key);
+                Ok(Self::from(Commit))
+            }
+            Rollback::SIG => {
+                structure_access!(map_access, Rollback, no_sig_key);
+                Ok(Self::from(Rollback))
+            }
             DiscardAll::SIG => {
-                structure_access!(map_access, DiscardAll, no_sig_key);
+                let fields = next_fields(map_access)?;
+                if fields.len() == usize::from(Discard::LEN) {
+                    let fields = from_fields::<V, Discard>(fields)?;
+                    return Ok(Self::from(Discard {
+                        extra: fields.value(),
+                    }));
+                }
+                from_fields::<V, DiscardAll>(fields)?;
                 Ok(Self::from(DiscardAll))
             }
             PullAll::SIG => {
-                structure_access!(map_access, PullAll, no_sig_key);
+                let fields = next_fields(map_access)?;
+                if fields.len() == usize::from(Pull::LEN) {
+                    let fields = from_fields::<V, Pull>(fields)?;
+                    return Ok(Self::from(Pull {
+                        extra: fields.value(),
+                    }));
+                }
+                from_fields::<V, PullAll>(fields)?;
                 Ok(Self::from(PullAll))
             }
+            Logon::SIG => {
+                let fields = structure_access!(map_access, Logon, no_sig_key);
+                Ok(Self::from(Logon {
+                    auth: fields.value(),
+                }))
+            }
+            Logoff::SIG => {
+                structure_access!(map_access, Logoff, no_sig_key);
+                Ok(Self::from(Logoff))
+            }
+            Telemetry::SIG => {
+                let fields = structure_access!(map_access, Telemetry, no_sig_key);
+                Ok(Self::from(Telemetry {
+                    api: fields.value(),
+                }))
+            }
+            Route::SIG => {
+                let (routing, bookmarks, db) = structure_access!(map_access, Route, no_sig_key);
+                Ok(Self::from(Route {
+                    routing,
+                    bookmarks,
+                    db,
+                }))
+            }
             signature => Err(V::Error::custom(format!(
                 "Expected signature of a known Structure, got {}",
                 signature,
@@ -82,15 +195,47 @@ impl RequestMessage {
         }
     }
 }
+/// Reads fields of the structure without knowing its type.
+fn next_fields<'de, V>(map_access: &mut V) -> Result<Vec<Value>, V::Error>
+where
+    V: serde::de::MapAccess<'de>,
+{
+    check!(__key, map_access, crate::constants::STRUCTURE_FIELDS_KEY);
+    let fields = map_access.next_value::<Vec<Value>>()?;
+    check!(__key, map_access);
+    Ok(fields)
+}
+
+fn from_fields<'de, V, S>(fields: Vec<Value>) -> Result<S::Fields, V::Error>
+where
+    V: serde::de::MapAccess<'de>,
+    S: PackstreamStructure,
+    S::Fields: DeserializeOwned,
+{
+    crate::from_value(Value::List(fields)).map_err(V::Error::custom)
+}
+
 impl fmt::Debug for RequestMessage {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
       match self {
           Self::Init(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::Hello(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::Goodbye(v) => f.debug_tuple("RequestMessage").field(v).finish(),
           Self::AckFailure(v) => f.debug_tuple("RequestMessage").field(v).finish(),
           Self::Reset(v) => f.debug_tuple("RequestMessage").field(v).finish(),
           Self::Run(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::RunWithMetadata(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::Begin(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::Commit(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::Rollback(v) => f.debug_tuple("RequestMessage").field(v).finish(),
           Self::DiscardAll(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::Discard(v) => f.debug_tuple("RequestMessage").field(v).finish(),
           Self::PullAll(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::Pull(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::Logon(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::Logoff(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::Telemetry(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::Route(v) => f.debug_tuple("RequestMessage").field(v).finish(),
       }
   }
 }
@@ -99,11 +244,23 @@ impl fmt::Display for RequestMessage {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
           Self::Init(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::Hello(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::Goodbye(v) => f.debug_tuple("RequestMessage").field(v).finish(),
           Self::AckFailure(v) => f.debug_tuple("RequestMessage").field(v).finish(),
           Self::Reset(v) => f.debug_tuple("RequestMessage").field(v).finish(),
           Self::Run(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::RunWithMetadata(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::Begin(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::Commit(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::Rollback(v) => f.debug_tuple("RequestMessage").field(v).finish(),
           Self::DiscardAll(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::Discard(v) => f.debug_tuple("RequestMessage").field(v).finish(),
           Self::PullAll(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::Pull(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::Logon(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::Logoff(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::Telemetry(v) => f.debug_tuple("RequestMessage").field(v).finish(),
+          Self::Route(v) => f.debug_tuple("RequestMessage").field(v).finish(),
         }
     }
 }
@@ -114,6 +271,18 @@ impl From<Init> for RequestMessage {
     }
 }
 
+impl From<Hello> for RequestMessage {
+    fn from(value: Hello) -> Self {
+        Self::Hello(value)
+    }
+}
+
+impl From<Goodbye> for RequestMessage {
+    fn from(value: Goodbye) -> Self {
+        Self::Goodbye(value)
+    }
+}
+
 impl From<AckFailure> for RequestMessage {
     fn from(value: AckFailure) -> Self {
         Self::AckFailure(value)
@@ -132,18 +301,78 @@ impl From<Run> for RequestMessage {
     }
 }
 
+impl From<RunWithMetadata> for RequestMessage {
+    fn from(value: RunWithMetadata) -> Self {
+        Self::RunWithMetadata(value)
+    }
+}
+
+impl From<Begin> for RequestMessage {
+    fn from(value: Begin) -> Self {
+        Self::Begin(value)
+    }
+}
+
+impl From<Commit> for RequestMessage {
+    fn from(value: Commit) -> Self {
+        Self::Commit(value)
+    }
+}
+
+impl From<Rollback> for RequestMessage {
+    fn from(value: Rollback) -> Self {
+        Self::Rollback(value)
+    }
+}
+
 impl From<DiscardAll> for RequestMessage {
     fn from(value: DiscardAll) -> Self {
         Self::DiscardAll(value)
     }
 }
 
+impl From<Discard> for RequestMessage {
+    fn from(value: Discard) -> Self {
+        Self::Discard(value)
+    }
+}
+
 impl From<PullAll> for RequestMessage {
     fn from(value: PullAll) -> Self {
         Self::PullAll(value)
     }
 }
 
+impl From<Pull> for RequestMessage {
+    fn from(value: Pull) -> Self {
+        Self::Pull(value)
+    }
+}
+
+impl From<Logon> for RequestMessage {
+    fn from(value: Logon) -> Self {
+        Self::Logon(value)
+    }
+}
+
+impl From<Logoff> for RequestMessage {
+    fn from(value: Logoff) -> Self {
+        Self::Logoff(value)
+    }
+}
+
+impl From<Telemetry> for RequestMessage {
+    fn from(value: Telemetry) -> Self {
+        Self::Telemetry(value)
+    }
+}
+
+impl From<Route> for RequestMessage {
+    fn from(value: Route) -> Self {
+        Self::Route(value)
+    }
+}
+
 /// Represents summary message.
 #[derive(PartialEq)]
 pub enum SummaryMessage {
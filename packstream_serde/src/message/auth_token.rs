@@ -0,0 +1,303 @@
+use crate::prelude::*;
+use crate::{
+    error::{PackstreamError, PackstreamResult},
+    Value,
+};
+use super::BasicAuth;
+use serde::{
+    de::{self, Error},
+    ser::{self, SerializeMap},
+};
+use crate::Map;
+use core::fmt;
+
+/// Represents the auth map sent by the `Init` and `Logon` messages.
+///
+/// Known schemes are modeled as separate variants, any other scheme
+/// can be sent with [`AuthToken::Custom`] along with its parameters.
+#[derive(Clone, PartialEq)]
+pub enum AuthToken {
+    None,
+    Basic {
+        principal: String,
+        credentials: String,
+        realm: Option<String>,
+    },
+    Bearer {
+        credentials: String,
+    },
+    Kerberos {
+        ticket: String,
+    },
+    /// Absent keys are omitted from the auth map.
+    Custom {
+        scheme: String,
+        principal: Option<String>,
+        credentials: Option<String>,
+        realm: Option<String>,
+        parameters: Map<String, Value>,
+    },
+}
+
+impl AuthToken {
+    pub fn none() -> Self {
+        Self::None
+    }
+
+    pub fn basic(principal: impl Into<String>, credentials: impl Into<String>) -> Self {
+        Self::Basic {
+            principal: principal.into(),
+            credentials: credentials.into(),
+            realm: None,
+        }
+    }
+
+    pub fn bearer(credentials: impl Into<String>) -> Self {
+        Self::Bearer {
+            credentials: credentials.into(),
+        }
+    }
+
+    /// Creates token with base64 encoded Kerberos ticket.
+    pub fn kerberos(ticket: impl Into<String>) -> Self {
+        Self::Kerberos {
+            ticket: ticket.into(),
+        }
+    }
+
+    pub fn scheme(&self) -> &str {
+        match self {
+            Self::None => "none",
+            Self::Basic { .. } => "basic",
+            Self::Bearer { .. } => "bearer",
+            Self::Kerberos { .. } => "kerberos",
+            Self::Custom { scheme, .. } => scheme,
+        }
+    }
+
+    /// Returns entries of the auth map in the order they are serialized.
+    fn entries(&self) -> Vec<(&'static str, AuthEntry<'_>)> {
+        let mut entries = vec![("scheme", AuthEntry::Str(self.scheme()))];
+
+        match self {
+            Self::None => {}
+            Self::Basic {
+                principal,
+                credentials,
+                realm,
+            } => {
+                entries.push(("principal", AuthEntry::Str(principal)));
+                entries.push(("credentials", AuthEntry::Str(credentials)));
+                if let Some(realm) = realm {
+                    entries.push(("realm", AuthEntry::Str(realm)));
+                }
+            }
+            Self::Bearer { credentials } => {
+                entries.push(("credentials", AuthEntry::Str(credentials)));
+            }
+            Self::Kerberos { ticket } => {
+                entries.push(("principal", AuthEntry::Str("")));
+                entries.push(("credentials", AuthEntry::Str(ticket)));
+            }
+            Self::Custom {
+                principal,
+                credentials,
+                realm,
+                parameters,
+                ..
+            } => {
+                if let Some(principal) = principal {
+                    entries.push(("principal", AuthEntry::Str(principal)));
+                }
+                if let Some(credentials) = credentials {
+                    entries.push(("credentials", AuthEntry::Str(credentials)));
+                }
+                if let Some(realm) = realm {
+                    entries.push(("realm", AuthEntry::Str(realm)));
+                }
+                if !parameters.is_empty() {
+                    entries.push(("parameters", AuthEntry::Map(parameters)));
+                }
+            }
+        }
+
+        entries
+    }
+
+    pub fn into_map(self) -> Map<String, Value> {
+        match crate::to_value(&self) {
+            Ok(Value::Map(map)) => map,
+            _ => unreachable!("AuthToken always serializes into a map of strings."),
+        }
+    }
+
+    pub(crate) fn into_value(self) -> Value {
+        Value::Map(self.into_map())
+    }
+
+    pub fn from_map(mut map: Map<String, Value>) -> PackstreamResult<Self> {
+        fn take_string(map: &mut Map<String, Value>, key: &str) -> PackstreamResult<Option<String>> {
+            match map.remove(key) {
+                None | Some(Value::Null) => Ok(None),
+                Some(Value::String(value)) => Ok(Some(value)),
+                Some(value) => Err(PackstreamError::create(format!(
+                    "Expected auth token '{}' to be a string, got {} instead",
+                    key, value
+                ))),
+            }
+        }
+
+        fn require(value: Option<String>, scheme: &str, key: &str) -> PackstreamResult<String> {
+            value.ok_or_else(|| {
+                PackstreamError::create(format!(
+                    "Auth token with '{}' scheme requires '{}' key",
+                    scheme, key
+                ))
+            })
+        }
+
+        let scheme = require(take_string(&mut map, "scheme")?, "any", "scheme")?;
+        let principal = take_string(&mut map, "principal")?;
+        let credentials = take_string(&mut map, "credentials")?;
+        let realm = take_string(&mut map, "realm")?;
+        let parameters = match map.remove("parameters") {
+            None | Some(Value::Null) => Map::new(),
+            Some(Value::Map(parameters)) => parameters,
+            Some(value) => {
+                return Err(PackstreamError::create(format!(
+                    "Expected auth token 'parameters' to be a map, got {} instead",
+                    value
+                )))
+            }
+        };
+
+        if let Some(key) = map.keys().next() {
+            return Err(PackstreamError::create(format!(
+                "Unexpected auth token key '{}'",
+                key
+            )));
+        }
+
+        if !parameters.is_empty() {
+            return Ok(Self::Custom {
+                principal,
+                credentials,
+                scheme,
+                realm,
+                parameters,
+            });
+        }
+
+        match scheme.as_str() {
+            "none" => Ok(Self::None),
+            "basic" => Ok(Self::Basic {
+                principal: require(principal, &scheme, "principal")?,
+                credentials: require(credentials, &scheme, "credentials")?,
+                realm,
+            }),
+            "bearer" => Ok(Self::Bearer {
+                credentials: require(credentials, &scheme, "credentials")?,
+            }),
+            "kerberos" => Ok(Self::Kerberos {
+                ticket: require(credentials, &scheme, "credentials")?,
+            }),
+            _ => Ok(Self::Custom {
+                principal,
+                credentials,
+                scheme,
+                realm,
+                parameters,
+            }),
+        }
+    }
+}
+
+impl From<BasicAuth> for AuthToken {
+    fn from(auth: BasicAuth) -> Self {
+        if auth.scheme == "basic" {
+            Self::Basic {
+                principal: auth.principal,
+                credentials: auth.credentials,
+                realm: None,
+            }
+        } else {
+            Self::Custom {
+                scheme: auth.scheme,
+                principal: Some(auth.principal),
+                credentials: Some(auth.credentials),
+                realm: None,
+                parameters: Map::new(),
+            }
+        }
+    }
+}
+
+enum AuthEntry<'a> {
+    Str(&'a str),
+    Map(&'a Map<String, Value>),
+}
+
+impl<'a> ser::Serialize for AuthEntry<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match self {
+            Self::Str(value) => serializer.serialize_str(value),
+            Self::Map(value) => value.serialize(serializer),
+        }
+    }
+}
+
+impl fmt::Debug for AuthEntry<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Str(value) => fmt::Debug::fmt(value, f),
+            Self::Map(value) => fmt::Debug::fmt(value, f),
+        }
+    }
+}
+
+impl ser::Serialize for AuthToken {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let entries = self.entries();
+        let mut map_serializer = serializer.serialize_map(Some(entries.len()))?;
+        for (key, value) in entries {
+            map_serializer.serialize_entry(key, &value)?;
+        }
+        map_serializer.end()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for AuthToken {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let map = Map::<String, Value>::deserialize(deserializer)?;
+        AuthToken::from_map(map).map_err(D::Error::custom)
+    }
+}
+
+impl fmt::Debug for AuthToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut map = f.debug_map();
+        for (key, value) in self.entries() {
+            if key == "credentials" {
+                map.entry(&key, &"...");
+            } else {
+                map.entry(&key, &value);
+            }
+        }
+        map.finish()
+    }
+}
+
+impl fmt::Display for AuthToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("AuthToken").field(self).finish()
+    }
+}
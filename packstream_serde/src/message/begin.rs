@@ -0,0 +1,99 @@
+use crate::prelude::*;
+use crate::{
+    constants::{message, STRUCTURE_NAME},
+    error::{PackstreamError, PackstreamResult},
+    packstream::{PackstreamStructure, Single},
+    Value,
+};
+use serde::{
+    de, forward_to_deserialize_any,
+    ser::{self, SerializeTupleStruct},
+};
+use crate::Map;
+use core::fmt;
+
+/// Begins an explicit transaction since Bolt 3.
+///
+/// The extra map carries the transaction options such as `tx_timeout` and `tx_metadata`.
+#[derive(Debug, PartialEq)]
+pub struct Begin {
+    pub extra: Map<String, Value>,
+}
+
+impl PackstreamStructure for Begin {
+    const SIG: u8 = message::BEGIN;
+    const LEN: u8 = 0x01;
+    const SERIALIZE_LEN: usize = serialize_length!(Self::SIG, Self::LEN);
+
+    type Fields = Single<Map<String, Value>>;
+
+    fn into_value(self) -> Value {
+        value_map! {
+            "extra" => Value::Map(self.extra),
+        }
+    }
+}
+
+impl fmt::Display for Begin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Begin").field(&self.extra).finish()
+    }
+}
+
+impl ser::Serialize for Begin {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let mut ts_serializer =
+            serializer.serialize_tuple_struct(STRUCTURE_NAME, Self::SERIALIZE_LEN)?;
+        ts_serializer.serialize_field(&self.extra)?;
+        ts_serializer.end()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Begin {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(BeginVisitor)
+    }
+}
+
+struct BeginVisitor;
+
+impl<'de> de::Visitor<'de> for BeginVisitor {
+    type Value = Begin;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Begin")
+    }
+
+    fn visit_map<V>(self, mut map_access: V) -> Result<Self::Value, V::Error>
+    where
+        V: de::MapAccess<'de>,
+    {
+        let fields = structure_access!(map_access, Begin);
+        Ok(Begin {
+            extra: fields.value(),
+        })
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Begin {
+    type Error = PackstreamError;
+
+    fn deserialize_any<V>(self, visitor: V) -> PackstreamResult<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.into_value().deserialize_map(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier enum ignored_any
+    }
+}
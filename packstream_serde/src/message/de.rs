@@ -1,10 +1,11 @@
+use crate::prelude::*;
 use super::{RequestMessage, SummaryMessage};
 use crate::{
     constants::STRUCTURE_SIG_KEY,
     error::{PackstreamError, PackstreamResult},
 };
 use serde::{de, forward_to_deserialize_any};
-use std::fmt;
+use core::fmt;
 
 struct RequestMessageVisitor;
 
@@ -42,11 +43,23 @@ impl<'de> de::Deserializer<'de> for RequestMessage {
     {
         match self {
             Self::Init(de) => de.deserialize_any(visitor),
+            Self::Hello(de) => de.deserialize_any(visitor),
+            Self::Goodbye(de) => de.deserialize_any(visitor),
             Self::AckFailure(de) => de.deserialize_any(visitor),
             Self::Reset(de) => de.deserialize_any(visitor),
             Self::Run(de) => de.deserialize_any(visitor),
+            Self::RunWithMetadata(de) => de.deserialize_any(visitor),
+            Self::Begin(de) => de.deserialize_any(visitor),
+            Self::Commit(de) => de.deserialize_any(visitor),
+            Self::Rollback(de) => de.deserialize_any(visitor),
             Self::DiscardAll(de) => de.deserialize_any(visitor),
+            Self::Discard(de) => de.deserialize_any(visitor),
             Self::PullAll(de) => de.deserialize_any(visitor),
+            Self::Pull(de) => de.deserialize_any(visitor),
+            Self::Logon(de) => de.deserialize_any(visitor),
+            Self::Logoff(de) => de.deserialize_any(visitor),
+            Self::Telemetry(de) => de.deserialize_any(visitor),
+            Self::Route(de) => de.deserialize_any(visitor),
         }
     }
 
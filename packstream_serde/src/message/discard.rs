@@ -0,0 +1,117 @@
+use crate::prelude::*;
+use crate::{
+    constants::{message, STRUCTURE_NAME},
+    error::{PackstreamError, PackstreamResult},
+    packstream::{PackstreamStructure, Single},
+    Value,
+};
+use serde::{
+    de, forward_to_deserialize_any,
+    ser::{self, SerializeTupleStruct},
+};
+use crate::Map;
+use core::fmt;
+
+/// Discards records of the result since Bolt 4, replacing `DiscardAll`.
+///
+/// Shares the signature with `DiscardAll`, the messages differ in the number of fields.
+#[derive(Debug, PartialEq)]
+pub struct Discard {
+    pub extra: Map<String, Value>,
+}
+
+impl Discard {
+    /// Discards all remaining records of the last result.
+    pub fn all() -> Self {
+        Self::new(-1, None)
+    }
+
+    /// Discards `n` records, `-1` meaning all, of the result identified by `qid`,
+    /// or of the last result when `qid` is `None`.
+    pub fn new(n: i64, qid: Option<i64>) -> Self {
+        let mut extra = Map::new();
+        extra.insert(String::from("n"), Value::I64(n));
+        if let Some(qid) = qid {
+            extra.insert(String::from("qid"), Value::I64(qid));
+        }
+        Self { extra }
+    }
+}
+
+impl PackstreamStructure for Discard {
+    const SIG: u8 = message::DISCARD;
+    const LEN: u8 = 0x01;
+    const SERIALIZE_LEN: usize = serialize_length!(Self::SIG, Self::LEN);
+
+    type Fields = Single<Map<String, Value>>;
+
+    fn into_value(self) -> Value {
+        value_map! {
+            "extra" => Value::Map(self.extra),
+        }
+    }
+}
+
+impl fmt::Display for Discard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Discard").field(&self.extra).finish()
+    }
+}
+
+impl ser::Serialize for Discard {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let mut ts_serializer =
+            serializer.serialize_tuple_struct(STRUCTURE_NAME, Self::SERIALIZE_LEN)?;
+        ts_serializer.serialize_field(&self.extra)?;
+        ts_serializer.end()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Discard {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(DiscardVisitor)
+    }
+}
+
+struct DiscardVisitor;
+
+impl<'de> de::Visitor<'de> for DiscardVisitor {
+    type Value = Discard;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Discard")
+    }
+
+    fn visit_map<V>(self, mut map_access: V) -> Result<Self::Value, V::Error>
+    where
+        V: de::MapAccess<'de>,
+    {
+        let fields = structure_access!(map_access, Discard);
+        Ok(Discard {
+            extra: fields.value(),
+        })
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Discard {
+    type Error = PackstreamError;
+
+    fn deserialize_any<V>(self, visitor: V) -> PackstreamResult<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.into_value().deserialize_map(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier enum ignored_any
+    }
+}
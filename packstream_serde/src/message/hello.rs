@@ -0,0 +1,112 @@
+use crate::prelude::*;
+use crate::{
+    constants::{message, STRUCTURE_NAME},
+    error::{PackstreamError, PackstreamResult},
+    packstream::{PackstreamStructure, Single},
+    Value,
+};
+use super::AuthToken;
+use serde::{
+    de, forward_to_deserialize_any,
+    ser::{self, SerializeTupleStruct},
+};
+use crate::Map;
+use core::fmt;
+
+/// Initializes the connection since Bolt 3, replacing `Init`.
+///
+/// Shares the signature with `Init`, the messages differ in the number of fields.
+#[derive(Debug, PartialEq)]
+pub struct Hello {
+    pub extra: Map<String, Value>,
+}
+
+impl Hello {
+    /// Creates `Hello` with the auth token entries, which Bolt 5.1+ sends in `Logon` instead.
+    pub fn new(user_agent: impl Into<String>, auth: Option<AuthToken>) -> Self {
+        let mut extra = match auth.map(AuthToken::into_value) {
+            Some(Value::Map(map)) => map,
+            _ => Map::new(),
+        };
+        extra.insert(String::from("user_agent"), Value::String(user_agent.into()));
+        Self { extra }
+    }
+}
+
+impl PackstreamStructure for Hello {
+    const SIG: u8 = message::HELLO;
+    const LEN: u8 = 0x01;
+    const SERIALIZE_LEN: usize = serialize_length!(Self::SIG, Self::LEN);
+
+    type Fields = Single<Map<String, Value>>;
+
+    fn into_value(self) -> Value {
+        value_map! {
+            "extra" => Value::Map(self.extra),
+        }
+    }
+}
+
+impl fmt::Display for Hello {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Hello").field(&self.extra).finish()
+    }
+}
+
+impl ser::Serialize for Hello {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let mut ts_serializer =
+            serializer.serialize_tuple_struct(STRUCTURE_NAME, Self::SERIALIZE_LEN)?;
+        ts_serializer.serialize_field(&self.extra)?;
+        ts_serializer.end()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Hello {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(HelloVisitor)
+    }
+}
+
+struct HelloVisitor;
+
+impl<'de> de::Visitor<'de> for HelloVisitor {
+    type Value = Hello;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Hello")
+    }
+
+    fn visit_map<V>(self, mut map_access: V) -> Result<Self::Value, V::Error>
+    where
+        V: de::MapAccess<'de>,
+    {
+        let fields = structure_access!(map_access, Hello);
+        Ok(Hello {
+            extra: fields.value(),
+        })
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Hello {
+    type Error = PackstreamError;
+
+    fn deserialize_any<V>(self, visitor: V) -> PackstreamResult<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.into_value().deserialize_map(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier enum ignored_any
+    }
+}
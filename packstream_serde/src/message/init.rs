@@ -1,20 +1,22 @@
+use crate::prelude::*;
 use crate::{
     constants::{message, STRUCTURE_NAME},
     error::{PackstreamError, PackstreamResult},
     packstream::PackstreamStructure,
     Value,
 };
+use super::AuthToken;
 use serde::{
     de, forward_to_deserialize_any,
     ser::{self, SerializeTupleStruct},
 };
 use serde_derive::{Deserialize, Serialize};
-use std::{collections::HashMap, fmt};
+use core::fmt;
 
 #[derive(Debug, PartialEq)]
 pub struct Init {
     pub client: String,
-    pub auth: BasicAuth,
+    pub auth: AuthToken,
 }
 
 impl PackstreamStructure for Init {
@@ -22,7 +24,7 @@ impl PackstreamStructure for Init {
     const LEN: u8 = 0x02;
     const SERIALIZE_LEN: usize = serialize_length!(Self::SIG, Self::LEN);
 
-    type Fields = (String, BasicAuth);
+    type Fields = (String, AuthToken);
 
     fn into_value(self) -> Value {
         value_map! {
@@ -48,16 +50,6 @@ pub struct BasicAuth {
     pub credentials: String,
 }
 
-impl BasicAuth {
-    fn into_value(self) -> Value {
-        let mut map = HashMap::new();
-        map.insert(String::from("scheme"), Value::String(self.scheme));
-        map.insert(String::from("principal"), Value::String(self.principal));
-        map.insert(String::from("credentials"), Value::String(self.credentials));
-        Value::Map(map)
-    }
-}
-
 impl fmt::Debug for BasicAuth {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("BasicAuth")
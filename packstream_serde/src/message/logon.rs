@@ -0,0 +1,98 @@
+use crate::prelude::*;
+use crate::{
+    constants::{message, STRUCTURE_NAME},
+    error::{PackstreamError, PackstreamResult},
+    packstream::{PackstreamStructure, Single},
+    Value,
+};
+use super::AuthToken;
+use serde::{
+    de, forward_to_deserialize_any,
+    ser::{self, SerializeTupleStruct},
+};
+use core::fmt;
+
+/// Authenticates the connection with the auth token map
+/// that was sent within `Init` before Bolt 5.1.
+#[derive(Debug, PartialEq)]
+pub struct Logon {
+    pub auth: AuthToken,
+}
+
+impl PackstreamStructure for Logon {
+    const SIG: u8 = message::LOGON;
+    const LEN: u8 = 0x01;
+    const SERIALIZE_LEN: usize = serialize_length!(Self::SIG, Self::LEN);
+
+    type Fields = Single<AuthToken>;
+
+    fn into_value(self) -> Value {
+        value_map! {
+            "auth" => self.auth.into_value(),
+        }
+    }
+}
+
+impl fmt::Display for Logon {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Logon").field(&self.auth).finish()
+    }
+}
+
+impl ser::Serialize for Logon {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let mut ts_serializer =
+            serializer.serialize_tuple_struct(STRUCTURE_NAME, Self::SERIALIZE_LEN)?;
+        ts_serializer.serialize_field(&self.auth)?;
+        ts_serializer.end()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Logon {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(LogonVisitor)
+    }
+}
+
+struct LogonVisitor;
+
+impl<'de> de::Visitor<'de> for LogonVisitor {
+    type Value = Logon;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Logon")
+    }
+
+    fn visit_map<V>(self, mut map_access: V) -> Result<Self::Value, V::Error>
+    where
+        V: de::MapAccess<'de>,
+    {
+        let fields = structure_access!(map_access, Logon);
+        Ok(Logon {
+            auth: fields.value(),
+        })
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Logon {
+    type Error = PackstreamError;
+
+    fn deserialize_any<V>(self, visitor: V) -> PackstreamResult<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.into_value().deserialize_map(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier enum ignored_any
+    }
+}
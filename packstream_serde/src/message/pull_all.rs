@@ -1,3 +1,4 @@
+use crate::prelude::*;
 use crate::{
     constants::{marker, message, STRUCTURE_NAME},
     error::{PackstreamError, PackstreamResult},
@@ -8,7 +9,7 @@ use serde::{
     de, forward_to_deserialize_any,
     ser::{self, SerializeTupleStruct},
 };
-use std::fmt;
+use core::fmt;
 
 #[derive(Debug, PartialEq)]
 pub struct PullAll;
@@ -1,5 +1,6 @@
+use crate::prelude::*;
 use crate::{
-    constants::{message, STRUCTURE_NAME},
+    constants::{message, STRUCTURE_FIELDS_KEY, STRUCTURE_NAME, STRUCTURE_SIG_KEY},
     error::{PackstreamError, PackstreamResult},
     packstream::{PackstreamStructure, Single},
     Value,
@@ -8,11 +9,15 @@ use serde::{
     de, forward_to_deserialize_any,
     ser::{self, SerializeTupleStruct},
 };
-use std::fmt;
+use core::{fmt, marker::PhantomData};
 
+/// Represents the RECORD message.
+///
+/// Fields are decoded into [`Value`]s by default. Other field types such as
+/// [`RawValue`](crate::RawValue) can be used to decode them lazily.
 #[derive(Debug, PartialEq)]
-pub struct Record {
-    pub fields: Vec<Value>,
+pub struct Record<F = Value> {
+    pub fields: Vec<F>,
 }
 
 impl PackstreamStructure for Record {
@@ -29,37 +34,37 @@ impl PackstreamStructure for Record {
     }
 }
 
-impl fmt::Display for Record {
+impl<F: fmt::Debug> fmt::Display for Record<F> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_tuple("Record").field(&self.fields).finish()
     }
 }
 
-impl ser::Serialize for Record {
+impl<F: ser::Serialize> ser::Serialize for Record<F> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: ser::Serializer,
     {
         let mut ts_serializer =
-            serializer.serialize_tuple_struct(STRUCTURE_NAME, Self::SERIALIZE_LEN)?;
+            serializer.serialize_tuple_struct(STRUCTURE_NAME, <Record>::SERIALIZE_LEN)?;
         ts_serializer.serialize_field(&self.fields)?;
         ts_serializer.end()
     }
 }
 
-impl<'de> de::Deserialize<'de> for Record {
+impl<'de, F: de::Deserialize<'de> + 'de> de::Deserialize<'de> for Record<F> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: de::Deserializer<'de>,
     {
-        deserializer.deserialize_map(RecordVisitor)
+        deserializer.deserialize_map(RecordVisitor(PhantomData))
     }
 }
 
-struct RecordVisitor;
+struct RecordVisitor<F>(PhantomData<F>);
 
-impl<'de> de::Visitor<'de> for RecordVisitor {
-    type Value = Record;
+impl<'de, F: de::Deserialize<'de> + 'de> de::Visitor<'de> for RecordVisitor<F> {
+    type Value = Record<F>;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         formatter.write_str("Record")
@@ -69,7 +74,12 @@ impl<'de> de::Visitor<'de> for RecordVisitor {
     where
         V: de::MapAccess<'de>,
     {
-        let fields = structure_access!(map_access, Record);
+        check!(__key, map_access, STRUCTURE_SIG_KEY);
+        check!(__sig, map_access, message::RECORD);
+        check!(__key, map_access, STRUCTURE_FIELDS_KEY);
+        let fields = map_access.next_value::<Single<Vec<F>>>()?;
+        check!(__key, map_access);
+
         Ok(Record {
             fields: fields.value(),
         })
@@ -0,0 +1,112 @@
+use crate::prelude::*;
+use crate::{
+    constants::{message, STRUCTURE_NAME},
+    error::{PackstreamError, PackstreamResult},
+    packstream::PackstreamStructure,
+    Value,
+};
+use serde::{
+    de, forward_to_deserialize_any,
+    ser::{self, SerializeTupleStruct},
+};
+use crate::Map;
+use core::fmt;
+
+/// Requests the routing table of a database since Bolt 4.3.
+///
+/// `routing` is the routing context of the driver URI. `db` is the database name
+/// or null in Bolt 4.3, since Bolt 4.4 it is the extra map with `db` and `imp_user`.
+#[derive(Debug, PartialEq)]
+pub struct Route {
+    pub routing: Map<String, Value>,
+    pub bookmarks: Vec<String>,
+    pub db: Value,
+}
+
+impl PackstreamStructure for Route {
+    const SIG: u8 = message::ROUTE;
+    const LEN: u8 = 0x03;
+    const SERIALIZE_LEN: usize = serialize_length!(Self::SIG, Self::LEN);
+
+    type Fields = (Map<String, Value>, Vec<String>, Value);
+
+    fn into_value(self) -> Value {
+        value_map! {
+            "routing" => Value::Map(self.routing),
+            "bookmarks" => Value::List(self.bookmarks.into_iter().map(Value::String).collect()),
+            "db" => self.db,
+        }
+    }
+}
+
+impl fmt::Display for Route {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Route")
+            .field("routing", &self.routing)
+            .field("bookmarks", &self.bookmarks)
+            .field("db", &self.db)
+            .finish()
+    }
+}
+
+impl ser::Serialize for Route {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let mut ts_serializer =
+            serializer.serialize_tuple_struct(STRUCTURE_NAME, Route::SERIALIZE_LEN)?;
+        ts_serializer.serialize_field(&self.routing)?;
+        ts_serializer.serialize_field(&self.bookmarks)?;
+        ts_serializer.serialize_field(&self.db)?;
+        ts_serializer.end()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Route {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(RouteVisitor)
+    }
+}
+
+struct RouteVisitor;
+
+impl<'de> de::Visitor<'de> for RouteVisitor {
+    type Value = Route;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Route")
+    }
+
+    fn visit_map<V>(self, mut map_access: V) -> Result<Self::Value, V::Error>
+    where
+        V: de::MapAccess<'de>,
+    {
+        let (routing, bookmarks, db) = structure_access!(map_access, Route);
+        Ok(Route {
+            routing,
+            bookmarks,
+            db,
+        })
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Route {
+    type Error = PackstreamError;
+
+    fn deserialize_any<V>(self, visitor: V) -> PackstreamResult<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.into_value().deserialize_map(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier enum ignored_any
+    }
+}
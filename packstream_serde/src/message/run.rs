@@ -1,3 +1,4 @@
+use crate::prelude::*;
 use crate::{
     constants::{message, STRUCTURE_NAME},
     error::{PackstreamError, PackstreamResult},
@@ -8,12 +9,13 @@ use serde::{
     de, forward_to_deserialize_any,
     ser::{self, SerializeTupleStruct},
 };
-use std::{collections::HashMap, fmt};
+use crate::Map;
+use core::fmt;
 
 #[derive(Debug, PartialEq)]
 pub struct Run {
     pub statement: String,
-    pub parameters: HashMap<String, Value>,
+    pub parameters: Map<String, Value>,
 }
 
 impl PackstreamStructure for Run {
@@ -21,7 +23,7 @@ impl PackstreamStructure for Run {
     const LEN: u8 = 0x02;
     const SERIALIZE_LEN: usize = serialize_length!(Self::SIG, Self::LEN);
 
-    type Fields = (String, HashMap<String, Value>);
+    type Fields = (String, Map<String, Value>);
 
     fn into_value(self) -> Value {
         value_map! {
@@ -0,0 +1,112 @@
+use crate::prelude::*;
+use crate::{
+    constants::{message, STRUCTURE_NAME},
+    error::{PackstreamError, PackstreamResult},
+    packstream::PackstreamStructure,
+    Value,
+};
+use serde::{
+    de, forward_to_deserialize_any,
+    ser::{self, SerializeTupleStruct},
+};
+use crate::Map;
+use core::fmt;
+
+/// RunWithMetadatas the statement since Bolt 3, `extra` holds transaction metadata,
+/// bookmarks, mode and database.
+///
+/// Shares the signature with `RunWithMetadata`, the messages differ in the number of fields.
+#[derive(Debug, PartialEq)]
+pub struct RunWithMetadata {
+    pub statement: String,
+    pub parameters: Map<String, Value>,
+    pub extra: Map<String, Value>,
+}
+
+impl PackstreamStructure for RunWithMetadata {
+    const SIG: u8 = message::RUN;
+    const LEN: u8 = 0x03;
+    const SERIALIZE_LEN: usize = serialize_length!(Self::SIG, Self::LEN);
+
+    type Fields = (String, Map<String, Value>, Map<String, Value>);
+
+    fn into_value(self) -> Value {
+        value_map! {
+            "statement" => Value::String(self.statement),
+            "parameters" => Value::Map(self.parameters),
+            "extra" => Value::Map(self.extra),
+        }
+    }
+}
+
+impl fmt::Display for RunWithMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RunWithMetadata")
+            .field("statement", &self.statement)
+            .field("parameters", &self.parameters)
+            .field("extra", &self.extra)
+            .finish()
+    }
+}
+
+impl ser::Serialize for RunWithMetadata {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let mut ts_serializer =
+            serializer.serialize_tuple_struct(STRUCTURE_NAME, RunWithMetadata::SERIALIZE_LEN)?;
+        ts_serializer.serialize_field(&self.statement)?;
+        ts_serializer.serialize_field(&self.parameters)?;
+        ts_serializer.serialize_field(&self.extra)?;
+        ts_serializer.end()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for RunWithMetadata {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(RunWithMetadataVisitor)
+    }
+}
+
+struct RunWithMetadataVisitor;
+
+impl<'de> de::Visitor<'de> for RunWithMetadataVisitor {
+    type Value = RunWithMetadata;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("RunWithMetadata")
+    }
+
+    fn visit_map<V>(self, mut map_access: V) -> Result<Self::Value, V::Error>
+    where
+        V: de::MapAccess<'de>,
+    {
+        let (statement, parameters, extra) = structure_access!(map_access, RunWithMetadata);
+        Ok(RunWithMetadata {
+            statement,
+            parameters,
+            extra,
+        })
+    }
+}
+
+impl<'de> de::Deserializer<'de> for RunWithMetadata {
+    type Error = PackstreamError;
+
+    fn deserialize_any<V>(self, visitor: V) -> PackstreamResult<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.into_value().deserialize_map(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier enum ignored_any
+    }
+}
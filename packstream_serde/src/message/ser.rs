@@ -9,10 +9,22 @@ impl ser::Serialize for RequestMessage {
         match self {
             Self::AckFailure(v) => v.serialize(serializer),
             Self::DiscardAll(v) => v.serialize(serializer),
+            Self::Discard(v) => v.serialize(serializer),
             Self::Init(v) => v.serialize(serializer),
+            Self::Hello(v) => v.serialize(serializer),
+            Self::Goodbye(v) => v.serialize(serializer),
             Self::PullAll(v) => v.serialize(serializer),
+            Self::Pull(v) => v.serialize(serializer),
             Self::Reset(v) => v.serialize(serializer),
             Self::Run(v) => v.serialize(serializer),
+            Self::RunWithMetadata(v) => v.serialize(serializer),
+            Self::Begin(v) => v.serialize(serializer),
+            Self::Commit(v) => v.serialize(serializer),
+            Self::Rollback(v) => v.serialize(serializer),
+            Self::Logon(v) => v.serialize(serializer),
+            Self::Logoff(v) => v.serialize(serializer),
+            Self::Telemetry(v) => v.serialize(serializer),
+            Self::Route(v) => v.serialize(serializer),
         }
     }
 }
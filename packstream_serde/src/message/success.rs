@@ -1,3 +1,4 @@
+use crate::prelude::*;
 use crate::{
     constants::{message, STRUCTURE_NAME},
     error::{PackstreamError, PackstreamResult},
@@ -8,11 +9,12 @@ use serde::{
     de, forward_to_deserialize_any,
     ser::{self, SerializeTupleStruct},
 };
-use std::{collections::HashMap, fmt};
+use crate::Map;
+use core::fmt;
 
 #[derive(Debug, PartialEq)]
 pub struct Success {
-    pub metadata: HashMap<String, Value>,
+    pub metadata: Map<String, Value>,
 }
 
 impl PackstreamStructure for Success {
@@ -20,7 +22,7 @@ impl PackstreamStructure for Success {
     const LEN: u8 = 0x01;
     const SERIALIZE_LEN: usize = serialize_length!(Self::SIG, Self::LEN);
 
-    type Fields = Single<HashMap<String, Value>>;
+    type Fields = Single<Map<String, Value>>;
 
     fn into_value(self) -> Value {
         value_map! {
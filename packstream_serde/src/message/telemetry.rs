@@ -0,0 +1,103 @@
+use crate::prelude::*;
+use crate::{
+    constants::{message, STRUCTURE_NAME},
+    error::{PackstreamError, PackstreamResult},
+    packstream::{PackstreamStructure, Single},
+    Value,
+};
+use serde::{
+    de, forward_to_deserialize_any,
+    ser::{self, SerializeTupleStruct},
+};
+use core::fmt;
+
+/// Tells the server which driver API was used to run the following work.
+#[derive(Debug, PartialEq)]
+pub struct Telemetry {
+    pub api: i64,
+}
+
+impl Telemetry {
+    pub const MANAGED_TRANSACTION: i64 = 0;
+    pub const EXPLICIT_TRANSACTION: i64 = 1;
+    pub const IMPLICIT_TRANSACTION: i64 = 2;
+    pub const EXECUTE_QUERY: i64 = 3;
+}
+
+impl PackstreamStructure for Telemetry {
+    const SIG: u8 = message::TELEMETRY;
+    const LEN: u8 = 0x01;
+    const SERIALIZE_LEN: usize = serialize_length!(Self::SIG, Self::LEN);
+
+    type Fields = Single<i64>;
+
+    fn into_value(self) -> Value {
+        value_map! {
+            "api" => Value::I64(self.api),
+        }
+    }
+}
+
+impl fmt::Display for Telemetry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Telemetry").field(&self.api).finish()
+    }
+}
+
+impl ser::Serialize for Telemetry {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let mut ts_serializer =
+            serializer.serialize_tuple_struct(STRUCTURE_NAME, Self::SERIALIZE_LEN)?;
+        ts_serializer.serialize_field(&self.api)?;
+        ts_serializer.end()
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Telemetry {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(TelemetryVisitor)
+    }
+}
+
+struct TelemetryVisitor;
+
+impl<'de> de::Visitor<'de> for TelemetryVisitor {
+    type Value = Telemetry;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Telemetry")
+    }
+
+    fn visit_map<V>(self, mut map_access: V) -> Result<Self::Value, V::Error>
+    where
+        V: de::MapAccess<'de>,
+    {
+        let fields = structure_access!(map_access, Telemetry);
+        Ok(Telemetry {
+            api: fields.value(),
+        })
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Telemetry {
+    type Error = PackstreamError;
+
+    fn deserialize_any<V>(self, visitor: V) -> PackstreamResult<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.into_value().deserialize_map(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier enum ignored_any
+    }
+}
@@ -0,0 +1,140 @@
+//! Options of the packstream [serializer](crate::to_bytes_with_options), [value serializer](crate::to_value_with_options)
+//! and [deserializer](crate::from_bytes_with_options).
+
+use crate::prelude::*;
+use crate::error::{PackstreamError, PackstreamResult};
+use core::fmt;
+
+/// Representation of Rust enums in packstream.
+///
+/// ```
+/// # use packstream_serde::{from_bytes_with_options, to_bytes_with_options, to_text, from_bytes};
+/// # use packstream_serde::options::{EnumRepr, Options};
+/// # use serde_derive::{Deserialize, Serialize};
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// enum Shape {
+///     Empty,
+///     Circle { r: i64 },
+/// }
+///
+/// let options = Options::new().enum_repr(EnumRepr::Adjacent { tag: "type", content: "value" });
+/// let bytes = to_bytes_with_options(&Shape::Circle { r: 1 }, options).unwrap();
+/// assert_eq!(to_text(&from_bytes(&bytes).unwrap()), r#"{type: "Circle", value: {r: 1}}"#);
+///
+/// let options = Options::new().enum_repr(EnumRepr::UnitAsString);
+/// let bytes = to_bytes_with_options(&Shape::Empty, options).unwrap();
+/// assert_eq!(to_text(&from_bytes(&bytes).unwrap()), r#""Empty""#);
+/// assert_eq!(from_bytes_with_options::<Shape>(&bytes, options).unwrap(), Shape::Empty);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EnumRepr {
+    /// `{Variant: content}`, unit variants are written as `{Variant: null}`.
+    #[default]
+    External,
+    /// `{tag: "Variant", ..fields}`.
+    ///
+    /// Newtype variants must hold a map or a struct, tuple variants are not supported.
+    Internal { tag: &'static str },
+    /// `{tag: "Variant", content: content}`, unit variants are written as `{tag: "Variant"}`.
+    ///
+    /// The canonical mode writes the two keys sorted, whichever sorts first.
+    Adjacent {
+        tag: &'static str,
+        content: &'static str,
+    },
+    /// Unit variants are written as plain strings, so that they can be stored as Neo4j
+    /// string properties. Other variants are externally tagged.
+    UnitAsString,
+}
+
+/// Handling of integers which do not fit into their target type.
+///
+/// Packstream integers are `i64`, so the policy applies to serialized `u64`, `u128` and `i128`
+/// values and to the deserialized integers narrowed into smaller types.
+///
+/// ```
+/// # use packstream_serde::{from_bytes_with_options, to_bytes_with_options};
+/// # use packstream_serde::options::{IntegerPolicy, Options};
+/// let options = Options::new().integer_policy(IntegerPolicy::String);
+/// let bytes = to_bytes_with_options(&u64::MAX, options).unwrap();
+/// assert_eq!(from_bytes_with_options::<u64>(&bytes, options).unwrap(), u64::MAX);
+///
+/// let options = Options::new().integer_policy(IntegerPolicy::Saturate);
+/// let bytes = to_bytes_with_options(&300, options).unwrap();
+/// assert_eq!(from_bytes_with_options::<u8>(&bytes, options).unwrap(), u8::MAX);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IntegerPolicy {
+    /// Fail with an error naming the value and the target range.
+    #[default]
+    Error,
+    /// Clamp the value to the nearest bound of the target range.
+    Saturate,
+    /// Serialize values outside of the `i64` range as decimal strings.
+    /// The deserializer accepts decimal strings for integers, narrowing fails as with `Error`.
+    String,
+}
+
+impl IntegerPolicy {
+    /// Converts the serialized integer into `i64`, returns `None` when it is written as a decimal string.
+    pub(crate) fn fit<T>(self, value: T) -> PackstreamResult<Option<i64>>
+    where
+        T: TryInto<i64> + PartialOrd + Default + Copy + fmt::Display,
+    {
+        match value.try_into() {
+            Ok(int) => Ok(Some(int)),
+            Err(_) => match self {
+                IntegerPolicy::Error => Err(PackstreamError::create(format!(
+                    "Integer {} is out of the packstream range {}..={}",
+                    value,
+                    i64::MIN,
+                    i64::MAX
+                ))),
+                IntegerPolicy::Saturate if value < T::default() => Ok(Some(i64::MIN)),
+                IntegerPolicy::Saturate => Ok(Some(i64::MAX)),
+                IntegerPolicy::String => Ok(None),
+            },
+        }
+    }
+}
+
+/// Options shared by the serializer and the deserializer.
+///
+/// Bytes should be read with the same options they were written with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Options {
+    pub(crate) enum_repr: EnumRepr,
+    pub(crate) canonical: bool,
+    pub(crate) integer_policy: IntegerPolicy,
+    pub(crate) strict_f32: bool,
+}
+
+impl Options {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn enum_repr(mut self, enum_repr: EnumRepr) -> Self {
+        self.enum_repr = enum_repr;
+        self
+    }
+
+    pub fn integer_policy(mut self, integer_policy: IntegerPolicy) -> Self {
+        self.integer_policy = integer_policy;
+        self
+    }
+
+    /// Makes deserialization of `f32` fail when the packstream float cannot be represented
+    /// exactly, instead of rounding it.
+    pub fn strict_f32(mut self, strict_f32: bool) -> Self {
+        self.strict_f32 = strict_f32;
+        self
+    }
+
+    /// Enables the canonical encoding of [`to_bytes_canonical`](crate::to_bytes_canonical).
+    /// Ignored by the deserializer.
+    pub fn canonical(mut self, canonical: bool) -> Self {
+        self.canonical = canonical;
+        self
+    }
+}
@@ -1,8 +1,9 @@
+use crate::prelude::*;
 use serde::de::{self, Deserialize, Deserializer, Error};
 use serde::ser::{self, Serialize, SerializeTuple};
 use crate::Value;
-use std::fmt;
-use std::marker::PhantomData;
+use core::fmt;
+use core::marker::PhantomData;
 
 pub trait PackstreamStructure {
     const SIG: u8;
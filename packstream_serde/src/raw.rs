@@ -0,0 +1,204 @@
+use crate::prelude::*;
+use super::constants::RAW_VALUE_NAME;
+use super::error::{ErrorCode, PackstreamError, PackstreamResult};
+use super::read::{ByteReader, Unpacker};
+use super::Value;
+use serde::{de, ser};
+use core::fmt;
+
+/// Holds a single encoded packstream value without decoding it.
+///
+/// When deserialized with [`from_bytes`](crate::from_bytes) the value is skipped and its
+/// bytes are borrowed from the input. Use [`RawValueBuf`] to own the bytes or to deserialize
+/// from other sources, such as [`from_value`](crate::from_value).
+/// Serializing a `RawValue` writes its bytes verbatim, also in the canonical mode.
+///
+/// ```
+/// # use packstream_serde::{from_bytes, to_bytes, RawValue};
+/// // [1, "a"]
+/// const BYTES: &[u8] = &[0x92, 0x01, 0x81, 0x61];
+///
+/// let (id, rest): (i64, RawValue) = from_bytes(BYTES).unwrap();
+/// assert_eq!(id, 1);
+/// assert_eq!(rest.as_bytes(), &[0x81, 0x61]);
+/// assert_eq!(rest.deserialize::<String>().unwrap(), "a");
+/// assert_eq!(to_bytes(&(id, rest)).unwrap(), BYTES);
+/// ```
+#[derive(Clone, Copy, PartialEq)]
+pub struct RawValue<'a> {
+    bytes: &'a [u8],
+}
+
+/// Owned version of the [`RawValue`].
+#[derive(Clone, PartialEq)]
+pub struct RawValueBuf {
+    bytes: Vec<u8>,
+}
+
+impl<'a> RawValue<'a> {
+    /// Creates `RawValue` borrowing bytes which must hold exactly one encoded value.
+    pub fn from_bytes(bytes: &'a [u8]) -> PackstreamResult<Self> {
+        let mut reader = ByteReader::new(bytes);
+        reader.skip_value()?;
+
+        if !reader.is_done() {
+            return Err(PackstreamError::create(ErrorCode::UnexpectedTrailingBytes));
+        }
+
+        Ok(Self { bytes })
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    pub fn to_raw_value_buf(&self) -> RawValueBuf {
+        RawValueBuf {
+            bytes: self.bytes.to_vec(),
+        }
+    }
+
+    /// Decodes the held value.
+    pub fn deserialize<T>(&self) -> PackstreamResult<T>
+    where
+        T: de::Deserialize<'a>,
+    {
+        crate::from_bytes(self.bytes)
+    }
+}
+
+impl RawValueBuf {
+    /// Creates `RawValueBuf` from bytes which must hold exactly one encoded value.
+    pub fn from_vec(bytes: Vec<u8>) -> PackstreamResult<Self> {
+        RawValue::from_bytes(&bytes)?;
+        Ok(Self { bytes })
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn into_vec(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    pub fn as_raw_value(&self) -> RawValue<'_> {
+        RawValue { bytes: &self.bytes }
+    }
+
+    /// Decodes the held value.
+    pub fn deserialize<'de, T>(&'de self) -> PackstreamResult<T>
+    where
+        T: de::Deserialize<'de>,
+    {
+        crate::from_bytes(&self.bytes)
+    }
+}
+
+impl<'a> From<RawValue<'a>> for RawValueBuf {
+    fn from(raw: RawValue<'a>) -> Self {
+        raw.to_raw_value_buf()
+    }
+}
+
+impl fmt::Debug for RawValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("RawValue").field(&self.bytes).finish()
+    }
+}
+
+impl fmt::Display for RawValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.deserialize::<Value>() {
+            Ok(value) => fmt::Display::fmt(&value, f),
+            Err(_) => f.debug_list().entries(self.bytes.iter()).finish(),
+        }
+    }
+}
+
+impl fmt::Debug for RawValueBuf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("RawValueBuf").field(&self.bytes).finish()
+    }
+}
+
+impl fmt::Display for RawValueBuf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.as_raw_value(), f)
+    }
+}
+
+impl ser::Serialize for RawValue<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_newtype_struct(RAW_VALUE_NAME, serde_bytes::Bytes::new(self.bytes))
+    }
+}
+
+impl ser::Serialize for RawValueBuf {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        self.as_raw_value().serialize(serializer)
+    }
+}
+
+impl<'de: 'a, 'a> de::Deserialize<'de> for RawValue<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct RawValueVisitor;
+
+        impl<'de> de::Visitor<'de> for RawValueVisitor {
+            type Value = RawValue<'de>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("borrowed packstream value")
+            }
+
+            fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+                Ok(RawValue { bytes: v })
+            }
+        }
+
+        deserializer.deserialize_newtype_struct(RAW_VALUE_NAME, RawValueVisitor)
+    }
+}
+
+impl<'de> de::Deserialize<'de> for RawValueBuf {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct RawValueBufVisitor;
+
+        impl<'de> de::Visitor<'de> for RawValueBufVisitor {
+            type Value = RawValueBuf;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("any packstream value")
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                Ok(RawValueBuf { bytes: v.to_vec() })
+            }
+
+            fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                use de::Error;
+
+                let value = <Value as de::Deserialize>::deserialize(deserializer)?;
+                let bytes = crate::to_bytes(&value).map_err(D::Error::custom)?;
+                Ok(RawValueBuf { bytes })
+            }
+        }
+
+        deserializer.deserialize_newtype_struct(RAW_VALUE_NAME, RawValueBufVisitor)
+    }
+}
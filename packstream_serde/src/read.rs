@@ -1,3 +1,4 @@
+use crate::prelude::*;
 use super::constants::marker::*;
 use super::error::{ErrorCode, PackstreamError, PackstreamResult};
 use super::marker::Marker;
@@ -38,6 +39,9 @@ pub trait Unpacker<'a> {
     fn peek_marker(&mut self) -> PackstreamResult<Marker>;
 
     fn scratch_peeked(&mut self);
+
+    /// Consumes next value without decoding it and returns its encoded bytes
+    fn skip_value(&mut self) -> PackstreamResult<&'a [u8]>;
 }
 
 impl<'a> Unpacker<'a> for ByteReader<'a> {
@@ -146,7 +150,7 @@ impl<'a> Unpacker<'a> for ByteReader<'a> {
                 Marker::Map(len)
             }
             MAP_16 => {
-                let b8 = self.peek_byte_nth_ahead(self.index + 2)?;
+                let b8 = self.peek_byte_nth_ahead(2)?;
                 let b7 = self.bytes[self.index + 1];
 
                 self.peeked = 3;
@@ -163,7 +167,7 @@ impl<'a> Unpacker<'a> for ByteReader<'a> {
             }
             MAP_STREAM => {
                 self.peeked = 1;
-                Marker::Map(std::usize::MAX)
+                Marker::Map(usize::MAX)
             }
             TINY_STRUCT..=TINY_STRUCT_MAX => {
                 self.peeked = 1;
@@ -208,7 +212,7 @@ impl<'a> Unpacker<'a> for ByteReader<'a> {
             }
             LIST_STREAM => {
                 self.peeked = 1;
-                Marker::List(std::usize::MAX)
+                Marker::List(usize::MAX)
             }
             NULL => {
                 self.peeked = 1;
@@ -320,6 +324,38 @@ impl<'a> Unpacker<'a> for ByteReader<'a> {
             self.peeked = 0;
         }
     }
+
+    fn skip_value(&mut self) -> PackstreamResult<&'a [u8]> {
+        if self.virtual_marker.is_some() || self.virtual_value.is_some() {
+            return Err(PackstreamError::create("Cannot skip value while virtual value is set"));
+        }
+
+        let start = self.index;
+        self.skip_next()?;
+        Ok(&self.bytes[start..self.index])
+    }
+}
+
+impl<'a> ByteReader<'a> {
+    fn skip_next(&mut self) -> PackstreamResult<()> {
+        let (len, values) = match self.consume_marker()? {
+            Marker::List(usize::MAX) | Marker::Map(usize::MAX) => {
+                return Err(PackstreamError::create("Cannot skip List or Map stream"));
+            }
+            Marker::String(len) | Marker::Bytes(len) => (len, 0),
+            Marker::List(len) => (0, len),
+            Marker::Map(len) => (0, len * 2),
+            Marker::Struct(len) => (1, len),
+            _ => (0, 0),
+        };
+
+        self.consume_bytes(len)?;
+        for _ in 0..values {
+            self.skip_next()?;
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -360,7 +396,7 @@ mod tests {
             [MAP_8, 20] => Marker::Map(20),
             [MAP_16, 1, 0] => Marker::Map(256),
             [MAP_32, 0, 1, 0, 0] => Marker::Map(256 * 256),
-            [MAP_STREAM] => Marker::Map(std::usize::MAX),
+            [MAP_STREAM] => Marker::Map(usize::MAX),
             [TINY_STRING] => Marker::String(0),
             [TINY_STRING + 10] => Marker::String(10),
             [STRING_8, 20] => Marker::String(20),
@@ -373,7 +409,7 @@ mod tests {
             [LIST_8, 100] => Marker::List(100),
             [LIST_16, 1, 0] => Marker::List(256),
             [LIST_32, 0, 1, 0, 0] => Marker::List(256 * 256),
-            [LIST_STREAM] => Marker::List(std::usize::MAX),
+            [LIST_STREAM] => Marker::List(usize::MAX),
             [BYTES_8, 1] => Marker::Bytes(1),
             [BYTES_16, 1, 0] => Marker::Bytes(256),
             [BYTES_32, 0, 1, 0, 0] => Marker::Bytes(256 * 256),
@@ -0,0 +1,301 @@
+//! Validation of [`Value`] trees against declared shapes.
+//!
+//! ```
+//! # use packstream_serde::from_text;
+//! # use packstream_serde::schema::Schema;
+//! let schema = Schema::map()
+//!     .required("name", Schema::string().max_len(5))
+//!     .optional("tags", Schema::list(Schema::string()));
+//!
+//! assert!(schema.validate(&from_text(r#"{name: "Alice", tags: ["a"]}"#).unwrap()).is_ok());
+//!
+//! let violations = schema.validate(&from_text(r#"{name: "Robert", tags: [1]}"#).unwrap()).unwrap_err();
+//! let mut messages: Vec<String> = violations.iter().map(ToString::to_string).collect();
+//! messages.sort();
+//! assert_eq!(messages, [
+//!     "$.name: expected at most 5 characters, got 6 instead",
+//!     "$.tags[0]: expected string, got integer instead",
+//! ]);
+//! ```
+
+use crate::prelude::*;
+use crate::value::structure::Structure;
+use crate::value::Value;
+use crate::Map;
+use core::fmt;
+
+/// Declared shape of a [`Value`].
+///
+/// Schemas are built with the constructor of the expected kind and refined with
+/// the builder methods. Methods which do not apply to the kind are ignored.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Schema {
+    kind: Kind,
+    nullable: bool,
+    min_len: Option<usize>,
+    max_len: Option<usize>,
+    fields: Vec<Field>,
+    deny_unknown_keys: bool,
+    labels: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Kind {
+    Any,
+    Bool,
+    Integer,
+    Float,
+    String,
+    Bytes,
+    List(Box<Schema>),
+    Map,
+    Node,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Field {
+    key: String,
+    schema: Schema,
+    required: bool,
+}
+
+/// Single failed constraint found by [`Schema::validate`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    /// Location of the value, e.g. `$.people[2].name`.
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+impl Schema {
+    fn new(kind: Kind) -> Self {
+        Self {
+            kind,
+            nullable: false,
+            min_len: None,
+            max_len: None,
+            fields: Vec::new(),
+            deny_unknown_keys: false,
+            labels: None,
+        }
+    }
+
+    /// Accepts any value, including `Null`.
+    pub fn any() -> Self {
+        Self::new(Kind::Any).nullable()
+    }
+
+    pub fn boolean() -> Self {
+        Self::new(Kind::Bool)
+    }
+
+    pub fn integer() -> Self {
+        Self::new(Kind::Integer)
+    }
+
+    pub fn float() -> Self {
+        Self::new(Kind::Float)
+    }
+
+    pub fn string() -> Self {
+        Self::new(Kind::String)
+    }
+
+    pub fn bytes() -> Self {
+        Self::new(Kind::Bytes)
+    }
+
+    /// List which elements match the `items` schema.
+    pub fn list(items: Schema) -> Self {
+        Self::new(Kind::List(Box::new(items)))
+    }
+
+    /// Map with keys declared by [`Schema::required`] and [`Schema::optional`].
+    pub fn map() -> Self {
+        Self::new(Kind::Map)
+    }
+
+    /// `Node` structure, its properties are declared as the keys of a map.
+    pub fn node() -> Self {
+        Self::new(Kind::Node)
+    }
+
+    /// Accepts `Null` in addition to the values of the kind.
+    pub fn nullable(mut self) -> Self {
+        self.nullable = true;
+        self
+    }
+
+    /// Minimum length of strings in characters, of bytes and of lists.
+    pub fn min_len(mut self, len: usize) -> Self {
+        self.min_len = Some(len);
+        self
+    }
+
+    /// Maximum length of strings in characters, of bytes and of lists.
+    pub fn max_len(mut self, len: usize) -> Self {
+        self.max_len = Some(len);
+        self
+    }
+
+    /// Declares key of a map or property of a node which must be present.
+    pub fn required(mut self, key: impl Into<String>, schema: Schema) -> Self {
+        self.fields.push(Field { key: key.into(), schema, required: true });
+        self
+    }
+
+    /// Declares key of a map or property of a node which may be missing.
+    pub fn optional(mut self, key: impl Into<String>, schema: Schema) -> Self {
+        self.fields.push(Field { key: key.into(), schema, required: false });
+        self
+    }
+
+    /// Rejects keys of a map or properties of a node which were not declared.
+    pub fn deny_unknown_keys(mut self) -> Self {
+        self.deny_unknown_keys = true;
+        self
+    }
+
+    /// Restricts labels of a node to the given ones.
+    pub fn labels<I, S>(mut self, labels: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.labels = Some(labels.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Validates the value returning all found violations.
+    pub fn validate(&self, value: &Value) -> Result<(), Vec<Violation>> {
+        let mut validator = Validator { path: String::from("$"), violations: Vec::new() };
+        validator.validate(self, value);
+
+        if validator.violations.is_empty() {
+            Ok(())
+        } else {
+            Err(validator.violations)
+        }
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::I64(_) => "integer",
+        Value::F64(_) => "float",
+        Value::String(_) => "string",
+        Value::List(_) => "list",
+        Value::Map(_) => "map",
+        Value::Bytes(_) => "bytes",
+        Value::Structure(Structure::Node(_)) => "node",
+        Value::Structure(_) => "structure",
+    }
+}
+
+fn kind_name(kind: &Kind) -> &'static str {
+    match kind {
+        Kind::Any => "any",
+        Kind::Bool => "boolean",
+        Kind::Integer => "integer",
+        Kind::Float => "float",
+        Kind::String => "string",
+        Kind::Bytes => "bytes",
+        Kind::List(_) => "list",
+        Kind::Map => "map",
+        Kind::Node => "node",
+    }
+}
+
+struct Validator {
+    path: String,
+    violations: Vec<Violation>,
+}
+
+impl Validator {
+    fn violation(&mut self, message: String) {
+        self.violations.push(Violation { path: self.path.clone(), message });
+    }
+
+    /// Validates the value with `segment` appended to the path.
+    fn nested(&mut self, segment: fmt::Arguments, schema: &Schema, value: &Value) {
+        let len = self.path.len();
+        fmt::Write::write_fmt(&mut self.path, segment).expect("writing to String to succeed");
+        self.validate(schema, value);
+        self.path.truncate(len);
+    }
+
+    fn validate(&mut self, schema: &Schema, value: &Value) {
+        let len = match (&schema.kind, value) {
+            (_, Value::Null) if schema.nullable => return,
+            (Kind::Any, _) => return,
+            (Kind::Bool, Value::Bool(_)) | (Kind::Integer, Value::I64(_)) | (Kind::Float, Value::F64(_)) => {
+                return
+            }
+            (Kind::String, Value::String(string)) => string.chars().count(),
+            (Kind::Bytes, Value::Bytes(bytes)) => bytes.len(),
+            (Kind::List(items), Value::List(list)) => {
+                for (index, item) in list.iter().enumerate() {
+                    self.nested(format_args!("[{}]", index), items, item);
+                }
+                list.len()
+            }
+            (Kind::Map, Value::Map(map)) => return self.validate_fields(schema, map),
+            (Kind::Node, Value::Structure(Structure::Node(node))) => {
+                if let Some(allowed) = &schema.labels {
+                    for label in node.labels.iter().filter(|label| !allowed.contains(label)) {
+                        self.violation(format!("label '{}' is not allowed", label));
+                    }
+                }
+                return self.validate_fields(schema, &node.properties);
+            }
+            (kind, value) => {
+                return self.violation(format!(
+                    "expected {}, got {} instead",
+                    kind_name(kind),
+                    type_name(value)
+                ))
+            }
+        };
+
+        let unit = match schema.kind {
+            Kind::String => "characters",
+            Kind::Bytes => "bytes",
+            _ => "items",
+        };
+        if let Some(min) = schema.min_len.filter(|min| len < *min) {
+            self.violation(format!("expected at least {} {}, got {} instead", min, unit, len));
+        }
+        if let Some(max) = schema.max_len.filter(|max| len > *max) {
+            self.violation(format!("expected at most {} {}, got {} instead", max, unit, len));
+        }
+    }
+
+    fn validate_fields(&mut self, schema: &Schema, map: &Map<String, Value>) {
+        for field in &schema.fields {
+            match map.get(&field.key) {
+                Some(value) => self.nested(format_args!(".{}", field.key), &field.schema, value),
+                None if field.required => self.violation(format!("missing required key '{}'", field.key)),
+                None => (),
+            }
+        }
+
+        if schema.deny_unknown_keys {
+            let mut unknown: Vec<&String> = map
+                .keys()
+                .filter(|key| !schema.fields.iter().any(|field| &field.key == *key))
+                .collect();
+            unknown.sort();
+            for key in unknown {
+                self.violation(format!("unknown key '{}'", key));
+            }
+        }
+    }
+}
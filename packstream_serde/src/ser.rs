@@ -1,19 +1,139 @@
-use super::constants::STRUCTURE_NAME;
+use crate::prelude::*;
+use super::constants::{RAW_VALUE_NAME, STRUCTURE_NAME};
 use super::error::{PackstreamError, PackstreamResult};
 use super::marker::Marker;
+use super::options::{EnumRepr, Options};
+use super::read::{ByteReader, Unpacker};
 use serde::{ser, Serialize};
 
 #[derive(Clone, Debug)]
 pub struct Serializer {
     output: Vec<u8>,
+    options: Options,
+    // Set when the next bytes are already encoded value of a RawValue.
+    raw: bool,
+}
+
+impl Serializer {
+    fn new(options: Options) -> Self {
+        Self { output: Vec::new(), options, raw: false }
+    }
+
+    /// Writes integer which may not fit into the packstream `i64` following the integer policy.
+    fn write_wide_int<T>(&mut self, value: T) -> PackstreamResult<()>
+    where
+        T: TryInto<i64> + PartialOrd + Default + Copy + core::fmt::Display,
+    {
+        let int = match self.options.integer_policy.fit(value)? {
+            Some(int) => int,
+            None => return ser::Serializer::serialize_str(self, &value.to_string()),
+        };
+        Marker::I64(int).append_to_vec(&mut self.output)?;
+        Ok(())
+    }
+
+    /// Writes the `{key: variant}` entry of a tagged enum.
+    fn write_tag(&mut self, key: &str, variant: &str) -> PackstreamResult<()> {
+        ser::Serializer::serialize_str(&mut *self, key)?;
+        ser::Serializer::serialize_str(&mut *self, variant)
+    }
+
+    /// Writes the map of an adjacently tagged enum up to the content value.
+    ///
+    /// Returns the tag entry which must be written after the content, when the content key
+    /// sorts first in the canonical mode.
+    fn write_adjacent(
+        &mut self,
+        tag: &str,
+        content: &str,
+        variant: &str,
+    ) -> PackstreamResult<Option<Vec<u8>>> {
+        Marker::Map(2).append_to_vec(&mut self.output)?;
+        if self.options.canonical && content < tag {
+            ser::Serializer::serialize_str(&mut *self, content)?;
+            let mut entry = encode_str(tag)?;
+            entry.extend(encode_str(variant)?);
+            return Ok(Some(entry));
+        }
+        self.write_tag(tag, variant)?;
+        ser::Serializer::serialize_str(&mut *self, content)?;
+        Ok(None)
+    }
+
+    /// Writes the internally tagged newtype variant by adding the tag entry into the map
+    /// of serialized `value`.
+    fn write_internal<T>(&mut self, tag: &str, variant: &str, value: &T) -> PackstreamResult<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        let mut content = Serializer::new(self.options);
+        value.serialize(&mut content)?;
+
+        let mut reader = ByteReader::new(&content.output);
+        let len = match reader.consume_marker()? {
+            Marker::Map(len) => len,
+            m => {
+                return Err(PackstreamError::create(format!(
+                    "Internally tagged variant {} must hold a map or a struct, got {} instead",
+                    variant, m
+                )))
+            }
+        };
+        let mut entries = Vec::with_capacity(len + 1);
+        for _ in 0..len {
+            let key = reader.skip_value()?;
+            if key_content(key) == tag.as_bytes() {
+                return Err(PackstreamError::create(format!(
+                    "Internally tagged variant {} contains the tag key '{}'",
+                    variant, tag
+                )));
+            }
+            entries.push((key, reader.skip_value()?));
+        }
+
+        let (key, value) = (encode_str(tag)?, encode_str(variant)?);
+        let index = if self.options.canonical {
+            entries.partition_point(|(k, _)| key_content(k) < tag.as_bytes())
+        } else {
+            0
+        };
+        entries.insert(index, (&key, &value));
+
+        Marker::Map(entries.len()).append_to_vec(&mut self.output)?;
+        for (key, value) in entries {
+            self.output.extend_from_slice(key);
+            self.output.extend_from_slice(value);
+        }
+        Ok(())
+    }
+}
+
+fn encode_str(value: &str) -> PackstreamResult<Vec<u8>> {
+    let mut serializer = Serializer::new(Options::default());
+    ser::Serializer::serialize_str(&mut serializer, value)?;
+    Ok(serializer.output)
 }
 
 pub fn to_bytes<T: Serialize>(value: &T) -> PackstreamResult<Vec<u8>> {
-    let mut serializer = Serializer { output: Vec::new() };
+    to_bytes_with_options(value, Options::default())
+}
+
+/// Serializes the value using given [`Options`].
+pub fn to_bytes_with_options<T: Serialize>(value: &T, options: Options) -> PackstreamResult<Vec<u8>> {
+    let mut serializer = Serializer::new(options);
     value.serialize(&mut serializer)?;
     Ok(serializer.output)
 }
 
+/// Serializes the value into its canonical packstream encoding.
+///
+/// Map entries are sorted by their keys, so equal values always produce equal bytes.
+/// Integers and sizes always use the smallest marker, which [`to_bytes`] does as well.
+/// Use [`is_canonical`](crate::is_canonical) to validate encoded bytes.
+pub fn to_bytes_canonical<T: Serialize>(value: &T) -> PackstreamResult<Vec<u8>> {
+    to_bytes_with_options(value, Options::new().canonical(true))
+}
+
 impl<'a> ser::Serializer for &'a mut Serializer {
     type Ok = ();
     type Error = PackstreamError;
@@ -68,11 +188,15 @@ impl<'a> ser::Serializer for &'a mut Serializer {
     }
 
     fn serialize_u64(self, value: u64) -> PackstreamResult<Self::Ok> {
-        let val_int = i64::try_from(value).map_err(|_| {
-            PackstreamError::create(format!("Attempt to convert {}u64 into i64 failed", value))
-        })?;
-        Marker::I64(val_int).append_to_vec(&mut self.output)?;
-        Ok(())
+        self.write_wide_int(value)
+    }
+
+    fn serialize_i128(self, value: i128) -> PackstreamResult<Self::Ok> {
+        self.write_wide_int(value)
+    }
+
+    fn serialize_u128(self, value: u128) -> PackstreamResult<Self::Ok> {
+        self.write_wide_int(value)
     }
 
     fn serialize_f32(self, value: f32) -> PackstreamResult<Self::Ok> {
@@ -95,6 +219,11 @@ impl<'a> ser::Serializer for &'a mut Serializer {
     }
 
     fn serialize_bytes(self, value: &[u8]) -> PackstreamResult<Self::Ok> {
+        if self.raw {
+            self.raw = false;
+            self.output.extend_from_slice(value);
+            return Ok(());
+        }
         Marker::String(value.len()).append_to_vec(&mut self.output)?;
         self.output.extend_from_slice(value);
         Ok(())
@@ -126,6 +255,15 @@ impl<'a> ser::Serializer for &'a mut Serializer {
         _variant_index: u32,
         variant: &'static str,
     ) -> PackstreamResult<Self::Ok> {
+        match self.options.enum_repr {
+            EnumRepr::External => (),
+            EnumRepr::UnitAsString => return self.serialize_str(variant),
+            EnumRepr::Internal { tag } | EnumRepr::Adjacent { tag, .. } => {
+                Marker::Map(1).append_to_vec(&mut self.output)?;
+                return self.write_tag(tag, variant);
+            }
+        }
+
         Marker::Map(1).append_to_vec(&mut self.output)?;
         Marker::String(variant.len()).append_to_vec(&mut self.output)?;
 
@@ -133,10 +271,11 @@ impl<'a> ser::Serializer for &'a mut Serializer {
         self.serialize_unit()
     }
 
-    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> PackstreamResult<Self::Ok>
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> PackstreamResult<Self::Ok>
     where
         T: ?Sized + Serialize,
     {
+        self.raw = name == RAW_VALUE_NAME;
         value.serialize(self)
     }
 
@@ -150,6 +289,19 @@ impl<'a> ser::Serializer for &'a mut Serializer {
     where
         T: ?Sized + Serialize,
     {
+        match self.options.enum_repr {
+            EnumRepr::External | EnumRepr::UnitAsString => (),
+            EnumRepr::Internal { tag } => return self.write_internal(tag, variant, value),
+            EnumRepr::Adjacent { tag, content } => {
+                let tag_entry = self.write_adjacent(tag, content, variant)?;
+                value.serialize(&mut *self)?;
+                if let Some(entry) = tag_entry {
+                    self.output.extend_from_slice(&entry);
+                }
+                return Ok(());
+            }
+        }
+
         Marker::Map(1).append_to_vec(&mut self.output)?;
         Marker::String(variant.len()).append_to_vec(&mut self.output)?;
 
@@ -194,15 +346,32 @@ impl<'a> ser::Serializer for &'a mut Serializer {
         variant: &'static str,
         len: usize,
     ) -> PackstreamResult<Self::SerializeTupleVariant> {
-        Marker::Map(1).append_to_vec(&mut self.output)?;
-        Marker::String(variant.len()).append_to_vec(&mut self.output)?;
-        self.output.extend_from_slice(&variant.as_bytes());
+        match self.options.enum_repr {
+            EnumRepr::External | EnumRepr::UnitAsString => {
+                Marker::Map(1).append_to_vec(&mut self.output)?;
+                Marker::String(variant.len()).append_to_vec(&mut self.output)?;
+                self.output.extend_from_slice(variant.as_bytes());
+            }
+            EnumRepr::Internal { .. } => {
+                return Err(PackstreamError::create(format!(
+                    "Tuple variant {} cannot be internally tagged",
+                    variant
+                )))
+            }
+            EnumRepr::Adjacent { tag, content } => {
+                if let Some(entry) = self.write_adjacent(tag, content, variant)? {
+                    return Ok(Compound::new_dyn(self, Marker::List(0)).with_trailer(entry));
+                }
+            }
+        }
         Marker::List(len).append_to_vec(&mut self.output)?;
         Ok(Compound::new_static(self))
     }
 
     fn serialize_map(self, len: Option<usize>) -> PackstreamResult<Self::SerializeMap> {
-        if let Some(len) = len {
+        if self.options.canonical {
+            Ok(Compound::new_sorted(self))
+        } else if let Some(len) = len {
             Marker::Map(len).append_to_vec(&mut self.output)?;
             Ok(Compound::new_static(self))
         } else {
@@ -215,6 +384,9 @@ impl<'a> ser::Serializer for &'a mut Serializer {
         _name: &'static str,
         len: usize,
     ) -> PackstreamResult<Self::SerializeStruct> {
+        if self.options.canonical {
+            return Ok(Compound::new_sorted(self));
+        }
         Marker::Map(len).append_to_vec(&mut self.output)?;
         Ok(Compound::new_static(self))
     }
@@ -226,9 +398,33 @@ impl<'a> ser::Serializer for &'a mut Serializer {
         variant: &'static str,
         len: usize,
     ) -> PackstreamResult<Self::SerializeStructVariant> {
-        Marker::Map(1).append_to_vec(&mut self.output)?;
-        Marker::String(variant.len()).append_to_vec(&mut self.output)?;
-        self.output.extend_from_slice(&variant.as_bytes());
+        match self.options.enum_repr {
+            EnumRepr::External | EnumRepr::UnitAsString => {
+                Marker::Map(1).append_to_vec(&mut self.output)?;
+                Marker::String(variant.len()).append_to_vec(&mut self.output)?;
+                self.output.extend_from_slice(variant.as_bytes());
+            }
+            EnumRepr::Internal { tag } if self.options.canonical => {
+                let mut compound = Compound::new_sorted(self);
+                if let Compound::Sorted { entries, .. } = &mut compound {
+                    entries.push((encode_str(tag)?, encode_str(variant)?));
+                }
+                return Ok(compound);
+            }
+            EnumRepr::Internal { tag } => {
+                Marker::Map(len + 1).append_to_vec(&mut self.output)?;
+                self.write_tag(tag, variant)?;
+                return Ok(Compound::new_static(self));
+            }
+            EnumRepr::Adjacent { tag, content } => {
+                if let Some(entry) = self.write_adjacent(tag, content, variant)? {
+                    return Ok(Compound::new_sorted(self).with_trailer(entry));
+                }
+            }
+        }
+        if self.options.canonical {
+            return Ok(Compound::new_sorted(self));
+        }
         Marker::Map(len).append_to_vec(&mut self.output)?;
         Ok(Compound::new_static(self))
     }
@@ -240,27 +436,82 @@ pub enum Compound<'a> {
         ser: &'a mut Serializer,
         buf: Vec<u8>, // old buffer
         marker: Marker,
+        trailer: Vec<u8>, // written after the value
     },
     StaticSized(&'a mut Serializer),
+    /// Map which entries are buffered and written sorted by key in the canonical mode.
+    Sorted {
+        ser: &'a mut Serializer,
+        buf: Vec<u8>, // old buffer
+        entries: Vec<(Vec<u8>, Vec<u8>)>,
+        trailer: Vec<u8>, // written after the value
+    },
 }
 
 impl<'a> Compound<'a> {
     fn new_dyn(ser: &'a mut Serializer, marker: Marker) -> Self {
         let mut buf = Vec::new();
-        std::mem::swap(&mut buf, &mut ser.output);
-        Self::DynSized { ser, buf, marker }
+        core::mem::swap(&mut buf, &mut ser.output);
+        Self::DynSized { ser, buf, marker, trailer: Vec::new() }
     }
 
     fn new_static(ser: &'a mut Serializer) -> Self {
         Self::StaticSized(ser)
     }
 
+    fn new_sorted(ser: &'a mut Serializer) -> Self {
+        let mut buf = Vec::new();
+        core::mem::swap(&mut buf, &mut ser.output);
+        Self::Sorted { ser, buf, entries: Vec::new(), trailer: Vec::new() }
+    }
+
+    /// Sets bytes written after the value, such as the tag entry of an adjacently tagged
+    /// variant which content key sorts first in the canonical mode.
+    fn with_trailer(mut self, bytes: Vec<u8>) -> Self {
+        match &mut self {
+            Compound::DynSized { trailer, .. } | Compound::Sorted { trailer, .. } => *trailer = bytes,
+            Compound::StaticSized(_) => unreachable!("StaticSized compound writes directly to the output"),
+        }
+        self
+    }
+
     fn end_state(&mut self) {
-        if let Compound::DynSized { ser, ref mut buf, marker } = self {
+        if let Compound::DynSized { ser, ref mut buf, marker, trailer } = self {
             marker.append_to_vec(buf).unwrap();
             buf.append(&mut ser.output);
-            std::mem::swap(buf, &mut ser.output);
+            buf.append(trailer);
+            core::mem::swap(buf, &mut ser.output);
+        }
+    }
+
+    fn end_sorted(&mut self) -> PackstreamResult<()> {
+        if let Compound::Sorted { ser, ref mut buf, entries, trailer } = self {
+            entries.sort_by(|(a, _), (b, _)| key_content(a).cmp(key_content(b)));
+
+            if entries.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+                return Err(PackstreamError::create("Canonical map cannot contain duplicate keys"));
+            }
+
+            Marker::Map(entries.len()).append_to_vec(buf)?;
+            for (key, value) in entries.drain(..) {
+                buf.extend_from_slice(&key);
+                buf.extend_from_slice(&value);
+            }
+            buf.append(trailer);
+            core::mem::swap(buf, &mut ser.output);
         }
+        Ok(())
+    }
+}
+
+/// Returns the bytes of the serialized map key without its marker.
+pub(crate) fn key_content(key: &[u8]) -> &[u8] {
+    use super::read::{ByteReader, Unpacker};
+
+    let mut reader = ByteReader::new(key);
+    match reader.peek_marker() {
+        Ok(Marker::String(_)) => &key[reader.peeked..],
+        _ => key,
     }
 }
 
@@ -278,6 +529,7 @@ impl<'a> ser::SerializeSeq for Compound<'a> {
                 marker.inc_size(1)?;
                 ser
             }
+            Compound::Sorted { .. } => unreachable!("Sorted compound is used only for maps"),
         };
         value.serialize(&mut **ser)
     }
@@ -302,6 +554,7 @@ impl<'a> ser::SerializeTuple for Compound<'a> {
                 ser
             }
             Compound::StaticSized(ser) => ser,
+            Compound::Sorted { .. } => unreachable!("Sorted compound is used only for maps"),
         };
         value.serialize(&mut **ser)
     }
@@ -326,6 +579,7 @@ impl<'a> ser::SerializeTupleStruct for Compound<'a> {
                 ser
             }
             Compound::StaticSized(ser) => ser,
+            Compound::Sorted { .. } => unreachable!("Sorted compound is used only for maps"),
         };
         value.serialize(&mut **ser)
     }
@@ -350,6 +604,7 @@ impl<'a> ser::SerializeTupleVariant for Compound<'a> {
                 ser
             }
             Compound::StaticSized(ser) => ser,
+            Compound::Sorted { .. } => unreachable!("Sorted compound is used only for maps"),
         };
         value.serialize(&mut **ser)
     }
@@ -374,6 +629,11 @@ impl<'a> ser::SerializeMap for Compound<'a> {
                 ser
             }
             Compound::StaticSized(ser) => ser,
+            Compound::Sorted { ser, entries, .. } => {
+                value.serialize(&mut **ser)?;
+                entries.push((core::mem::take(&mut ser.output), Vec::new()));
+                return Ok(());
+            }
         };
         value.serialize(&mut **ser)
     }
@@ -388,13 +648,19 @@ impl<'a> ser::SerializeMap for Compound<'a> {
                 ser
             }
             Compound::StaticSized(ser) => ser,
+            Compound::Sorted { ser, entries, .. } => {
+                value.serialize(&mut **ser)?;
+                let entry = entries.last_mut().expect("serialize_key to be called before serialize_value");
+                entry.1 = core::mem::take(&mut ser.output);
+                return Ok(());
+            }
         };
         value.serialize(&mut **ser)
     }
 
     fn end(mut self) -> PackstreamResult<Self::Ok> {
         self.end_state();
-        Ok(())
+        self.end_sorted()
     }
 }
 
@@ -406,21 +672,11 @@ impl<'a> ser::SerializeStruct for Compound<'a> {
     where
         T: ?Sized + Serialize,
     {
-        let ser = match self {
-            Compound::DynSized { ser, marker, .. } => {
-                marker.inc_size(1)?;
-                ser
-            }
-            Compound::StaticSized(ser) => ser,
-        };
-
-        key.serialize(&mut **ser).unwrap();
-        value.serialize(&mut **ser)
+        ser::SerializeMap::serialize_entry(self, key, value)
     }
 
-    fn end(mut self) -> PackstreamResult<Self::Ok> {
-        self.end_state();
-        Ok(())
+    fn end(self) -> PackstreamResult<Self::Ok> {
+        ser::SerializeMap::end(self)
     }
 }
 
@@ -432,20 +688,10 @@ impl<'a> ser::SerializeStructVariant for Compound<'a> {
     where
         T: ?Sized + Serialize,
     {
-        let ser = match self {
-            Compound::DynSized { ser, marker, .. } => {
-                marker.inc_size(1)?;
-                ser
-            }
-            Compound::StaticSized(ser) => ser,
-        };
-
-        key.serialize(&mut **ser).unwrap();
-        value.serialize(&mut **ser)
+        ser::SerializeMap::serialize_entry(self, key, value)
     }
 
-    fn end(mut self) -> PackstreamResult<()> {
-        self.end_state();
-        Ok(())
+    fn end(self) -> PackstreamResult<()> {
+        ser::SerializeMap::end(self)
     }
 }
@@ -1,20 +1,25 @@
 mod de;
 mod ser;
 mod display;
+mod walk;
+mod text;
 pub mod structure;
 pub use de::from_value;
-pub use ser::to_value;
+pub use ser::{to_value, to_value_with_options};
+pub use text::{from_text, to_text};
 
+use crate::prelude::*;
 use serde_bytes::ByteBuf;
-use std::collections::HashMap;
-use std::fmt;
+use crate::Map;
+use core::fmt;
 
 pub use structure::Structure;
+pub use walk::ValueVisitor;
 
 /// Represents any [Packstream value].
 ///
 /// [Packstream value]: https://7687.org/packstream/packstream-specification-1.html
-#[derive(PartialEq)]
+#[derive(Clone, PartialEq)]
 pub enum Value {
     Null,
     Bool(bool),
@@ -22,7 +27,7 @@ pub enum Value {
     F64(f64),
     String(String),
     List(Vec<Value>),
-    Map(HashMap<String, Value>),
+    Map(Map<String, Value>),
     Bytes(ByteBuf),
     Structure(Structure),
 }
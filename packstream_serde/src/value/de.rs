@@ -1,13 +1,14 @@
+use crate::prelude::*;
 use super::{Structure, Value};
 use crate::constants::STRUCTURE_SIG_KEY;
 use crate::error::{PackstreamError, PackstreamResult};
 use serde::de::IntoDeserializer;
 use serde::{de, forward_to_deserialize_any};
 use serde_bytes::ByteBuf;
-use std::borrow::Cow;
-use std::collections::HashMap;
-use std::convert::TryFrom;
-use std::fmt;
+use alloc::borrow::Cow;
+use crate::Map;
+use core::convert::TryFrom;
+use core::fmt;
 
 pub fn from_value<T>(value: Value) -> PackstreamResult<T>
 where
@@ -18,6 +19,7 @@ where
 
 mod errors {
     use super::{PackstreamError, Value};
+    use alloc::format;
 
     pub(super) fn unexpected_type(expected: &str, actual: &Value) -> PackstreamError {
         PackstreamError::create(format!(
@@ -99,14 +101,14 @@ impl<'de> de::Visitor<'de> for ValueVisitor {
                 Structure::from_map_access_no_sig_key(&mut map_access)?,
             )),
             Some(key) => {
-                let mut map: HashMap<String, Value> = HashMap::new();
+                let mut map: Map<String, Value> = Map::new();
                 map.insert(String::from(key), map_access.next_value()?);
                 while let Some(key) = map_access.next_key::<String>()? {
                     map.insert(key, map_access.next_value()?);
                 }
                 Ok(Value::Map(map))
             }
-            None => Ok(Value::Map(HashMap::new())),
+            None => Ok(Value::Map(Map::new())),
         }
     }
 }
@@ -433,7 +435,7 @@ impl<'de> de::Deserializer<'de> for Value {
 }
 
 struct SeqDeserializer {
-    iter: std::vec::IntoIter<Value>,
+    iter: alloc::vec::IntoIter<Value>,
 }
 
 impl<'de> de::Deserializer<'de> for SeqDeserializer {
@@ -526,7 +528,7 @@ impl<'de> de::Deserializer<'de> for StringDe<'de> {
 }
 
 struct MapAccess {
-    iter: <std::collections::HashMap<String, Value> as IntoIterator>::IntoIter,
+    iter: <Map<String, Value> as IntoIterator>::IntoIter,
     value: Option<Value>,
 }
 
@@ -1,5 +1,6 @@
-use std::collections::HashMap;
-use std::fmt;
+use crate::prelude::*;
+use crate::Map;
+use core::fmt;
 
 pub fn display_value_list<V: fmt::Display>(value: &Vec<V>, formatter: &mut fmt::Formatter) -> fmt::Result {
     let len = value.len();
@@ -25,7 +26,7 @@ pub fn display_value_list<V: fmt::Display>(value: &Vec<V>, formatter: &mut fmt::
     formatter.write_str(output.as_ref())
 }
 
-pub fn display_value_hash_map<V: fmt::Display>(value: &HashMap<String, V>, formatter: &mut fmt::Formatter) -> fmt::Result {
+pub fn display_value_hash_map<V: fmt::Display>(value: &Map<String, V>, formatter: &mut fmt::Formatter) -> fmt::Result {
     let len = value.len();
 
     if len == 0 {
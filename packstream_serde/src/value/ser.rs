@@ -1,14 +1,62 @@
+use crate::prelude::*;
 use super::Value;
+use crate::constants::RAW_VALUE_NAME;
 use crate::error::{PackstreamError, PackstreamResult};
+use crate::options::{EnumRepr, Options};
 use serde::ser::{self, Impossible};
 use serde_bytes::ByteBuf;
-use std::collections::HashMap;
+use crate::Map;
 
 pub fn to_value<T>(value: T) -> PackstreamResult<Value>
 where
     T: ser::Serialize,
 {
-    value.serialize(Serializer)
+    to_value_with_options(value, Options::default())
+}
+
+/// Serializes the value into [`Value`] using given [`Options`], which matches
+/// [`to_bytes_with_options`](crate::to_bytes_with_options) followed by decoding.
+///
+/// Unlike in bytes, unit variants of the externally tagged representation are
+/// serialized into strings.
+pub fn to_value_with_options<T>(value: T, options: Options) -> PackstreamResult<Value>
+where
+    T: ser::Serialize,
+{
+    value.serialize(Serializer { options })
+}
+
+/// Wraps the serialized content of an enum variant following the enum representation.
+fn tag_variant(options: Options, variant: &str, content: Value) -> PackstreamResult<Value> {
+    let mut map = Map::new();
+    match options.enum_repr {
+        EnumRepr::External | EnumRepr::UnitAsString => {
+            map.insert(String::from(variant), content);
+        }
+        EnumRepr::Internal { tag } => match content {
+            Value::Map(mut fields) => {
+                if fields.contains_key(tag) {
+                    return Err(PackstreamError::create(format!(
+                        "Internally tagged variant {} contains the tag key '{}'",
+                        variant, tag
+                    )));
+                }
+                fields.insert(String::from(tag), Value::String(String::from(variant)));
+                return Ok(Value::Map(fields));
+            }
+            content => {
+                return Err(PackstreamError::create(format!(
+                    "Internally tagged variant {} must hold a map or a struct, got {} instead",
+                    variant, content
+                )))
+            }
+        },
+        EnumRepr::Adjacent { tag, content: key } => {
+            map.insert(String::from(tag), Value::String(String::from(variant)));
+            map.insert(String::from(key), content);
+        }
+    }
+    Ok(Value::Map(map))
 }
 
 impl ser::Serialize for Value {
@@ -35,7 +83,21 @@ impl ser::Serialize for Value {
     }
 }
 
-pub struct Serializer;
+pub struct Serializer {
+    options: Options,
+}
+
+impl Serializer {
+    fn serialize_wide_int<T>(self, value: T) -> PackstreamResult<Value>
+    where
+        T: TryInto<i64> + PartialOrd + Default + Copy + core::fmt::Display,
+    {
+        match self.options.integer_policy.fit(value)? {
+            Some(int) => Ok(Value::I64(int)),
+            None => Ok(Value::String(value.to_string())),
+        }
+    }
+}
 
 impl ser::Serializer for Serializer {
     type Ok = Value;
@@ -82,10 +144,15 @@ impl ser::Serializer for Serializer {
     }
 
     fn serialize_u64(self, value: u64) -> PackstreamResult<Self::Ok> {
-        let val_int = i64::try_from(value).map_err(|_| {
-            PackstreamError::create(format!("Attempt to convert {}u64 into i63 failed", value))
-        })?;
-        self.serialize_i64(val_int)
+        self.serialize_wide_int(value)
+    }
+
+    fn serialize_i128(self, value: i128) -> PackstreamResult<Self::Ok> {
+        self.serialize_wide_int(value)
+    }
+
+    fn serialize_u128(self, value: u128) -> PackstreamResult<Self::Ok> {
+        self.serialize_wide_int(value)
     }
 
     fn serialize_f32(self, value: f32) -> PackstreamResult<Self::Ok> {
@@ -124,17 +191,30 @@ impl ser::Serializer for Serializer {
         _variant_index: u32,
         variant: &'static str,
     ) -> PackstreamResult<Self::Ok> {
-        self.serialize_str(variant)
+        match self.options.enum_repr {
+            EnumRepr::External | EnumRepr::UnitAsString => self.serialize_str(variant),
+            EnumRepr::Internal { tag } | EnumRepr::Adjacent { tag, .. } => {
+                let mut map = Map::new();
+                map.insert(String::from(tag), Value::String(String::from(variant)));
+                Ok(Value::Map(map))
+            }
+        }
     }
 
     fn serialize_newtype_struct<T: ?Sized>(
         self,
-        _name: &'static str,
+        name: &'static str,
         value: &T,
     ) -> PackstreamResult<Self::Ok>
     where
         T: ser::Serialize,
     {
+        if name == RAW_VALUE_NAME {
+            return match value.serialize(self)? {
+                Value::Bytes(bytes) => crate::from_bytes(&bytes),
+                _ => unreachable!("RawValue always serializes its bytes."),
+            };
+        }
         value.serialize(self)
     }
 
@@ -148,9 +228,7 @@ impl ser::Serializer for Serializer {
     where
         T: ser::Serialize,
     {
-        let mut map = HashMap::new();
-        map.insert(String::from(variant), to_value(value)?);
-        Ok(Value::Map(map))
+        tag_variant(self.options, variant, to_value_with_options(value, self.options)?)
     }
 
     fn serialize_none(self) -> PackstreamResult<Self::Ok> {
@@ -165,7 +243,7 @@ impl ser::Serializer for Serializer {
     }
 
     fn serialize_seq(self, len: Option<usize>) -> PackstreamResult<Self::SerializeSeq> {
-        Ok(SerializeSeq::new(len.unwrap_or(0)))
+        Ok(SerializeSeq::new(len.unwrap_or(0), self.options))
     }
 
     fn serialize_tuple(self, len: usize) -> PackstreamResult<Self::SerializeTuple> {
@@ -187,16 +265,24 @@ impl ser::Serializer for Serializer {
         variant: &'static str,
         _len: usize,
     ) -> PackstreamResult<Self::SerializeTupleVariant> {
+        if let EnumRepr::Internal { .. } = self.options.enum_repr {
+            return Err(PackstreamError::create(format!(
+                "Tuple variant {} cannot be internally tagged",
+                variant
+            )));
+        }
         Ok(SerializeTupleVariant {
             name: variant.to_owned(),
             vec: Vec::new(),
+            options: self.options,
         })
     }
 
     fn serialize_map(self, _len: Option<usize>) -> PackstreamResult<Self::SerializeMap> {
         Ok(SerializeMap {
             key: None,
-            map: HashMap::new(),
+            map: Map::new(),
+            options: self.options,
         })
     }
 
@@ -217,19 +303,22 @@ impl ser::Serializer for Serializer {
     ) -> PackstreamResult<Self::SerializeStructVariant> {
         Ok(SerializeStructVariant {
             name: variant.to_owned(),
-            map: HashMap::new(),
+            map: Map::new(),
+            options: self.options,
         })
     }
 }
 
 pub struct SerializeSeq {
     vec: Vec<Value>,
+    options: Options,
 }
 
 impl SerializeSeq {
-    fn new(len: usize) -> Self {
+    fn new(len: usize, options: Options) -> Self {
         Self {
             vec: Vec::with_capacity(len),
+            options,
         }
     }
 }
@@ -242,7 +331,7 @@ impl ser::SerializeSeq for SerializeSeq {
     where
         T: ser::Serialize,
     {
-        self.vec.push(to_value(&value)?);
+        self.vec.push(to_value_with_options(value, self.options)?);
         Ok(())
     }
 
@@ -286,6 +375,7 @@ impl ser::SerializeTupleStruct for SerializeSeq {
 pub struct SerializeTupleVariant {
     name: String,
     vec: Vec<Value>,
+    options: Options,
 }
 
 impl ser::SerializeTupleVariant for SerializeTupleVariant {
@@ -296,20 +386,19 @@ impl ser::SerializeTupleVariant for SerializeTupleVariant {
     where
         T: ser::Serialize,
     {
-        self.vec.push(to_value(&value)?);
+        self.vec.push(to_value_with_options(value, self.options)?);
         Ok(())
     }
 
     fn end(self) -> PackstreamResult<Self::Ok> {
-        let mut map = HashMap::new();
-        map.insert(self.name, Value::List(self.vec));
-        Ok(Value::Map(map))
+        tag_variant(self.options, &self.name, Value::List(self.vec))
     }
 }
 
 pub struct SerializeMap {
-    map: HashMap<String, Value>,
+    map: Map<String, Value>,
     key: Option<String>,
+    options: Options,
 }
 
 impl ser::SerializeMap for SerializeMap {
@@ -329,7 +418,7 @@ impl ser::SerializeMap for SerializeMap {
         T: ser::Serialize,
     {
         self.map
-            .insert(self.key.take().expect("Key to exist"), to_value(&value)?);
+            .insert(self.key.take().expect("Key to exist"), to_value_with_options(value, self.options)?);
         Ok(())
     }
 
@@ -357,7 +446,8 @@ impl ser::SerializeStruct for SerializeMap {
 
 pub struct SerializeStructVariant {
     name: String,
-    map: HashMap<String, Value>,
+    map: Map<String, Value>,
+    options: Options,
 }
 
 impl ser::SerializeStructVariant for SerializeStructVariant {
@@ -368,14 +458,12 @@ impl ser::SerializeStructVariant for SerializeStructVariant {
     where
         T: ser::Serialize,
     {
-        self.map.insert(key.to_owned(), to_value(value)?);
+        self.map.insert(key.to_owned(), to_value_with_options(value, self.options)?);
         Ok(())
     }
 
     fn end(self) -> PackstreamResult<Self::Ok> {
-        let mut map = HashMap::new();
-        map.insert(self.name, Value::Map(self.map));
-        Ok(Value::Map(map))
+        tag_variant(self.options, &self.name, Value::Map(self.map))
     }
 }
 
@@ -1,9 +1,12 @@
-use std::fmt;
+use crate::prelude::*;
+use core::fmt;
 use crate::packstream::PackstreamStructure;
 use serde::de::Error;
 
 mod de;
 mod ser;
+mod temporal;
+mod spatial;
 
 mod node;
 mod path;
@@ -32,11 +35,12 @@ pub use local_date_time::LocalDateTime;
 pub use duration::Duration;
 pub use point_2d::Point2D;
 pub use point_3d::Point3D;
+pub use spatial::Crs;
 
 /// Represents any possible [`Bolt Structure`].
 ///
 /// [`Bolt Structure`]: https://boltprotocol.org/v1/#structures
-#[derive(PartialEq)]
+#[derive(Clone, PartialEq)]
 pub enum Structure {
     Node(Node),
     Path(Path),
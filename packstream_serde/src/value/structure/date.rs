@@ -1,3 +1,4 @@
+use crate::prelude::*;
 use super::super::Value;
 use crate::{
     constants::STRUCTURE_NAME,
@@ -9,13 +10,68 @@ use serde::{
     de, forward_to_deserialize_any,
     ser::{self, SerializeTupleStruct},
 };
-use std::fmt;
+use super::temporal::{self, Cursor};
+use super::Duration;
+use core::{fmt, ops, str::FromStr};
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Date {
     pub days: i64,
 }
 
+impl Date {
+    pub fn new(days: i64) -> PackstreamResult<Self> {
+        temporal::validate_days(days)?;
+        Ok(Self { days })
+    }
+
+    /// Creates date from the proleptic Gregorian calendar year, month (1-12) and day (1-31).
+    pub fn from_ymd(year: i64, month: u32, day: u32) -> PackstreamResult<Self> {
+        temporal::days_from_ymd(year, month, day).map(|days| Self { days })
+    }
+
+    /// Returns the year, month (1-12) and day (1-31) of the date.
+    pub fn to_ymd(&self) -> (i64, u32, u32) {
+        temporal::ymd_from_days(self.days)
+    }
+
+    /// Adds the duration following the Cypher semantics.
+    ///
+    /// Months are added first and the day is clamped to the last day of the resulting month,
+    /// then days are added. Seconds and nanoseconds are added as whole days truncated towards zero.
+    pub fn add_duration(&self, duration: &Duration) -> PackstreamResult<Self> {
+        let time_days = duration.total_time_nanoseconds() / i128::from(temporal::NANOS_PER_DAY);
+        let days = temporal::add_months(self.days, duration.months)?
+            .checked_add(duration.days)
+            .and_then(|days| days.checked_add(time_days as i64))
+            .ok_or_else(|| temporal::overflow("Date"))?;
+
+        Self::new(days)
+    }
+}
+
+/// # Panics
+///
+/// Panics if the result is out of range, see [`Date::add_duration`] for the checked version.
+impl ops::Add<Duration> for Date {
+    type Output = Date;
+
+    fn add(self, duration: Duration) -> Date {
+        self.add_duration(&duration).expect("Date out of range")
+    }
+}
+
+/// # Panics
+///
+/// Panics if the result is out of range, see [`Date::add_duration`] for the checked version.
+impl ops::Sub<Duration> for Date {
+    type Output = Date;
+
+    fn sub(self, duration: Duration) -> Date {
+        self.add_duration(&-duration).expect("Date out of range")
+    }
+}
+
 impl PackstreamStructure for Date {
     const SIG: u8 = structure::DATE;
     const LEN: u8 = 0x01;
@@ -32,9 +88,18 @@ impl PackstreamStructure for Date {
 
 impl fmt::Display for Date {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("Date")
-            .field("days", &self.days)
-            .finish()
+        temporal::write_date(f, self.days)
+    }
+}
+
+impl FromStr for Date {
+    type Err = PackstreamError;
+
+    fn from_str(text: &str) -> PackstreamResult<Self> {
+        let mut cursor = Cursor::new("Date", text);
+        let days = cursor.date()?;
+        cursor.end()?;
+        Ok(Self { days })
     }
 }
 
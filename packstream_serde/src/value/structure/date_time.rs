@@ -1,3 +1,4 @@
+use crate::prelude::*;
 use super::super::Value;
 use crate::{
     constants::STRUCTURE_NAME,
@@ -9,15 +10,31 @@ use serde::{
     de, forward_to_deserialize_any,
     ser::{self, SerializeTupleStruct},
 };
-use std::fmt;
+use super::temporal::{self, Cursor};
+use core::{fmt, str::FromStr};
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct DateTime {
     pub seconds: i64,
     pub nanoseconds: i64,
     pub tz_offset_seconds: i64,
 }
 
+impl DateTime {
+    /// Creates date time from the local seconds since the Unix epoch, nanoseconds of the second
+    /// and the time zone offset.
+    pub fn new(seconds: i64, nanoseconds: i64, tz_offset_seconds: i64) -> PackstreamResult<Self> {
+        temporal::validate_epoch_seconds(seconds)?;
+        temporal::validate_nanoseconds(nanoseconds)?;
+        temporal::validate_offset(tz_offset_seconds)?;
+        Ok(Self {
+            seconds,
+            nanoseconds,
+            tz_offset_seconds,
+        })
+    }
+}
+
 impl PackstreamStructure for DateTime {
     const SIG: u8 = structure::DATE_TIME;
     const LEN: u8 = 0x03;
@@ -36,11 +53,27 @@ impl PackstreamStructure for DateTime {
 
 impl fmt::Display for DateTime {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("DateTime")
-            .field("seconds", &self.seconds)
-            .field("nanoseconds", &self.nanoseconds)
-            .field("tz_offset_seconds", &self.tz_offset_seconds)
-            .finish()
+        let (days, nano_of_day) = temporal::split_epoch_seconds(self.seconds, self.nanoseconds);
+        temporal::write_date(f, days)?;
+        f.write_str("T")?;
+        temporal::write_time(f, nano_of_day)?;
+        temporal::write_offset(f, self.tz_offset_seconds)
+    }
+}
+
+impl FromStr for DateTime {
+    type Err = PackstreamError;
+
+    fn from_str(text: &str) -> PackstreamResult<Self> {
+        let mut cursor = Cursor::new("DateTime", text);
+        let (seconds, nanoseconds) = cursor.date_time()?;
+        let tz_offset_seconds = cursor.offset()?;
+        cursor.end()?;
+        Ok(Self {
+            seconds,
+            nanoseconds,
+            tz_offset_seconds,
+        })
     }
 }
 
@@ -1,3 +1,4 @@
+use crate::prelude::*;
 use super::super::Value;
 use crate::{
     constants::STRUCTURE_NAME,
@@ -9,15 +10,34 @@ use serde::{
     de, forward_to_deserialize_any,
     ser::{self, SerializeTupleStruct},
 };
-use std::fmt;
+use super::temporal::{self, Cursor};
+use core::{fmt, str::FromStr};
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct DateTimeZoneId {
     pub seconds: i64,
     pub nanoseconds: i64,
     pub tz_id: String,
 }
 
+impl DateTimeZoneId {
+    /// Creates date time from the local seconds since the Unix epoch, nanoseconds of the second
+    /// and the time zone identifier, e.g. `Europe/Warsaw`.
+    pub fn new(seconds: i64, nanoseconds: i64, tz_id: impl Into<String>) -> PackstreamResult<Self> {
+        let tz_id = tz_id.into();
+        temporal::validate_epoch_seconds(seconds)?;
+        temporal::validate_nanoseconds(nanoseconds)?;
+        if tz_id.is_empty() || tz_id.contains(|c: char| c == '[' || c == ']' || c.is_whitespace()) {
+            return Err(temporal::error("time zone id", format!("'{}'", tz_id)));
+        }
+        Ok(Self {
+            seconds,
+            nanoseconds,
+            tz_id,
+        })
+    }
+}
+
 impl PackstreamStructure for DateTimeZoneId {
     const SIG: u8 = structure::DATE_TIME_ZONE_ID;
     const LEN: u8 = 0x03;
@@ -36,11 +56,27 @@ impl PackstreamStructure for DateTimeZoneId {
 
 impl fmt::Display for DateTimeZoneId {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("DateTimeZoneId")
-            .field("seconds", &self.seconds)
-            .field("nanoseconds", &self.nanoseconds)
-            .field("tz_id", &self.tz_id)
-            .finish()
+        let (days, nano_of_day) = temporal::split_epoch_seconds(self.seconds, self.nanoseconds);
+        temporal::write_date(f, days)?;
+        f.write_str("T")?;
+        temporal::write_time(f, nano_of_day)?;
+        write!(f, "[{}]", self.tz_id)
+    }
+}
+
+impl FromStr for DateTimeZoneId {
+    type Err = PackstreamError;
+
+    /// Parses local date time followed by the time zone id in brackets, e.g. `2020-01-01T12:00:00[Europe/Warsaw]`.
+    fn from_str(text: &str) -> PackstreamResult<Self> {
+        let mut cursor = Cursor::new("DateTimeZoneId", text);
+        let (seconds, nanoseconds) = cursor.date_time()?;
+        cursor.expect(b'[')?;
+        let tz_id = cursor.take_rest();
+        match tz_id.strip_suffix(']') {
+            Some(tz_id) => Self::new(seconds, nanoseconds, tz_id).map_err(|_| cursor.error()),
+            None => Err(cursor.error()),
+        }
     }
 }
 
@@ -1,10 +1,11 @@
+use crate::prelude::*;
 use super::*;
 use crate::{
     constants::STRUCTURE_SIG_KEY,
     error::{PackstreamError, PackstreamResult},
 };
 use serde::{de, forward_to_deserialize_any};
-use std::fmt;
+use core::fmt;
 struct StructureVisitor;
 
 impl<'de> de::Visitor<'de> for StructureVisitor {
@@ -1,3 +1,4 @@
+use crate::prelude::*;
 use super::super::Value;
 use crate::{
     constants::STRUCTURE_NAME,
@@ -9,9 +10,10 @@ use serde::{
     de, forward_to_deserialize_any,
     ser::{self, SerializeTupleStruct},
 };
-use std::fmt;
+use super::temporal::{self, Cursor};
+use core::{convert::TryFrom, fmt, ops, str::FromStr};
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Duration {
     pub months: i64,
     pub days: i64,
@@ -19,6 +21,45 @@ pub struct Duration {
     pub nanoseconds: i64,
 }
 
+impl Duration {
+    /// Creates duration carrying nanoseconds outside of the `0..1_000_000_000` range into seconds.
+    pub fn new(months: i64, days: i64, seconds: i64, nanoseconds: i64) -> PackstreamResult<Self> {
+        let seconds = seconds
+            .checked_add(nanoseconds.div_euclid(temporal::NANOS_PER_SECOND))
+            .ok_or_else(|| temporal::overflow("Duration"))?;
+
+        Ok(Self {
+            months,
+            days,
+            seconds,
+            nanoseconds: nanoseconds.rem_euclid(temporal::NANOS_PER_SECOND),
+        })
+    }
+
+    pub(super) fn total_time_nanoseconds(&self) -> i128 {
+        i128::from(self.seconds) * i128::from(temporal::NANOS_PER_SECOND) + i128::from(self.nanoseconds)
+    }
+}
+
+/// # Panics
+///
+/// Panics if a component of the negated duration is out of range.
+impl ops::Neg for Duration {
+    type Output = Duration;
+
+    fn neg(self) -> Duration {
+        let nanoseconds = -self.total_time_nanoseconds();
+        let seconds = nanoseconds.div_euclid(i128::from(temporal::NANOS_PER_SECOND));
+
+        Duration {
+            months: self.months.checked_neg().expect("Duration out of range"),
+            days: self.days.checked_neg().expect("Duration out of range"),
+            seconds: i64::try_from(seconds).expect("Duration out of range"),
+            nanoseconds: nanoseconds.rem_euclid(i128::from(temporal::NANOS_PER_SECOND)) as i64,
+        }
+    }
+}
+
 impl PackstreamStructure for Duration {
     const SIG: u8 = structure::DURATION;
     const LEN: u8 = 0x04;
@@ -37,13 +78,107 @@ impl PackstreamStructure for Duration {
 }
 
 impl fmt::Display for Duration {
+    /// Formats the duration as ISO-8601 duration, e.g. `P1Y2M3DT4H5M6.5S`.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("Duration")
-            .field("months", &self.months)
-            .field("days", &self.days)
-            .field("seconds", &self.seconds)
-            .field("nanoseconds", &self.nanoseconds)
-            .finish()
+        let time = self.total_time_nanoseconds();
+        if self.months == 0 && self.days == 0 && time == 0 {
+            return f.write_str("PT0S");
+        }
+
+        f.write_str("P")?;
+        let (years, months) = (self.months / 12, self.months % 12);
+        if years != 0 {
+            write!(f, "{}Y", years)?;
+        }
+        if months != 0 {
+            write!(f, "{}M", months)?;
+        }
+        if self.days != 0 {
+            write!(f, "{}D", self.days)?;
+        }
+        if time == 0 {
+            return Ok(());
+        }
+
+        f.write_str("T")?;
+        let nanos_per_second = i128::from(temporal::NANOS_PER_SECOND);
+        let (hours, minutes) = (time / (3_600 * nanos_per_second), time / (60 * nanos_per_second) % 60);
+        let nanoseconds = time % (60 * nanos_per_second);
+        if hours != 0 {
+            write!(f, "{}H", hours)?;
+        }
+        if minutes != 0 {
+            write!(f, "{}M", minutes)?;
+        }
+        if nanoseconds != 0 {
+            if nanoseconds < 0 {
+                f.write_str("-")?;
+            }
+            let nanoseconds = nanoseconds.abs();
+            write!(f, "{}", nanoseconds / nanos_per_second)?;
+            temporal::write_fraction(f, (nanoseconds % nanos_per_second) as i64)?;
+            f.write_str("S")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Duration {
+    type Err = PackstreamError;
+
+    /// Parses ISO-8601 duration, e.g. `P1Y2M3W4DT5H6M7.8S`. Components can be negative
+    /// and only seconds can have a fraction.
+    fn from_str(text: &str) -> PackstreamResult<Self> {
+        let mut cursor = Cursor::new("Duration", text);
+        cursor.expect(b'P')?;
+
+        let overflow = || temporal::overflow("Duration");
+        let (mut months, mut days, mut nanoseconds) = (0_i64, 0_i64, 0_i128);
+        let mut time = false;
+        let mut empty = true;
+
+        while cursor.peek().is_some() {
+            if !time && cursor.consume(b'T') {
+                time = true;
+                continue;
+            }
+
+            let negative = cursor.consume(b'-');
+            let sign = if negative { -1 } else { 1 };
+            let (value, _) = cursor.digits(1, 18)?;
+            let value = sign * value;
+            let fraction = if time { cursor.fraction()? } else { 0 };
+
+            let unit = cursor.peek().ok_or_else(|| cursor.error())?;
+            cursor.expect(unit)?;
+
+            let nanos_per_second = i128::from(temporal::NANOS_PER_SECOND);
+            match (time, unit) {
+                (false, b'Y') => months = value.checked_mul(12).and_then(|v| months.checked_add(v)).ok_or_else(overflow)?,
+                (false, b'M') => months = months.checked_add(value).ok_or_else(overflow)?,
+                (false, b'W') => days = value.checked_mul(7).and_then(|v| days.checked_add(v)).ok_or_else(overflow)?,
+                (false, b'D') => days = days.checked_add(value).ok_or_else(overflow)?,
+                (true, b'H') if fraction == 0 => nanoseconds += i128::from(value) * 3_600 * nanos_per_second,
+                (true, b'M') if fraction == 0 => nanoseconds += i128::from(value) * 60 * nanos_per_second,
+                (true, b'S') => {
+                    nanoseconds += i128::from(value) * nanos_per_second + i128::from(sign * fraction)
+                }
+                _ => return Err(cursor.error()),
+            }
+            empty = false;
+        }
+
+        if empty {
+            return Err(cursor.error());
+        }
+
+        let seconds = nanoseconds.div_euclid(i128::from(temporal::NANOS_PER_SECOND));
+        Ok(Self {
+            months,
+            days,
+            seconds: i64::try_from(seconds).map_err(|_| overflow())?,
+            nanoseconds: nanoseconds.rem_euclid(i128::from(temporal::NANOS_PER_SECOND)) as i64,
+        })
     }
 }
 
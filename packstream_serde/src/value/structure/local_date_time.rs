@@ -1,3 +1,4 @@
+use crate::prelude::*;
 use super::super::Value;
 use crate::{
     constants::STRUCTURE_NAME,
@@ -9,14 +10,89 @@ use serde::{
     de, forward_to_deserialize_any,
     ser::{self, SerializeTupleStruct},
 };
-use std::fmt;
+use super::temporal::{self, Cursor};
+use super::{Date, Duration, LocalTime};
+use core::{convert::TryFrom, fmt, ops, str::FromStr};
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct LocalDateTime {
     pub seconds: i64,
     pub nanoseconds: i64,
 }
 
+impl LocalDateTime {
+    /// Creates date time from the seconds since the Unix epoch and the nanoseconds of the second.
+    pub fn new(seconds: i64, nanoseconds: i64) -> PackstreamResult<Self> {
+        temporal::validate_epoch_seconds(seconds)?;
+        temporal::validate_nanoseconds(nanoseconds)?;
+        Ok(Self { seconds, nanoseconds })
+    }
+
+    pub fn from_date_time(date: &Date, time: &LocalTime) -> PackstreamResult<Self> {
+        let seconds = date
+            .days
+            .checked_mul(temporal::SECONDS_PER_DAY)
+            .and_then(|seconds| seconds.checked_add(time.nanoseconds.div_euclid(temporal::NANOS_PER_SECOND)))
+            .ok_or_else(|| temporal::overflow("LocalDateTime"))?;
+        Self::new(seconds, time.nanoseconds.rem_euclid(temporal::NANOS_PER_SECOND))
+    }
+
+    pub fn date(&self) -> Date {
+        Date {
+            days: temporal::split_epoch_seconds(self.seconds, self.nanoseconds).0,
+        }
+    }
+
+    pub fn time(&self) -> LocalTime {
+        LocalTime {
+            nanoseconds: temporal::split_epoch_seconds(self.seconds, self.nanoseconds).1,
+        }
+    }
+
+    /// Adds the duration following the Cypher semantics.
+    ///
+    /// Months are added first and the day is clamped to the last day of the resulting month,
+    /// then days are added and finally seconds and nanoseconds.
+    pub fn add_duration(&self, duration: &Duration) -> PackstreamResult<Self> {
+        let (days, nano_of_day) = temporal::split_epoch_seconds(self.seconds, self.nanoseconds);
+        let days = temporal::add_months(days, duration.months)?
+            .checked_add(duration.days)
+            .ok_or_else(|| temporal::overflow("LocalDateTime"))?;
+
+        let nanoseconds = i128::from(days) * i128::from(temporal::NANOS_PER_DAY)
+            + i128::from(nano_of_day)
+            + duration.total_time_nanoseconds();
+        let seconds = nanoseconds.div_euclid(i128::from(temporal::NANOS_PER_SECOND));
+
+        Self::new(
+            i64::try_from(seconds).map_err(|_| temporal::overflow("LocalDateTime"))?,
+            nanoseconds.rem_euclid(i128::from(temporal::NANOS_PER_SECOND)) as i64,
+        )
+    }
+}
+
+/// # Panics
+///
+/// Panics if the result is out of range, see [`LocalDateTime::add_duration`] for the checked version.
+impl ops::Add<Duration> for LocalDateTime {
+    type Output = LocalDateTime;
+
+    fn add(self, duration: Duration) -> LocalDateTime {
+        self.add_duration(&duration).expect("LocalDateTime out of range")
+    }
+}
+
+/// # Panics
+///
+/// Panics if the result is out of range, see [`LocalDateTime::add_duration`] for the checked version.
+impl ops::Sub<Duration> for LocalDateTime {
+    type Output = LocalDateTime;
+
+    fn sub(self, duration: Duration) -> LocalDateTime {
+        self.add_duration(&-duration).expect("LocalDateTime out of range")
+    }
+}
+
 impl PackstreamStructure for LocalDateTime {
     const SIG: u8 = structure::LOCAL_DATE_TIME;
     const LEN: u8 = 0x02;
@@ -34,10 +110,21 @@ impl PackstreamStructure for LocalDateTime {
 
 impl fmt::Display for LocalDateTime {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("LocalDateTime")
-            .field("seconds", &self.seconds)
-            .field("nanoseconds", &self.nanoseconds)
-            .finish()
+        let (days, nano_of_day) = temporal::split_epoch_seconds(self.seconds, self.nanoseconds);
+        temporal::write_date(f, days)?;
+        f.write_str("T")?;
+        temporal::write_time(f, nano_of_day)
+    }
+}
+
+impl FromStr for LocalDateTime {
+    type Err = PackstreamError;
+
+    fn from_str(text: &str) -> PackstreamResult<Self> {
+        let mut cursor = Cursor::new("LocalDateTime", text);
+        let (seconds, nanoseconds) = cursor.date_time()?;
+        cursor.end()?;
+        Ok(Self { seconds, nanoseconds })
     }
 }
 
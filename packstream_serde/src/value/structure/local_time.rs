@@ -1,3 +1,4 @@
+use crate::prelude::*;
 use super::super::Value;
 use crate::{
     constants::STRUCTURE_NAME,
@@ -9,13 +10,47 @@ use serde::{
     de, forward_to_deserialize_any,
     ser::{self, SerializeTupleStruct},
 };
-use std::fmt;
+use super::temporal::{self, Cursor};
+use core::{fmt, str::FromStr};
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct LocalTime {
     pub nanoseconds: i64,
 }
 
+impl LocalTime {
+    /// Creates time from the nanoseconds since midnight.
+    pub fn new(nanoseconds: i64) -> PackstreamResult<Self> {
+        temporal::validate_nano_of_day(nanoseconds)?;
+        Ok(Self { nanoseconds })
+    }
+
+    pub fn from_hms_nano(hour: u32, minute: u32, second: u32, nanosecond: u32) -> PackstreamResult<Self> {
+        if hour > 23 || minute > 59 || second > 59 || i64::from(nanosecond) >= temporal::NANOS_PER_SECOND {
+            return Err(temporal::error(
+                "LocalTime",
+                format!("{}:{}:{}.{}", hour, minute, second, nanosecond),
+            ));
+        }
+
+        let seconds = i64::from(hour) * 3_600 + i64::from(minute) * 60 + i64::from(second);
+        Ok(Self {
+            nanoseconds: seconds * temporal::NANOS_PER_SECOND + i64::from(nanosecond),
+        })
+    }
+
+    /// Returns the hour, minute, second and nanosecond of the time.
+    pub fn to_hms_nano(&self) -> (u32, u32, u32, u32) {
+        let seconds = self.nanoseconds / temporal::NANOS_PER_SECOND;
+        (
+            (seconds / 3_600) as u32,
+            (seconds / 60 % 60) as u32,
+            (seconds % 60) as u32,
+            (self.nanoseconds % temporal::NANOS_PER_SECOND) as u32,
+        )
+    }
+}
+
 impl PackstreamStructure for LocalTime {
     const SIG: u8 = structure::LOCAL_TIME;
     const LEN: u8 = 0x01;
@@ -32,9 +67,18 @@ impl PackstreamStructure for LocalTime {
 
 impl fmt::Display for LocalTime {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("LocalTime")
-            .field("nanoseconds", &self.nanoseconds)
-            .finish()
+        temporal::write_time(f, self.nanoseconds)
+    }
+}
+
+impl FromStr for LocalTime {
+    type Err = PackstreamError;
+
+    fn from_str(text: &str) -> PackstreamResult<Self> {
+        let mut cursor = Cursor::new("LocalTime", text);
+        let nanoseconds = cursor.time()?;
+        cursor.end()?;
+        Ok(Self { nanoseconds })
     }
 }
 
@@ -1,3 +1,4 @@
+use crate::prelude::*;
 use super::super::Value;
 use super::super::display;
 use crate::{
@@ -9,13 +10,14 @@ use serde::{
     de, forward_to_deserialize_any,
     ser::{self, SerializeTupleStruct},
 };
-use std::{collections::HashMap, fmt};
+use crate::Map;
+use core::fmt;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Node {
     pub id: i64,
     pub labels: Vec<String>,
-    pub properties: HashMap<String, Value>,
+    pub properties: Map<String, Value>,
 }
 
 impl PackstreamStructure for Node {
@@ -23,7 +25,7 @@ impl PackstreamStructure for Node {
     const LEN: u8 = 0x03;
     const SERIALIZE_LEN: usize = serialize_length!(Self::SIG, Self::LEN);
 
-    type Fields = (i64, Vec<String>, HashMap<String, Value>);
+    type Fields = (i64, Vec<String>, Map<String, Value>);
 
     fn into_value(self) -> Value {
         value_map! {
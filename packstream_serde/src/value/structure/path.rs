@@ -1,3 +1,4 @@
+use crate::prelude::*;
 use super::super::Value;
 use super::{Node, UnboundRelationship};
 use crate::{
@@ -9,9 +10,9 @@ use serde::{
     de, forward_to_deserialize_any,
     ser::{self, SerializeTupleStruct},
 };
-use std::fmt;
+use core::fmt;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Path {
     pub nodes: Vec<Node>,
     pub relationships: Vec<UnboundRelationship>,
@@ -1,3 +1,4 @@
+use crate::prelude::*;
 use super::super::Value;
 use crate::{
     constants::{structure, STRUCTURE_NAME},
@@ -8,15 +9,56 @@ use serde::{
     de, forward_to_deserialize_any,
     ser::{self, SerializeTupleStruct},
 };
-use std::fmt;
+use super::spatial::{self, Crs};
+use core::fmt;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Point2D {
     pub srid: i64,
     pub x: f64,
     pub y: f64,
 }
 
+impl Point2D {
+    /// Creates point validating that the CRS is 2D and geographic coordinates are in range.
+    pub fn new(crs: Crs, x: f64, y: f64) -> PackstreamResult<Self> {
+        spatial::crs(crs.srid(), 2)?;
+        spatial::validate(crs, &[x, y])?;
+        Ok(Self { srid: crs.srid(), x, y })
+    }
+
+    /// Returns the CRS of the point, fails for unknown SRID or 3D CRS.
+    pub fn crs(&self) -> PackstreamResult<Crs> {
+        spatial::crs(self.srid, 2)
+    }
+
+    /// Returns the distance between points like the Cypher `point.distance` function,
+    /// in meters for geographic points. Returns `None` if points have different or unknown CRS.
+    ///
+    /// Requires the `std` feature for floating point functions.
+    #[cfg(feature = "std")]
+    pub fn distance(&self, other: &Point2D) -> Option<f64> {
+        match (self.crs(), other.crs()) {
+            (Ok(a), Ok(b)) if a == b => Some(spatial::distance(a, &[self.x, self.y], &[other.x, other.y])),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "geo-types")]
+impl From<Point2D> for geo_types::Point<f64> {
+    fn from(point: Point2D) -> Self {
+        geo_types::Point::new(point.x, point.y)
+    }
+}
+
+#[cfg(feature = "geo-types")]
+impl Point2D {
+    pub fn from_geo(point: geo_types::Point<f64>, crs: Crs) -> PackstreamResult<Self> {
+        Self::new(crs, point.x(), point.y())
+    }
+}
+
 impl PackstreamStructure for Point2D {
     const SIG: u8 = structure::POINT_2D;
     const LEN: u8 = 0x03;
@@ -1,3 +1,4 @@
+use crate::prelude::*;
 use super::super::Value;
 use crate::{
     constants::{structure, STRUCTURE_NAME},
@@ -8,9 +9,10 @@ use serde::{
     de, forward_to_deserialize_any,
     ser::{self, SerializeTupleStruct},
 };
-use std::fmt;
+use super::spatial::{self, Crs};
+use core::fmt;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Point3D {
     pub srid: i64,
     pub x: f64,
@@ -18,6 +20,36 @@ pub struct Point3D {
     pub z: f64,
 }
 
+impl Point3D {
+    /// Creates point validating that the CRS is 3D and geographic coordinates are in range.
+    pub fn new(crs: Crs, x: f64, y: f64, z: f64) -> PackstreamResult<Self> {
+        spatial::crs(crs.srid(), 3)?;
+        spatial::validate(crs, &[x, y, z])?;
+        Ok(Self { srid: crs.srid(), x, y, z })
+    }
+
+    /// Returns the CRS of the point, fails for unknown SRID or 2D CRS.
+    pub fn crs(&self) -> PackstreamResult<Crs> {
+        spatial::crs(self.srid, 3)
+    }
+
+    /// Returns the distance between points like the Cypher `point.distance` function,
+    /// in meters for geographic points. Returns `None` if points have different or unknown CRS.
+    ///
+    /// Requires the `std` feature for floating point functions.
+    #[cfg(feature = "std")]
+    pub fn distance(&self, other: &Point3D) -> Option<f64> {
+        match (self.crs(), other.crs()) {
+            (Ok(a), Ok(b)) if a == b => Some(spatial::distance(
+                a,
+                &[self.x, self.y, self.z],
+                &[other.x, other.y, other.z],
+            )),
+            _ => None,
+        }
+    }
+}
+
 impl PackstreamStructure for Point3D {
     const SIG: u8 = structure::POINT_3D;
     const LEN: u8 = 0x04;
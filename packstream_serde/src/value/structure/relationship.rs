@@ -1,3 +1,4 @@
+use crate::prelude::*;
 use super::super::Value;
 use super::super::display;
 use crate::{
@@ -9,15 +10,16 @@ use serde::{
     de, forward_to_deserialize_any,
     ser::{self, SerializeTupleStruct},
 };
-use std::{collections::HashMap, fmt};
+use crate::Map;
+use core::fmt;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Relationship {
     pub id: i64,
     pub start_node_id: i64,
     pub end_node_id: i64,
     pub r#type: String,
-    pub properties: HashMap<String, Value>,
+    pub properties: Map<String, Value>,
 }
 
 impl PackstreamStructure for Relationship {
@@ -25,7 +27,7 @@ impl PackstreamStructure for Relationship {
     const LEN: u8 = 0x05;
     const SERIALIZE_LEN: usize = serialize_length!(Self::SIG, Self::LEN);
 
-    type Fields = (i64, i64, i64, String, HashMap<String, Value>);
+    type Fields = (i64, i64, i64, String, Map<String, Value>);
 
     fn into_value(self) -> Value {
         value_map! {
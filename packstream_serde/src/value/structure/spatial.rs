@@ -0,0 +1,131 @@
+use crate::prelude::*;
+use crate::error::{PackstreamError, PackstreamResult};
+use core::fmt;
+
+/// Equatorial Earth radius, the value used by the Cypher `point.distance` function.
+#[cfg(feature = "std")]
+const EARTH_RADIUS_METERS: f64 = 6_378_140.0;
+
+/// Coordinate reference systems supported by Neo4j.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Crs {
+    /// Geographic 2D, `x` is longitude and `y` is latitude in degrees.
+    Wgs84,
+    /// Geographic 3D, as [`Crs::Wgs84`] with `z` height in meters.
+    Wgs84_3D,
+    Cartesian,
+    Cartesian3D,
+}
+
+impl Crs {
+    pub fn from_srid(srid: i64) -> Option<Self> {
+        match srid {
+            4326 => Some(Self::Wgs84),
+            4979 => Some(Self::Wgs84_3D),
+            7203 => Some(Self::Cartesian),
+            9157 => Some(Self::Cartesian3D),
+            _ => None,
+        }
+    }
+
+    pub fn srid(self) -> i64 {
+        match self {
+            Self::Wgs84 => 4326,
+            Self::Wgs84_3D => 4979,
+            Self::Cartesian => 7203,
+            Self::Cartesian3D => 9157,
+        }
+    }
+
+    /// Returns the name used by Cypher, e.g. `wgs-84-3d`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Wgs84 => "wgs-84",
+            Self::Wgs84_3D => "wgs-84-3d",
+            Self::Cartesian => "cartesian",
+            Self::Cartesian3D => "cartesian-3d",
+        }
+    }
+
+    pub fn dimension(self) -> usize {
+        match self {
+            Self::Wgs84 | Self::Cartesian => 2,
+            Self::Wgs84_3D | Self::Cartesian3D => 3,
+        }
+    }
+
+    pub fn is_geographic(self) -> bool {
+        matches!(self, Self::Wgs84 | Self::Wgs84_3D)
+    }
+}
+
+impl fmt::Display for Crs {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Returns the CRS of the point with given SRID and dimension.
+pub(super) fn crs(srid: i64, dimension: usize) -> PackstreamResult<Crs> {
+    match Crs::from_srid(srid) {
+        Some(crs) if crs.dimension() == dimension => Ok(crs),
+        Some(crs) => Err(PackstreamError::create(format!(
+            "CRS {} requires {} dimensions, got {} instead",
+            crs,
+            crs.dimension(),
+            dimension
+        ))),
+        None => Err(PackstreamError::create(format!("Unknown CRS with SRID {}", srid))),
+    }
+}
+
+/// Validates coordinates of the point in the CRS.
+pub(super) fn validate(crs: Crs, coordinates: &[f64]) -> PackstreamResult<()> {
+    if coordinates.iter().any(|c| !c.is_finite()) {
+        return Err(PackstreamError::create("Point coordinates must be finite"));
+    }
+
+    if crs.is_geographic() {
+        if !(-180.0..=180.0).contains(&coordinates[0]) {
+            return Err(PackstreamError::create(format!(
+                "Longitude must be in range -180..=180, got {} instead",
+                coordinates[0]
+            )));
+        }
+        if !(-90.0..=90.0).contains(&coordinates[1]) {
+            return Err(PackstreamError::create(format!(
+                "Latitude must be in range -90..=90, got {} instead",
+                coordinates[1]
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the distance between points in the same CRS following the Cypher `point.distance`.
+///
+/// Geographic distance is computed with the haversine formula in meters, height difference
+/// of 3D points is then applied with the Pythagorean theorem.
+#[cfg(feature = "std")]
+pub(super) fn distance(crs: Crs, a: &[f64], b: &[f64]) -> f64 {
+    if !crs.is_geographic() {
+        return a
+            .iter()
+            .zip(b.iter())
+            .map(|(a, b)| (a - b).powi(2))
+            .sum::<f64>()
+            .sqrt();
+    }
+
+    let (lat1, lat2) = (a[1].to_radians(), b[1].to_radians());
+    let dlat = lat2 - lat1;
+    let dlon = (b[0] - a[0]).to_radians();
+    let h = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    let distance = EARTH_RADIUS_METERS * 2.0 * h.sqrt().atan2((1.0 - h).sqrt());
+
+    match (a.get(2), b.get(2)) {
+        (Some(z1), Some(z2)) => (distance.powi(2) + (z2 - z1).powi(2)).sqrt(),
+        _ => distance,
+    }
+}
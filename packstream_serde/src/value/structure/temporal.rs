@@ -0,0 +1,318 @@
+use crate::prelude::*;
+use crate::error::{PackstreamError, PackstreamResult};
+use core::fmt;
+
+pub(super) const NANOS_PER_SECOND: i64 = 1_000_000_000;
+pub(super) const SECONDS_PER_DAY: i64 = 86_400;
+pub(super) const NANOS_PER_DAY: i64 = SECONDS_PER_DAY * NANOS_PER_SECOND;
+pub(super) const MAX_OFFSET_SECONDS: i64 = 18 * 3_600;
+pub(super) const MIN_YEAR: i64 = -999_999_999;
+pub(super) const MAX_YEAR: i64 = 999_999_999;
+
+pub(super) fn error(kind: &str, value: impl fmt::Display) -> PackstreamError {
+    PackstreamError::create(format!("Invalid {} {}", kind, value))
+}
+
+pub(super) fn overflow(kind: &str) -> PackstreamError {
+    PackstreamError::create(format!("{} arithmetic overflow", kind))
+}
+
+pub(super) fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+pub(super) fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the number of days since the Unix epoch for the proleptic Gregorian calendar date.
+pub(super) fn days_from_ymd(year: i64, month: u32, day: u32) -> PackstreamResult<i64> {
+    if !(MIN_YEAR..=MAX_YEAR).contains(&year)
+        || !(1..=12).contains(&month)
+        || day < 1
+        || day > days_in_month(year, month)
+    {
+        return Err(error("date", format!("{}-{}-{}", year, month, day)));
+    }
+
+    // http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    Ok(era * 146_097 + day_of_era - 719_468)
+}
+
+/// Returns the proleptic Gregorian calendar date for the number of days since the Unix epoch.
+pub(super) fn ymd_from_days(days: i64) -> (i64, u32, u32) {
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    // Computed in i128 as decoded dates are not validated and may hold any day count.
+    let days = i128::from(days) + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = year_of_era + era * 400 + i128::from(month <= 2);
+
+    // Fits as the year is smaller than the day count.
+    (year as i64, month, day)
+}
+
+/// Adds months to the date clamping the day to the last day of the resulting month.
+pub(super) fn add_months(days: i64, months: i64) -> PackstreamResult<i64> {
+    let (year, month, day) = ymd_from_days(days);
+    let total = (year * 12 + i64::from(month) - 1)
+        .checked_add(months)
+        .ok_or_else(|| overflow("Date"))?;
+    let year = total.div_euclid(12);
+    let month = total.rem_euclid(12) as u32 + 1;
+
+    days_from_ymd(year, month, day.min(days_in_month(year, month)))
+}
+
+pub(super) fn validate_nanoseconds(nanoseconds: i64) -> PackstreamResult<()> {
+    if (0..NANOS_PER_SECOND).contains(&nanoseconds) {
+        Ok(())
+    } else {
+        Err(error("nanoseconds", nanoseconds))
+    }
+}
+
+pub(super) fn validate_nano_of_day(nanoseconds: i64) -> PackstreamResult<()> {
+    if (0..NANOS_PER_DAY).contains(&nanoseconds) {
+        Ok(())
+    } else {
+        Err(error("nanosecond of day", nanoseconds))
+    }
+}
+
+pub(super) fn validate_offset(seconds: i64) -> PackstreamResult<()> {
+    if (-MAX_OFFSET_SECONDS..=MAX_OFFSET_SECONDS).contains(&seconds) {
+        Ok(())
+    } else {
+        Err(error("time zone offset", seconds))
+    }
+}
+
+pub(super) fn validate_days(days: i64) -> PackstreamResult<()> {
+    // Avoid overflow in the date algorithm for values far outside of the supported range.
+    const MAX_DAYS: i64 = (MAX_YEAR + 1) * 366;
+
+    if (-MAX_DAYS..=MAX_DAYS).contains(&days) && (MIN_YEAR..=MAX_YEAR).contains(&ymd_from_days(days).0) {
+        Ok(())
+    } else {
+        Err(error("days", days))
+    }
+}
+
+pub(super) fn validate_epoch_seconds(seconds: i64) -> PackstreamResult<()> {
+    validate_days(seconds.div_euclid(SECONDS_PER_DAY)).map_err(|_| error("seconds", seconds))
+}
+
+pub(super) fn write_date(f: &mut fmt::Formatter, days: i64) -> fmt::Result {
+    let (year, month, day) = ymd_from_days(days);
+    match year {
+        0..=9999 => write!(f, "{:04}-{:02}-{:02}", year, month, day),
+        _ => write!(f, "{:+05}-{:02}-{:02}", year, month, day),
+    }
+}
+
+pub(super) fn write_time(f: &mut fmt::Formatter, nano_of_day: i64) -> fmt::Result {
+    let seconds = nano_of_day / NANOS_PER_SECOND;
+    write!(f, "{:02}:{:02}:{:02}", seconds / 3_600, seconds / 60 % 60, seconds % 60)?;
+    write_fraction(f, nano_of_day % NANOS_PER_SECOND)
+}
+
+pub(super) fn write_fraction(f: &mut fmt::Formatter, nanoseconds: i64) -> fmt::Result {
+    if nanoseconds == 0 {
+        return Ok(());
+    }
+    let fraction = format!("{:09}", nanoseconds);
+    write!(f, ".{}", fraction.trim_end_matches('0'))
+}
+
+pub(super) fn write_offset(f: &mut fmt::Formatter, seconds: i64) -> fmt::Result {
+    if seconds == 0 {
+        return f.write_str("Z");
+    }
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = i128::from(seconds).abs();
+    write!(f, "{}{:02}:{:02}", sign, seconds / 3_600, seconds / 60 % 60)?;
+    if seconds % 60 != 0 {
+        write!(f, ":{:02}", seconds % 60)?;
+    }
+    Ok(())
+}
+
+/// Splits local seconds since the Unix epoch into days and nanosecond of the day.
+///
+/// Nanoseconds outside of the second, which decoded values may hold, carry over into the seconds.
+pub(super) fn split_epoch_seconds(seconds: i64, nanoseconds: i64) -> (i64, i64) {
+    let nanoseconds = i128::from(seconds) * i128::from(NANOS_PER_SECOND) + i128::from(nanoseconds);
+    (
+        nanoseconds.div_euclid(i128::from(NANOS_PER_DAY)) as i64,
+        nanoseconds.rem_euclid(i128::from(NANOS_PER_DAY)) as i64,
+    )
+}
+
+/// Parser of the ISO-8601 text representations.
+pub(super) struct Cursor<'a> {
+    kind: &'static str,
+    text: &'a str,
+    index: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub(super) fn new(kind: &'static str, text: &'a str) -> Self {
+        Self { kind, text, index: 0 }
+    }
+
+    pub(super) fn error(&self) -> PackstreamError {
+        error(self.kind, format!("'{}'", self.text))
+    }
+
+    pub(super) fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.index).copied()
+    }
+
+    pub(super) fn consume(&mut self, c: u8) -> bool {
+        if self.peek() == Some(c) {
+            self.index += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub(super) fn expect(&mut self, c: u8) -> PackstreamResult<()> {
+        if self.consume(c) {
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    pub(super) fn rest(&self) -> &'a str {
+        &self.text[self.index..]
+    }
+
+    /// Consumes the rest of the text.
+    pub(super) fn take_rest(&mut self) -> &'a str {
+        let rest = self.rest();
+        self.index = self.text.len();
+        rest
+    }
+
+    pub(super) fn end(&self) -> PackstreamResult<()> {
+        if self.index == self.text.len() {
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    /// Consumes at least `min` and at most `max` ASCII digits.
+    pub(super) fn digits(&mut self, min: usize, max: usize) -> PackstreamResult<(i64, usize)> {
+        let len = self.rest().bytes().take(max).take_while(u8::is_ascii_digit).count();
+        if len < min {
+            return Err(self.error());
+        }
+        let digits = &self.rest()[..len];
+        self.index += len;
+        digits.parse().map(|value| (value, len)).map_err(|_| self.error())
+    }
+
+    fn number(&mut self, len: usize) -> PackstreamResult<i64> {
+        self.digits(len, len).map(|(value, _)| value)
+    }
+
+    /// Consumes optional `.` followed by fraction of a second.
+    pub(super) fn fraction(&mut self) -> PackstreamResult<i64> {
+        if !self.consume(b'.') && !self.consume(b',') {
+            return Ok(0);
+        }
+        let (value, len) = self.digits(1, 9)?;
+        Ok(value * 10_i64.pow(9 - len as u32))
+    }
+
+    pub(super) fn date(&mut self) -> PackstreamResult<i64> {
+        let sign = if self.consume(b'-') {
+            -1
+        } else {
+            self.consume(b'+');
+            1
+        };
+        let (year, _) = self.digits(4, 9)?;
+        self.expect(b'-')?;
+        let month = self.number(2)?;
+        self.expect(b'-')?;
+        let day = self.number(2)?;
+
+        days_from_ymd(sign * year, month as u32, day as u32).map_err(|_| self.error())
+    }
+
+    pub(super) fn time(&mut self) -> PackstreamResult<i64> {
+        let hour = self.number(2)?;
+        self.expect(b':')?;
+        let minute = self.number(2)?;
+        let (second, nanosecond) = if self.consume(b':') {
+            (self.number(2)?, self.fraction()?)
+        } else {
+            (0, 0)
+        };
+
+        if hour > 23 || minute > 59 || second > 59 {
+            return Err(self.error());
+        }
+
+        Ok((hour * 3_600 + minute * 60 + second) * NANOS_PER_SECOND + nanosecond)
+    }
+
+    pub(super) fn offset(&mut self) -> PackstreamResult<i64> {
+        if self.consume(b'Z') {
+            return Ok(0);
+        }
+        let sign = match self.peek() {
+            Some(b'+') => 1,
+            Some(b'-') => -1,
+            _ => return Err(self.error()),
+        };
+        self.index += 1;
+
+        let hours = self.number(2)?;
+        self.expect(b':')?;
+        let minutes = self.number(2)?;
+        let seconds = if self.consume(b':') { self.number(2)? } else { 0 };
+
+        if minutes > 59 || seconds > 59 {
+            return Err(self.error());
+        }
+
+        let offset = sign * (hours * 3_600 + minutes * 60 + seconds);
+        validate_offset(offset).map_err(|_| self.error())?;
+        Ok(offset)
+    }
+
+    /// Parses date and time separated by `T` into local seconds and nanoseconds.
+    pub(super) fn date_time(&mut self) -> PackstreamResult<(i64, i64)> {
+        let days = self.date()?;
+        self.expect(b'T')?;
+        let nano_of_day = self.time()?;
+
+        Ok((
+            days * SECONDS_PER_DAY + nano_of_day / NANOS_PER_SECOND,
+            nano_of_day % NANOS_PER_SECOND,
+        ))
+    }
+}
@@ -1,3 +1,4 @@
+use crate::prelude::*;
 use super::super::Value;
 use crate::{
     constants::STRUCTURE_NAME,
@@ -9,14 +10,27 @@ use serde::{
     de, forward_to_deserialize_any,
     ser::{self, SerializeTupleStruct},
 };
-use std::fmt;
+use super::temporal::{self, Cursor};
+use core::{fmt, str::FromStr};
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Time {
     pub nanoseconds: i64,
     pub tz_offset_seconds: i64,
 }
 
+impl Time {
+    /// Creates time from the nanoseconds since midnight and the time zone offset.
+    pub fn new(nanoseconds: i64, tz_offset_seconds: i64) -> PackstreamResult<Self> {
+        temporal::validate_nano_of_day(nanoseconds)?;
+        temporal::validate_offset(tz_offset_seconds)?;
+        Ok(Self {
+            nanoseconds,
+            tz_offset_seconds,
+        })
+    }
+}
+
 impl PackstreamStructure for Time {
     const SIG: u8 = structure::TIME;
     const LEN: u8 = 0x02;
@@ -34,10 +48,23 @@ impl PackstreamStructure for Time {
 
 impl fmt::Display for Time {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("Time")
-            .field("nanoseconds", &self.nanoseconds)
-            .field("tz_offset_seconds", &self.tz_offset_seconds)
-            .finish()
+        temporal::write_time(f, self.nanoseconds)?;
+        temporal::write_offset(f, self.tz_offset_seconds)
+    }
+}
+
+impl FromStr for Time {
+    type Err = PackstreamError;
+
+    fn from_str(text: &str) -> PackstreamResult<Self> {
+        let mut cursor = Cursor::new("Time", text);
+        let nanoseconds = cursor.time()?;
+        let tz_offset_seconds = cursor.offset()?;
+        cursor.end()?;
+        Ok(Self {
+            nanoseconds,
+            tz_offset_seconds,
+        })
     }
 }
 
@@ -1,3 +1,4 @@
+use crate::prelude::*;
 use super::super::Value;
 use crate::{
     constants::{structure, STRUCTURE_NAME},
@@ -8,13 +9,14 @@ use serde::{
     de, forward_to_deserialize_any,
     ser::{self, SerializeTupleStruct},
 };
-use std::{collections::HashMap, fmt};
+use crate::Map;
+use core::fmt;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct UnboundRelationship {
     pub id: i64,
     pub r#type: String,
-    pub properties: HashMap<String, Value>,
+    pub properties: Map<String, Value>,
 }
 
 impl PackstreamStructure for UnboundRelationship {
@@ -22,7 +24,7 @@ impl PackstreamStructure for UnboundRelationship {
     const LEN: u8 = 0x03;
     const SERIALIZE_LEN: usize = serialize_length!(Self::SIG, Self::LEN);
 
-    type Fields = (i64, String, HashMap<String, Value>);
+    type Fields = (i64, String, Map<String, Value>);
 
     fn into_value(self) -> Value {
         value_map! {
@@ -0,0 +1,398 @@
+use crate::prelude::*;
+use super::{Structure, Value};
+use crate::constants::structure;
+use crate::error::{PackstreamError, PackstreamResult};
+use crate::marker::Marker;
+use crate::read::{ByteReader, Unpacker};
+use serde_bytes::ByteBuf;
+use crate::Map;
+use core::fmt::Write;
+use core::str::FromStr;
+
+const STRUCTURES: &[(&str, u8)] = &[
+    ("Node", structure::NODE),
+    ("Path", structure::PATH),
+    ("Relationship", structure::RELATIONSHIP),
+    ("UnboundRelationship", structure::UNBOUND_RELATIONSHIP),
+    ("Date", structure::DATE),
+    ("Time", structure::TIME),
+    ("LocalTime", structure::LOCAL_TIME),
+    ("DateTime", structure::DATE_TIME),
+    ("DateTimeZoneId", structure::DATE_TIME_ZONE_ID),
+    ("LocalDateTime", structure::LOCAL_DATE_TIME),
+    ("Duration", structure::DURATION),
+    ("Point2D", structure::POINT_2D),
+    ("Point3D", structure::POINT_3D),
+];
+
+/// Parses the text notation into a [`Value`].
+///
+/// ```text
+/// {name: "x", tags: [1, 2.5, null], n: Node(1, ["A"], {})}
+/// ```
+///
+/// * `null`, `true` and `false`
+/// * integers `-1`, `42` and floats `2.5`, `1e-3`, `NaN`, `inf`, `-inf`
+/// * strings in double quotes with `\"`, `\\`, `\n`, `\r`, `\t` and `\u{..}` escapes
+/// * lists `[..]` and maps `{key: value, "any key": value}`
+/// * bytes `Bytes([0, 255])`
+/// * structures written as `Name(field, ..)`, where `Name` is one of the [`Structure`] variants
+///   and fields are listed in the packstream order, e.g. `Point2D(7203, 1.0, 2.0)`
+pub fn from_text(text: &str) -> PackstreamResult<Value> {
+    let mut parser = Parser { text, index: 0 };
+    let value = parser.parse_value()?;
+    parser.skip_whitespace();
+
+    if parser.index != text.len() {
+        return Err(parser.error("end of input"));
+    }
+
+    Ok(value)
+}
+
+/// Prints the [`Value`] in the text notation accepted by [`from_text`].
+///
+/// Map keys are printed in ascending order.
+pub fn to_text(value: &Value) -> String {
+    let mut output = String::new();
+    print_value(value, &mut output);
+    output
+}
+
+impl FromStr for Value {
+    type Err = PackstreamError;
+
+    fn from_str(text: &str) -> PackstreamResult<Self> {
+        from_text(text)
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    index: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, expected: &str) -> PackstreamError {
+        let found = match self.peek() {
+            Some(c) => format!("'{}'", c),
+            None => String::from("end of input"),
+        };
+        PackstreamError::create(format!(
+            "Expected {} at position {}, found {} instead",
+            expected, self.index, found
+        ))
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.text[self.index..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.index += rest.len() - rest.trim_start().len();
+    }
+
+    fn consume(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.index += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> PackstreamResult<()> {
+        if self.consume(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("'{}'", c)))
+        }
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.index += len;
+        &rest[..len]
+    }
+
+    fn parse_value(&mut self) -> PackstreamResult<Value> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('[') => self.parse_list().map(Value::List),
+            Some('{') => self.parse_map().map(Value::Map),
+            Some('"') => self.parse_string().map(Value::String),
+            Some(c) if c == '-' || c == '+' || c.is_ascii_digit() => self.parse_number(),
+            Some(c) if c.is_alphabetic() || c == '_' => self.parse_word(),
+            _ => Err(self.error("value")),
+        }
+    }
+
+    fn parse_items<T>(&mut self, end: char, mut item: impl FnMut(&mut Self) -> PackstreamResult<T>) -> PackstreamResult<Vec<T>> {
+        let mut items = Vec::new();
+        if self.consume(end) {
+            return Ok(items);
+        }
+
+        loop {
+            items.push(item(self)?);
+            if self.consume(end) {
+                return Ok(items);
+            }
+            self.expect(',')?;
+            // Allow trailing comma.
+            if self.consume(end) {
+                return Ok(items);
+            }
+        }
+    }
+
+    fn parse_list(&mut self) -> PackstreamResult<Vec<Value>> {
+        self.expect('[')?;
+        self.parse_items(']', Self::parse_value)
+    }
+
+    fn parse_map(&mut self) -> PackstreamResult<Map<String, Value>> {
+        self.expect('{')?;
+        let entries = self.parse_items('}', |parser| {
+            parser.skip_whitespace();
+            let key = if parser.peek() == Some('"') {
+                parser.parse_string()?
+            } else {
+                let key = parser.take_while(|c| c.is_alphanumeric() || c == '_');
+                if key.is_empty() {
+                    return Err(parser.error("map key"));
+                }
+                String::from(key)
+            };
+            parser.expect(':')?;
+            Ok((key, parser.parse_value()?))
+        })?;
+
+        let len = entries.len();
+        let map: Map<String, Value> = entries.into_iter().collect();
+        if map.len() != len {
+            return Err(PackstreamError::create("Map cannot contain duplicate keys"));
+        }
+
+        Ok(map)
+    }
+
+    fn parse_string(&mut self) -> PackstreamResult<String> {
+        self.expect('"')?;
+        let mut output = String::new();
+
+        loop {
+            let c = self.peek().ok_or_else(|| self.error("'\"'"))?;
+            self.index += c.len_utf8();
+
+            match c {
+                '"' => return Ok(output),
+                '\\' => {
+                    let escaped = self.peek().ok_or_else(|| self.error("escape sequence"))?;
+                    self.index += escaped.len_utf8();
+                    match escaped {
+                        '"' => output.push('"'),
+                        '\\' => output.push('\\'),
+                        'n' => output.push('\n'),
+                        'r' => output.push('\r'),
+                        't' => output.push('\t'),
+                        'u' => {
+                            self.expect('{')?;
+                            let hex = self.take_while(|c| c.is_ascii_hexdigit());
+                            let c = u32::from_str_radix(hex, 16)
+                                .ok()
+                                .and_then(core::char::from_u32)
+                                .ok_or_else(|| self.error("unicode code point"))?;
+                            output.push(c);
+                            self.expect('}')?;
+                        }
+                        _ => {
+                            self.index -= escaped.len_utf8();
+                            return Err(self.error("escape sequence"));
+                        }
+                    }
+                }
+                c => output.push(c),
+            }
+        }
+    }
+
+    fn parse_number(&mut self) -> PackstreamResult<Value> {
+        let start = self.index;
+        let sign = self.take_while(|c| c == '-' || c == '+');
+        if self.rest().starts_with("inf") {
+            self.index += 3;
+            return match sign {
+                "-" => Ok(Value::F64(f64::NEG_INFINITY)),
+                _ => Ok(Value::F64(f64::INFINITY)),
+            };
+        }
+
+        let number = self.take_while(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '+');
+        let text = &self.text[start..self.index];
+
+        if number.contains(['.', 'e', 'E']) {
+            text.parse().map(Value::F64).map_err(|_| self.invalid_number(start))
+        } else {
+            text.parse().map(Value::I64).map_err(|_| self.invalid_number(start))
+        }
+    }
+
+    fn invalid_number(&mut self, start: usize) -> PackstreamError {
+        self.index = start;
+        self.error("number")
+    }
+
+    fn parse_word(&mut self) -> PackstreamResult<Value> {
+        let start = self.index;
+        let word = self.take_while(|c| c.is_alphanumeric() || c == '_');
+
+        match word {
+            "null" => return Ok(Value::Null),
+            "true" => return Ok(Value::Bool(true)),
+            "false" => return Ok(Value::Bool(false)),
+            "NaN" => return Ok(Value::F64(f64::NAN)),
+            "inf" => return Ok(Value::F64(f64::INFINITY)),
+            _ => {}
+        }
+
+        if !self.consume('(') {
+            self.index = start;
+            return Err(self.error("value"));
+        }
+
+        let fields = self.parse_items(')', Self::parse_value)?;
+
+        if word == "Bytes" {
+            return match fields.as_slice() {
+                [Value::List(bytes)] => bytes
+                    .iter()
+                    .map(|byte| match byte {
+                        Value::I64(byte @ 0..=255) => Ok(*byte as u8),
+                        _ => Err(PackstreamError::create("Bytes must contain integers in range 0..=255")),
+                    })
+                    .collect::<PackstreamResult<Vec<u8>>>()
+                    .map(|bytes| Value::Bytes(ByteBuf::from(bytes))),
+                _ => Err(PackstreamError::create("Bytes expects a single list of integers")),
+            };
+        }
+
+        let signature = match STRUCTURES.iter().find(|(name, _)| *name == word) {
+            Some((_, signature)) => *signature,
+            None => {
+                self.index = start;
+                return Err(self.error("structure name"));
+            }
+        };
+
+        // Encode the structure and decode it with the structure deserializer,
+        // so that the fields are validated in the same way as packstream bytes.
+        let mut bytes = Vec::new();
+        Marker::Struct(fields.len()).append_to_vec(&mut bytes)?;
+        bytes.push(signature);
+        for field in fields.iter() {
+            bytes.extend(crate::to_bytes(field)?);
+        }
+
+        crate::from_bytes::<Structure>(&bytes)
+            .map(Value::Structure)
+            .map_err(|e| PackstreamError::create(format!("Invalid {} structure: {}", word, e)))
+    }
+}
+
+fn print_value(value: &Value, output: &mut String) {
+    match value {
+        Value::Null => output.push_str("null"),
+        Value::Bool(v) => output.push_str(if *v { "true" } else { "false" }),
+        Value::I64(v) => write!(output, "{}", v).unwrap(),
+        Value::F64(v) => write!(output, "{:?}", v).unwrap(),
+        Value::String(v) => print_string(v, output),
+        Value::List(v) => print_list(v.iter(), output),
+        Value::Map(v) => {
+            let mut entries: Vec<_> = v.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+
+            output.push('{');
+            for (index, (key, value)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    output.push_str(", ");
+                }
+                if !key.is_empty() && key.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                    output.push_str(key);
+                } else {
+                    print_string(key, output);
+                }
+                output.push_str(": ");
+                print_value(value, output);
+            }
+            output.push('}');
+        }
+        Value::Bytes(v) => {
+            output.push_str("Bytes(");
+            print_list(v.iter().map(|byte| Value::I64(i64::from(*byte))).collect::<Vec<_>>().iter(), output);
+            output.push(')');
+        }
+        Value::Structure(v) => print_structure(v, output),
+    }
+}
+
+fn print_list<'a>(values: impl Iterator<Item = &'a Value>, output: &mut String) {
+    output.push('[');
+    for (index, value) in values.enumerate() {
+        if index > 0 {
+            output.push_str(", ");
+        }
+        print_value(value, output);
+    }
+    output.push(']');
+}
+
+fn print_string(value: &str, output: &mut String) {
+    output.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if c.is_control() => write!(output, "\\u{{{:x}}}", c as u32).unwrap(),
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+}
+
+fn print_structure(value: &Structure, output: &mut String) {
+    // Fields are printed in the packstream order, which is read back from the encoded structure.
+    let bytes = crate::to_bytes(value).expect("Structure to be serializable");
+    let mut reader = ByteReader::new(&bytes);
+    let len = match reader.consume_marker() {
+        Ok(Marker::Struct(len)) => len,
+        _ => unreachable!("Structure always serializes into Marker::Struct."),
+    };
+    let signature = reader.consume_bytes(1).expect("Structure to have a signature")[0];
+    let name = STRUCTURES
+        .iter()
+        .find(|(_, sig)| *sig == signature)
+        .map(|(name, _)| *name)
+        .expect("Structure signature to be known");
+
+    output.push_str(name);
+    output.push('(');
+    for index in 0..len {
+        if index > 0 {
+            output.push_str(", ");
+        }
+        let field = reader.skip_value().expect("Structure field to be encoded");
+        print_value(&crate::from_bytes(field).expect("Structure field to be decodable"), output);
+    }
+    output.push(')');
+}
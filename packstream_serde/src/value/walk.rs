@@ -0,0 +1,186 @@
+use crate::prelude::*;
+use super::structure::{Node, Path, Relationship, Structure, UnboundRelationship};
+use super::Value;
+use crate::Map;
+
+/// Callbacks invoked by [`Value::walk`] for every visited item.
+///
+/// All methods have empty default implementations, so only the interesting
+/// ones need to be implemented. Any `FnMut(&Value)` closure is a visitor too.
+pub trait ValueVisitor<'a> {
+    /// Called for every value, including property values of graph structures.
+    fn visit_value(&mut self, _value: &'a Value) {}
+
+    /// Called for every node, including the nodes of a path.
+    fn visit_node(&mut self, _node: &'a Node) {}
+
+    fn visit_relationship(&mut self, _relationship: &'a Relationship) {}
+
+    /// Called for every unbound relationship, including the relationships of a path.
+    fn visit_unbound_relationship(&mut self, _relationship: &'a UnboundRelationship) {}
+}
+
+impl<'a, F> ValueVisitor<'a> for F
+where
+    F: FnMut(&'a Value),
+{
+    fn visit_value(&mut self, value: &'a Value) {
+        self(value)
+    }
+}
+
+impl Value {
+    /// Visits the value and all of its descendants in depth-first order.
+    ///
+    /// Lists, maps, `Node`, `Relationship` and `UnboundRelationship` properties and
+    /// `Path` members are traversed. Parents are visited before their children.
+    pub fn walk<'a, V: ValueVisitor<'a>>(&'a self, visitor: &mut V) {
+        visitor.visit_value(self);
+
+        match self {
+            Self::List(list) => list.iter().for_each(|value| value.walk(visitor)),
+            Self::Map(map) => walk_map(map, visitor),
+            Self::Structure(structure) => walk_structure(structure, visitor),
+            _ => {}
+        }
+    }
+
+    /// Rebuilds the value by applying `f` to every value in the tree.
+    ///
+    /// Children are transformed before their parents, so `f` receives lists, maps
+    /// and structures with already transformed items.
+    pub fn map<F: FnMut(Value) -> Value>(self, mut f: F) -> Value {
+        self.map_with(&mut f)
+    }
+
+    fn map_with<F: FnMut(Value) -> Value>(self, f: &mut F) -> Value {
+        let value = match self {
+            Self::List(list) => Self::List(list.into_iter().map(|value| value.map_with(f)).collect()),
+            Self::Map(map) => Self::Map(map_properties(map, f)),
+            Self::Structure(structure) => Self::Structure(map_structure(structure, f)),
+            value => value,
+        };
+
+        f(value)
+    }
+
+    /// Returns all nodes found in the value, including the nodes of paths.
+    pub fn nodes(&self) -> Vec<&Node> {
+        struct Nodes<'a>(Vec<&'a Node>);
+
+        impl<'a> ValueVisitor<'a> for Nodes<'a> {
+            fn visit_node(&mut self, node: &'a Node) {
+                self.0.push(node);
+            }
+        }
+
+        let mut nodes = Nodes(Vec::new());
+        self.walk(&mut nodes);
+        nodes.0
+    }
+
+    /// Returns all relationships found in the value.
+    ///
+    /// Relationships of paths are unbound, use [`Value::unbound_relationships`] to collect them.
+    pub fn relationships(&self) -> Vec<&Relationship> {
+        struct Relationships<'a>(Vec<&'a Relationship>);
+
+        impl<'a> ValueVisitor<'a> for Relationships<'a> {
+            fn visit_relationship(&mut self, relationship: &'a Relationship) {
+                self.0.push(relationship);
+            }
+        }
+
+        let mut relationships = Relationships(Vec::new());
+        self.walk(&mut relationships);
+        relationships.0
+    }
+
+    /// Returns all unbound relationships found in the value, including the relationships of paths.
+    pub fn unbound_relationships(&self) -> Vec<&UnboundRelationship> {
+        struct UnboundRelationships<'a>(Vec<&'a UnboundRelationship>);
+
+        impl<'a> ValueVisitor<'a> for UnboundRelationships<'a> {
+            fn visit_unbound_relationship(&mut self, relationship: &'a UnboundRelationship) {
+                self.0.push(relationship);
+            }
+        }
+
+        let mut relationships = UnboundRelationships(Vec::new());
+        self.walk(&mut relationships);
+        relationships.0
+    }
+}
+
+fn walk_map<'a, V: ValueVisitor<'a>>(map: &'a Map<String, Value>, visitor: &mut V) {
+    map.values().for_each(|value| value.walk(visitor));
+}
+
+fn walk_node<'a, V: ValueVisitor<'a>>(node: &'a Node, visitor: &mut V) {
+    visitor.visit_node(node);
+    walk_map(&node.properties, visitor);
+}
+
+fn walk_unbound_relationship<'a, V: ValueVisitor<'a>>(relationship: &'a UnboundRelationship, visitor: &mut V) {
+    visitor.visit_unbound_relationship(relationship);
+    walk_map(&relationship.properties, visitor);
+}
+
+fn walk_structure<'a, V: ValueVisitor<'a>>(structure: &'a Structure, visitor: &mut V) {
+    match structure {
+        Structure::Node(node) => walk_node(node, visitor),
+        Structure::Relationship(relationship) => {
+            visitor.visit_relationship(relationship);
+            walk_map(&relationship.properties, visitor);
+        }
+        Structure::UnboundRelationship(relationship) => walk_unbound_relationship(relationship, visitor),
+        Structure::Path(path) => {
+            path.nodes.iter().for_each(|node| walk_node(node, visitor));
+            path.relationships
+                .iter()
+                .for_each(|relationship| walk_unbound_relationship(relationship, visitor));
+        }
+        _ => {}
+    }
+}
+
+fn map_properties<F: FnMut(Value) -> Value>(map: Map<String, Value>, f: &mut F) -> Map<String, Value> {
+    map.into_iter().map(|(key, value)| (key, value.map_with(f))).collect()
+}
+
+fn map_node<F: FnMut(Value) -> Value>(node: Node, f: &mut F) -> Node {
+    Node {
+        properties: map_properties(node.properties, f),
+        ..node
+    }
+}
+
+fn map_unbound_relationship<F: FnMut(Value) -> Value>(relationship: UnboundRelationship, f: &mut F) -> UnboundRelationship {
+    UnboundRelationship {
+        properties: map_properties(relationship.properties, f),
+        ..relationship
+    }
+}
+
+fn map_structure<F: FnMut(Value) -> Value>(structure: Structure, f: &mut F) -> Structure {
+    match structure {
+        Structure::Node(node) => Structure::Node(map_node(node, f)),
+        Structure::Relationship(relationship) => Structure::Relationship(Relationship {
+            properties: map_properties(relationship.properties, f),
+            ..relationship
+        }),
+        Structure::UnboundRelationship(relationship) => {
+            Structure::UnboundRelationship(map_unbound_relationship(relationship, f))
+        }
+        Structure::Path(path) => Structure::Path(Path {
+            nodes: path.nodes.into_iter().map(|node| map_node(node, f)).collect(),
+            relationships: path
+                .relationships
+                .into_iter()
+                .map(|relationship| map_unbound_relationship(relationship, f))
+                .collect(),
+            sequence: path.sequence,
+        }),
+        structure => structure,
+    }
+}
@@ -0,0 +1,101 @@
+use super::*;
+use packstream_serde::constants::marker::*;
+use packstream_serde::value::structure::*;
+use packstream_serde::{is_canonical, to_bytes_canonical};
+use packstream_serde::Map;
+
+#[test]
+fn sorted_map_keys() {
+    let value = Value::Map(map! {
+        "b" => Value::I64(2),
+        "a" => Value::I64(1),
+        "ab" => Value::Null,
+    });
+
+    let bytes = to_bytes_canonical(&value).unwrap();
+    assert_eq!(
+        bytes,
+        bytes!(
+            [TINY_MAP + 3],
+            [TINY_STRING + 1], b"a".to_vec(), [1],
+            [TINY_STRING + 2], b"ab".to_vec(), [NULL],
+            [TINY_STRING + 1], b"b".to_vec(), [2],
+        ),
+    );
+    assert!(is_canonical(&bytes));
+    assert_eq!(from_bytes::<Value>(&bytes).unwrap(), value);
+}
+
+#[test]
+fn deterministic() {
+    let build = || {
+        let mut properties = Map::new();
+        for i in 0..300 {
+            properties.insert(format!("key_{}", i), Value::I64(i * 1000));
+        }
+        Value::List(vec![
+            Value::Structure(Structure::Node(Node {
+                id: 1,
+                labels: vec![String::from("Label")],
+                properties,
+            })),
+            Value::Map(map! { "z" => Value::F64(1.5), "y" => Value::Bool(true) }),
+        ])
+    };
+
+    let bytes = to_bytes_canonical(&build()).unwrap();
+    for _ in 0..10 {
+        assert_eq!(to_bytes_canonical(&build()).unwrap(), bytes);
+    }
+    assert!(is_canonical(&bytes));
+    assert_eq!(from_bytes::<Value>(&bytes).unwrap(), build());
+}
+
+#[test]
+fn sorted_struct_fields() {
+    #[derive(Serialize)]
+    struct Test {
+        z: i64,
+        a: i64,
+    }
+
+    let bytes = to_bytes_canonical(&Test { z: 1, a: 2 }).unwrap();
+    assert_eq!(
+        bytes,
+        bytes!([TINY_MAP + 2, TINY_STRING + 1], b"a".to_vec(), [2, TINY_STRING + 1], b"z".to_vec(), [1]),
+    );
+    assert!(is_canonical(&bytes));
+    assert!(!is_canonical(&to_bytes(&Test { z: 1, a: 2 }).unwrap()));
+}
+
+#[test]
+fn validate() {
+    assert!(is_canonical(&[NULL]));
+    assert!(is_canonical(&[INT_8, 0x80]));
+    assert!(is_canonical(&[TINY_LIST + 2, 1, 2]));
+    assert!(is_canonical(&[TINY_STRUCT + 1, 0x44, 1]));
+
+    // Integers using larger markers than needed.
+    assert!(!is_canonical(&[INT_8, 1]));
+    assert!(!is_canonical(&[INT_16, 0, 0x7F]));
+    assert!(!is_canonical(&[INT_64, 0, 0, 0, 0, 0, 0, 0, 1]));
+
+    // Sizes using larger markers than needed.
+    assert!(!is_canonical(&[STRING_8, 1, b'a']));
+    assert!(!is_canonical(&[LIST_8, 0]));
+    assert!(!is_canonical(&[MAP_16, 0, 0]));
+
+    // Streams.
+    assert!(!is_canonical(&[LIST_STREAM, END_OF_STREAM]));
+
+    // Unsorted, duplicate and non-string keys.
+    assert!(!is_canonical(&[TINY_MAP + 2, TINY_STRING + 1, b'b', 1, TINY_STRING + 1, b'a', 1]));
+    assert!(!is_canonical(&[TINY_MAP + 2, TINY_STRING + 1, b'a', 1, TINY_STRING + 1, b'a', 1]));
+    assert!(!is_canonical(&[TINY_MAP + 1, 1, 1]));
+
+    // Invalid UTF-8, trailing and missing bytes.
+    assert!(!is_canonical(&[TINY_STRING + 1, 0xFF]));
+    assert!(!is_canonical(&[NULL, NULL]));
+    assert!(!is_canonical(&[TINY_LIST + 1]));
+    assert!(!is_canonical(&[]));
+}
@@ -121,7 +121,7 @@ fn map() {
     use std::collections::HashMap;
 
     de(&[TINY_MAP], HashMap::<(), ()>::new());
-    de(&[TINY_MAP], Value::Map(HashMap::new()));
+    de(&[TINY_MAP], Value::Map(packstream_serde::Map::new()));
     de(
         &bytes!([TINY_MAP + 1, TINY_STRING + 3], b"has".to_vec(), [NULL]),
         Value::Map(map! { "has" => Value::Null }),
@@ -0,0 +1,157 @@
+use super::*;
+use packstream_serde::options::{EnumRepr, Options};
+use packstream_serde::{
+    from_bytes_with_options, from_text, to_bytes_canonical, to_bytes_with_options, to_value_with_options,
+};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Props {
+    a: i64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Shape<'a> {
+    Empty,
+    Named(&'a str),
+    Props(Props),
+    Pair(i64, i64),
+    Circle { r: i64 },
+}
+
+const INTERNAL: EnumRepr = EnumRepr::Internal { tag: "type" };
+const ADJACENT: EnumRepr = EnumRepr::Adjacent { tag: "t", content: "c" };
+
+// Serializes the value with given representation into bytes and into a value
+// and compares them to the text notation.
+fn ser_text(repr: EnumRepr, value: &Shape, expected: &str) {
+    let options = Options::new().enum_repr(repr);
+    let bytes = to_bytes_with_options(value, options).unwrap();
+    assert_eq!(from_bytes::<Value>(&bytes).unwrap(), from_text(expected).unwrap());
+    assert_eq!(to_value_with_options(value, options).unwrap(), from_text(expected).unwrap());
+}
+
+// Deserializes the text notation with given representation.
+fn de_text(repr: EnumRepr, text: &str, expected: Shape) {
+    // Canonical bytes do not depend on the map iteration order.
+    let bytes = to_bytes_canonical(&from_text(text).unwrap()).unwrap();
+    let value: Shape = from_bytes_with_options(&bytes, Options::new().enum_repr(repr)).unwrap();
+    assert_eq!(value, expected);
+}
+
+fn round_trip(repr: EnumRepr, value: Shape) {
+    let options = Options::new().enum_repr(repr);
+    let bytes = to_bytes_with_options(&value, options).unwrap();
+    assert_eq!(from_bytes_with_options::<Shape>(&bytes, options).unwrap(), value);
+}
+
+#[test]
+fn external() {
+    assert_eq!(
+        to_bytes_with_options(&Shape::Empty, Options::new()).unwrap(),
+        to_bytes(&Shape::Empty).unwrap(),
+    );
+    ser_text(EnumRepr::External, &Shape::Circle { r: 1 }, "{Circle: {r: 1}}");
+    round_trip(EnumRepr::External, Shape::Pair(1, 2));
+}
+
+#[test]
+fn unit_as_string() {
+    ser_text(EnumRepr::UnitAsString, &Shape::Empty, r#""Empty""#);
+    ser_text(EnumRepr::UnitAsString, &Shape::Named("x"), r#"{Named: "x"}"#);
+    de_text(EnumRepr::UnitAsString, r#""Empty""#, Shape::Empty);
+    de_text(EnumRepr::UnitAsString, "{Empty: null}", Shape::Empty);
+
+    for value in [Shape::Empty, Shape::Named("x"), Shape::Pair(1, 2), Shape::Circle { r: 1 }] {
+        round_trip(EnumRepr::UnitAsString, value);
+    }
+
+    let bytes = to_bytes(&"Empty").unwrap();
+    assert!(from_bytes::<Shape>(&bytes).is_err());
+}
+
+#[test]
+fn internal() {
+    ser_text(INTERNAL, &Shape::Empty, r#"{type: "Empty"}"#);
+    ser_text(INTERNAL, &Shape::Props(Props { a: 1 }), r#"{type: "Props", a: 1}"#);
+    ser_text(INTERNAL, &Shape::Circle { r: 1 }, r#"{type: "Circle", r: 1}"#);
+
+    de_text(INTERNAL, r#"{a: 1, type: "Props"}"#, Shape::Props(Props { a: 1 }));
+    de_text(INTERNAL, r#"{r: 2, type: "Circle"}"#, Shape::Circle { r: 2 });
+    de_text(INTERNAL, r#"{type: "Empty", extra: 1}"#, Shape::Empty);
+
+    for value in [Shape::Empty, Shape::Props(Props { a: 1 }), Shape::Circle { r: 1 }] {
+        round_trip(INTERNAL, value);
+    }
+
+    let options = Options::new().enum_repr(INTERNAL);
+    assert!(to_bytes_with_options(&Shape::Named("x"), options).is_err());
+    assert!(to_bytes_with_options(&Shape::Pair(1, 2), options).is_err());
+    assert!(to_value_with_options(Shape::Named("x"), options).is_err());
+    assert!(to_value_with_options(Shape::Pair(1, 2), options).is_err());
+
+    let bytes = to_bytes_canonical(&from_text("{r: 1}").unwrap()).unwrap();
+    assert!(from_bytes_with_options::<Shape>(&bytes, options).is_err());
+}
+
+#[test]
+fn internal_borrowed() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Label<'a> {
+        Name { name: &'a str },
+    }
+
+    let options = Options::new().enum_repr(INTERNAL);
+    let bytes = to_bytes_with_options(&Label::Name { name: "x" }, options).unwrap();
+    let value: Label = from_bytes_with_options(&bytes, options).unwrap();
+    assert_eq!(value, Label::Name { name: "x" });
+}
+
+#[test]
+fn internal_canonical() {
+    let options = Options::new().enum_repr(INTERNAL).canonical(true);
+    let expected = to_bytes_canonical(&from_text(r#"{a: 1, type: "Props"}"#).unwrap()).unwrap();
+    assert_eq!(to_bytes_with_options(&Shape::Props(Props { a: 1 }), options).unwrap(), expected);
+
+    let expected = to_bytes_canonical(&from_text(r#"{r: 1, type: "Circle"}"#).unwrap()).unwrap();
+    assert_eq!(to_bytes_with_options(&Shape::Circle { r: 1 }, options).unwrap(), expected);
+}
+
+#[test]
+fn adjacent() {
+    ser_text(ADJACENT, &Shape::Empty, r#"{t: "Empty"}"#);
+    ser_text(ADJACENT, &Shape::Named("x"), r#"{t: "Named", c: "x"}"#);
+    ser_text(ADJACENT, &Shape::Pair(1, 2), r#"{t: "Pair", c: [1, 2]}"#);
+    ser_text(ADJACENT, &Shape::Circle { r: 1 }, r#"{t: "Circle", c: {r: 1}}"#);
+
+    de_text(ADJACENT, r#"{c: null, t: "Empty"}"#, Shape::Empty);
+    de_text(ADJACENT, r#"{c: [1, 2], t: "Pair"}"#, Shape::Pair(1, 2));
+
+    for value in [
+        Shape::Empty,
+        Shape::Named("x"),
+        Shape::Props(Props { a: 1 }),
+        Shape::Pair(1, 2),
+        Shape::Circle { r: 1 },
+    ] {
+        round_trip(ADJACENT, value);
+    }
+
+    let bytes = to_bytes_canonical(&from_text(r#"{t: "Pair"}"#).unwrap()).unwrap();
+    assert!(from_bytes_with_options::<Shape>(&bytes, Options::new().enum_repr(ADJACENT)).is_err());
+}
+
+#[test]
+fn adjacent_canonical() {
+    let options = Options::new().enum_repr(ADJACENT).canonical(true);
+    let expected = to_bytes_canonical(&from_text(r#"{c: "x", t: "Named"}"#).unwrap()).unwrap();
+    assert_eq!(to_bytes_with_options(&Shape::Named("x"), options).unwrap(), expected);
+    let expected = to_bytes_canonical(&from_text(r#"{c: [1, 2], t: "Pair"}"#).unwrap()).unwrap();
+    assert_eq!(to_bytes_with_options(&Shape::Pair(1, 2), options).unwrap(), expected);
+    let expected = to_bytes_canonical(&from_text(r#"{c: {r: 1}, t: "Circle"}"#).unwrap()).unwrap();
+    assert_eq!(to_bytes_with_options(&Shape::Circle { r: 1 }, options).unwrap(), expected);
+
+    let repr = EnumRepr::Adjacent { tag: "type", content: "value" };
+    let options = Options::new().enum_repr(repr).canonical(true);
+    let expected = to_bytes_canonical(&from_text(r#"{type: "Pair", value: [1, 2]}"#).unwrap()).unwrap();
+    assert_eq!(to_bytes_with_options(&Shape::Pair(1, 2), options).unwrap(), expected);
+}
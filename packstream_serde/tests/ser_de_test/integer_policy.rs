@@ -0,0 +1,102 @@
+use super::*;
+use packstream_serde::options::{IntegerPolicy, Options};
+use packstream_serde::{from_bytes_with_options, to_bytes_with_options, to_value_with_options};
+
+fn options(policy: IntegerPolicy) -> Options {
+    Options::new().integer_policy(policy)
+}
+
+fn err_message<T: Debug>(result: Result<T, packstream_serde::error::PackstreamError>) -> String {
+    result.expect_err("to be an error").to_string()
+}
+
+#[test]
+fn error() {
+    let policy = options(IntegerPolicy::Error);
+    assert_eq!(
+        to_bytes_with_options(&(i64::MAX as u64), policy).unwrap(),
+        bytes!([marker::INT_64], i64::MAX.to_be_bytes()),
+    );
+    assert_eq!(
+        err_message(to_bytes_with_options(&u64::MAX, policy)),
+        "Integer 18446744073709551615 is out of the packstream range -9223372036854775808..=9223372036854775807",
+    );
+    assert!(to_bytes_with_options(&i128::MIN, policy).is_err());
+    assert!(to_bytes(&u128::MAX).is_err());
+    assert!(to_value_with_options(u64::MAX, policy).is_err());
+    assert_eq!(to_bytes(&-1_i128).unwrap(), [0xFF]);
+
+    assert_eq!(
+        err_message(from_bytes_with_options::<u8>(&to_bytes(&300).unwrap(), policy)),
+        "Integer 300 is out of the u8 range 0..=255",
+    );
+    assert_eq!(
+        err_message(from_bytes::<i16>(&to_bytes(&-40_000).unwrap())),
+        "Integer -40000 is out of the i16 range -32768..=32767",
+    );
+    assert!(from_bytes::<u64>(&to_bytes(&-1).unwrap()).is_err());
+    assert!(from_bytes::<u64>(&to_bytes(&"1").unwrap()).is_err());
+    assert_eq!(from_bytes::<i128>(&to_bytes(&-1).unwrap()).unwrap(), -1);
+}
+
+#[test]
+fn saturate() {
+    let policy = options(IntegerPolicy::Saturate);
+    let de = |value: i64| to_bytes(&value).unwrap();
+
+    assert_eq!(to_bytes_with_options(&u64::MAX, policy).unwrap(), de(i64::MAX));
+    assert_eq!(to_bytes_with_options(&u128::MAX, policy).unwrap(), de(i64::MAX));
+    assert_eq!(to_bytes_with_options(&i128::MIN, policy).unwrap(), de(i64::MIN));
+    assert_eq!(to_value_with_options(u128::MAX, policy).unwrap(), Value::I64(i64::MAX));
+
+    assert_eq!(from_bytes_with_options::<u8>(&de(300), policy).unwrap(), u8::MAX);
+    assert_eq!(from_bytes_with_options::<u8>(&de(-1), policy).unwrap(), 0);
+    assert_eq!(from_bytes_with_options::<i32>(&de(i64::MIN), policy).unwrap(), i32::MIN);
+    assert_eq!(from_bytes_with_options::<u64>(&de(-5), policy).unwrap(), 0);
+}
+
+#[test]
+fn string() {
+    let policy = options(IntegerPolicy::String);
+
+    let bytes = to_bytes_with_options(&u64::MAX, policy).unwrap();
+    assert_eq!(from_bytes::<Value>(&bytes).unwrap(), Value::String(u64::MAX.to_string()));
+    assert_eq!(from_bytes_with_options::<u64>(&bytes, policy).unwrap(), u64::MAX);
+    assert_eq!(to_value_with_options(u64::MAX, policy).unwrap(), Value::String(u64::MAX.to_string()));
+
+    let bytes = to_bytes_with_options(&i128::MIN, policy).unwrap();
+    assert_eq!(from_bytes_with_options::<i128>(&bytes, policy).unwrap(), i128::MIN);
+
+    let bytes = to_bytes_with_options(&u128::MAX, policy).unwrap();
+    assert_eq!(from_bytes_with_options::<u128>(&bytes, policy).unwrap(), u128::MAX);
+
+    // Values in range are still integers.
+    assert_eq!(to_bytes_with_options(&1_u64, policy).unwrap(), [1]);
+
+    assert!(from_bytes_with_options::<u8>(&to_bytes(&300).unwrap(), policy).is_err());
+    assert_eq!(
+        err_message(from_bytes_with_options::<u64>(&to_bytes(&"-1").unwrap(), policy)),
+        "Integer '-1' is out of the u64 range 0..=18446744073709551615",
+    );
+}
+
+#[test]
+fn strict_f32() {
+    let strict = Options::new().strict_f32(true);
+
+    for value in [1.5, -0.25, f64::INFINITY, f64::from(f32::MAX)] {
+        let bytes = to_bytes(&value).unwrap();
+        assert_eq!(f64::from(from_bytes_with_options::<f32>(&bytes, strict).unwrap()), value);
+    }
+    assert!(from_bytes_with_options::<f32>(&to_bytes(&f64::NAN).unwrap(), strict)
+        .unwrap()
+        .is_nan());
+
+    let bytes = to_bytes(&0.1_f64).unwrap();
+    assert_eq!(from_bytes::<f32>(&bytes).unwrap(), 0.1_f32);
+    assert_eq!(
+        err_message(from_bytes_with_options::<f32>(&bytes, strict)),
+        "Float 0.1 cannot be represented as f32 without loss",
+    );
+    assert!(from_bytes_with_options::<f32>(&to_bytes(&1e300).unwrap(), strict).is_err());
+}
@@ -1,7 +1,8 @@
 use super::*;
 use packstream_serde::constants::marker::*;
 use packstream_serde::message::*;
-use std::collections::HashMap;
+use packstream_serde::packstream::EmptyPackstreamStructure;
+use packstream_serde::Map;
 
 mod request_message {
     use super::*;
@@ -39,21 +40,53 @@ mod request_message {
                 scheme: scheme.clone(),
                 principal: principal.clone(),
                 credentials: credentials.clone(),
-            }
+            }.into(),
         });
         de_ser(RequestMessage::Init(Init {
             client: client.clone(),
-            auth: BasicAuth {
-                scheme: scheme.clone(),
-                principal: principal.clone(),
-                credentials: credentials.clone(),
-            }
+            auth: AuthToken::basic(principal.clone(), credentials.clone()),
         }));
 
         de_err::<Init>(&BYTES[0..(BYTES.len() - 1)]);
     }
 
 
+    #[test]
+    fn hello() {
+        // Hello { extra: { user_agent: "a" } }
+        const BYTES: &[u8] = &[
+            0xB1, 0x01, 0xA1, 0x8A, 0x75, 0x73, 0x65, 0x72, 0x5F, 0x61, 0x67,
+            0x65, 0x6E, 0x74, 0x81, 0x61,
+        ];
+
+        ser_de::<Hello>(BYTES);
+        ser_de::<RequestMessage>(BYTES);
+        ser(Hello::new("a", None), BYTES);
+        de(BYTES, RequestMessage::Hello(Hello::new("a", None)));
+
+        let hello = Hello::new("a", Some(AuthToken::basic("user", "password")));
+        assert_eq!(hello.extra.get("scheme"), Some(&Value::String(String::from("basic"))));
+        assert_eq!(hello.extra.get("principal"), Some(&Value::String(String::from("user"))));
+        de_ser(RequestMessage::Hello(hello));
+
+        de_err::<Hello>(&BYTES[0..(BYTES.len() - 1)]);
+        de_err::<Init>(BYTES);
+    }
+
+    #[test]
+    fn goodbye() {
+        const BYTES: &[u8] = &[0xB0, 0x02];
+
+        ser_de::<Goodbye>(BYTES);
+        ser_de::<RequestMessage>(BYTES);
+        assert_eq!(<Goodbye as EmptyPackstreamStructure>::MSG, BYTES);
+
+        de_ser(Goodbye);
+        de_ser(RequestMessage::Goodbye(Goodbye));
+
+        de_err::<Goodbye>(&[TINY_STRUCT, Goodbye::SIG + 1]);
+    }
+
     #[test]
     fn ack_failure() {
         const BYTES: &[u8] = &[TINY_STRUCT + AckFailure::LEN, AckFailure::SIG];
@@ -89,16 +122,75 @@ mod request_message {
 
         de_ser(Run {
             statement: String::new(),
-            parameters: HashMap::new(),
+            parameters: Map::new(),
         });
         de_ser(RequestMessage::Run(Run {
             statement: String::new(),
-            parameters: HashMap::new(),
+            parameters: Map::new(),
         }));
 
         de_err::<Run>(&BYTES[0..(BYTES.len() - 1)]);
     }
 
+    #[test]
+    fn run_with_metadata() {
+        const BYTES: &[u8] = &[
+            TINY_STRUCT + RunWithMetadata::LEN, RunWithMetadata::SIG, TINY_STRING, TINY_MAP, TINY_MAP,
+        ];
+
+        ser_de::<RunWithMetadata>(BYTES);
+        ser_de::<RequestMessage>(BYTES);
+
+        de_ser(RequestMessage::RunWithMetadata(RunWithMetadata {
+            statement: String::from("RETURN $x"),
+            parameters: map!("x" => Value::I64(1)),
+            extra: map!("db" => Value::String(String::from("neo4j"))),
+        }));
+
+        de_err::<RunWithMetadata>(&BYTES[0..(BYTES.len() - 1)]);
+        de_err::<Run>(BYTES);
+    }
+
+    #[test]
+    fn begin() {
+        const BYTES: &[u8] = &[TINY_STRUCT + Begin::LEN, Begin::SIG, TINY_MAP];
+
+        ser_de::<Begin>(BYTES);
+        ser_de::<RequestMessage>(BYTES);
+
+        de_ser(RequestMessage::Begin(Begin {
+            extra: map!("tx_timeout" => Value::I64(1000)),
+        }));
+
+        de_err::<Begin>(&BYTES[0..(BYTES.len() - 1)]);
+    }
+
+    #[test]
+    fn commit() {
+        const BYTES: &[u8] = &[0xB0, 0x12];
+
+        ser_de::<Commit>(BYTES);
+        ser_de::<RequestMessage>(BYTES);
+        assert_eq!(<Commit as EmptyPackstreamStructure>::MSG, BYTES);
+
+        de_ser(RequestMessage::Commit(Commit));
+
+        de_err::<Commit>(&[TINY_STRUCT, Rollback::SIG]);
+    }
+
+    #[test]
+    fn rollback() {
+        const BYTES: &[u8] = &[0xB0, 0x13];
+
+        ser_de::<Rollback>(BYTES);
+        ser_de::<RequestMessage>(BYTES);
+        assert_eq!(<Rollback as EmptyPackstreamStructure>::MSG, BYTES);
+
+        de_ser(RequestMessage::Rollback(Rollback));
+
+        de_err::<Rollback>(&[TINY_STRUCT, Commit::SIG]);
+    }
+
     #[test]
     fn discard_all() {
         const BYTES: &[u8] = &[TINY_STRUCT + DiscardAll::LEN, DiscardAll::SIG];
@@ -124,6 +216,260 @@ mod request_message {
 
         de_err::<PullAll>(&[TINY_STRUCT, PullAll::SIG + 1]);
     }
+
+    #[test]
+    fn discard() {
+        // Discard { extra: { n: -1 } }
+        const BYTES: &[u8] = &[0xB1, 0x2F, 0xA1, 0x81, 0x6E, 0xFF];
+
+        ser_de::<Discard>(BYTES);
+        ser_de::<RequestMessage>(BYTES);
+        ser(Discard::all(), BYTES);
+        de(BYTES, RequestMessage::Discard(Discard::all()));
+
+        de_ser(RequestMessage::Discard(Discard::new(10, Some(2))));
+
+        de_err::<DiscardAll>(BYTES);
+    }
+
+    #[test]
+    fn pull() {
+        // Pull { extra: { n: -1 } }
+        const BYTES: &[u8] = &[0xB1, 0x3F, 0xA1, 0x81, 0x6E, 0xFF];
+
+        ser_de::<Pull>(BYTES);
+        ser_de::<RequestMessage>(BYTES);
+        ser(Pull::all(), BYTES);
+        de(BYTES, RequestMessage::Pull(Pull::all()));
+
+        let pull = Pull::new(10, Some(2));
+        assert_eq!(pull.extra.get("qid"), Some(&Value::I64(2)));
+        de_ser(RequestMessage::Pull(pull));
+
+        de_err::<PullAll>(BYTES);
+    }
+
+    #[test]
+    fn logon() {
+        // Logon {
+        //      auth: {
+        //          scheme: "basic"
+        //          principal: "user"
+        //          credentials: "password"
+        //      }
+        // }
+        const BYTES: &[u8] = &[
+            0xB1, 0x6A, 0xA3, 0x86, 0x73, 0x63, 0x68, 0x65, 0x6D, 0x65, 0x85,
+            0x62, 0x61, 0x73, 0x69, 0x63, 0x89, 0x70, 0x72, 0x69, 0x6E, 0x63,
+            0x69, 0x70, 0x61, 0x6C, 0x84, 0x75, 0x73, 0x65, 0x72, 0x8B, 0x63,
+            0x72, 0x65, 0x64, 0x65, 0x6E, 0x74, 0x69, 0x61, 0x6C, 0x73, 0x88,
+            0x70, 0x61, 0x73, 0x73, 0x77, 0x6F, 0x72, 0x64,
+        ];
+
+        let basic = || Logon {
+            auth: AuthToken::basic("user", "password"),
+        };
+
+        ser_de::<Logon>(BYTES);
+        ser_de::<RequestMessage>(BYTES);
+        ser(basic(), BYTES);
+        de(BYTES, RequestMessage::Logon(basic()));
+
+        // Logon { auth: { scheme: "none" } }
+        const NONE_BYTES: &[u8] = &[
+            0xB1, 0x6A, 0xA1, 0x86, 0x73, 0x63, 0x68, 0x65, 0x6D, 0x65, 0x84,
+            0x6E, 0x6F, 0x6E, 0x65,
+        ];
+
+        ser_de::<Logon>(NONE_BYTES);
+        ser(Logon { auth: AuthToken::none() }, NONE_BYTES);
+
+        de_ser(basic());
+        de_ser(RequestMessage::Logon(Logon {
+            auth: AuthToken::bearer("token"),
+        }));
+
+        assert!(!format!("{:?}", basic()).contains("password"));
+
+        de_err::<Logon>(&BYTES[0..(BYTES.len() - 1)]);
+    }
+
+    #[test]
+    fn logoff() {
+        const BYTES: &[u8] = &[0xB0, 0x6B];
+
+        ser_de::<Logoff>(BYTES);
+        ser_de::<RequestMessage>(BYTES);
+        assert_eq!(<Logoff as EmptyPackstreamStructure>::MSG, BYTES);
+
+        de_ser(Logoff);
+        de_ser(RequestMessage::Logoff(Logoff));
+
+        de_err::<Logoff>(&[TINY_STRUCT, Logoff::SIG + 1]);
+    }
+
+    #[test]
+    fn telemetry() {
+        // Telemetry { api: 1 }
+        const BYTES: &[u8] = &[0xB1, 0x54, 0x01];
+
+        ser_de::<Telemetry>(BYTES);
+        ser_de::<RequestMessage>(BYTES);
+        ser(
+            Telemetry {
+                api: Telemetry::EXPLICIT_TRANSACTION,
+            },
+            BYTES,
+        );
+
+        de_ser(Telemetry {
+            api: Telemetry::MANAGED_TRANSACTION,
+        });
+        de_ser(RequestMessage::Telemetry(Telemetry {
+            api: Telemetry::EXECUTE_QUERY,
+        }));
+
+        de_err::<Telemetry>(&BYTES[0..(BYTES.len() - 1)]);
+    }
+
+    #[test]
+    fn route() {
+        // Route { routing: {}, bookmarks: [], db: null }
+        const BYTES: &[u8] = &[TINY_STRUCT + Route::LEN, Route::SIG, TINY_MAP, TINY_LIST, NULL];
+
+        ser_de::<Route>(BYTES);
+        ser_de::<RequestMessage>(BYTES);
+
+        de_ser(Route {
+            routing: map!("address" => Value::String(String::from("localhost:7687"))),
+            bookmarks: vec![String::from("bookmark")],
+            db: Value::String(String::from("neo4j")),
+        });
+        de_ser(RequestMessage::Route(Route {
+            routing: Map::new(),
+            bookmarks: Vec::new(),
+            db: Value::Map(map!("db" => Value::String(String::from("neo4j")))),
+        }));
+
+        de_err::<Route>(&BYTES[0..(BYTES.len() - 1)]);
+    }
+}
+
+mod auth_token {
+    use super::*;
+
+    #[test]
+    fn scheme_none() {
+        ser(AuthToken::none(), &bytes!([TINY_MAP + 1, TINY_STRING + 6], b"scheme".to_vec(), [TINY_STRING + 4], b"none".to_vec()));
+
+        de_ser(AuthToken::None);
+    }
+
+    #[test]
+    fn scheme_basic() {
+        de_ser(AuthToken::basic("neo4j", "secret"));
+        de_ser(AuthToken::Basic {
+            principal: String::from("neo4j"),
+            credentials: String::from("secret"),
+            realm: Some(String::from("native")),
+        });
+
+        assert_eq!(
+            AuthToken::basic("neo4j", "secret").into_map(),
+            map! {
+                "scheme" => Value::String(String::from("basic")),
+                "principal" => Value::String(String::from("neo4j")),
+                "credentials" => Value::String(String::from("secret")),
+            },
+        );
+    }
+
+    #[test]
+    fn scheme_bearer() {
+        ser(
+            AuthToken::bearer("t"),
+            &bytes!(
+                [TINY_MAP + 2, TINY_STRING + 6], b"scheme".to_vec(),
+                [TINY_STRING + 6], b"bearer".to_vec(),
+                [TINY_STRING + 11], b"credentials".to_vec(),
+                [TINY_STRING + 1], b"t".to_vec(),
+            ),
+        );
+
+        de_ser(AuthToken::bearer("token"));
+    }
+
+    #[test]
+    fn scheme_kerberos() {
+        de_ser(AuthToken::kerberos("dGlja2V0"));
+
+        assert_eq!(
+            AuthToken::kerberos("dGlja2V0").into_map(),
+            map! {
+                "scheme" => Value::String(String::from("kerberos")),
+                "principal" => Value::String(String::new()),
+                "credentials" => Value::String(String::from("dGlja2V0")),
+            },
+        );
+    }
+
+    #[test]
+    fn scheme_custom() {
+        de_ser(AuthToken::Custom {
+            scheme: String::from("sso"),
+            principal: Some(String::from("neo4j")),
+            credentials: Some(String::from("secret")),
+            realm: None,
+            parameters: Map::new(),
+        });
+        de_ser(AuthToken::Custom {
+            scheme: String::from("basic"),
+            principal: Some(String::from("neo4j")),
+            credentials: Some(String::from("secret")),
+            realm: Some(String::from("ldap")),
+            parameters: map! { "ttl" => Value::I64(60) },
+        });
+
+        // Absent keys are not sent as empty strings.
+        let map = map! {
+            "scheme" => Value::String(String::from("sso")),
+            "parameters" => Value::Map(map! { "token" => Value::String(String::from("t")) }),
+        };
+        let token = AuthToken::from_map(map.clone()).unwrap();
+        assert_eq!(token, AuthToken::Custom {
+            scheme: String::from("sso"),
+            principal: None,
+            credentials: None,
+            realm: None,
+            parameters: map! { "token" => Value::String(String::from("t")) },
+        });
+        assert_eq!(token.into_map(), map);
+
+        assert_eq!(
+            AuthToken::from(BasicAuth {
+                scheme: String::from("sso"),
+                principal: String::new(),
+                credentials: String::new(),
+            }).scheme(),
+            "sso",
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(AuthToken::from_map(Map::new()).is_err());
+        assert!(AuthToken::from_map(map! {
+            "scheme" => Value::String(String::from("basic")),
+            "principal" => Value::String(String::from("neo4j")),
+        }).is_err());
+        assert!(AuthToken::from_map(map! {
+            "scheme" => Value::String(String::from("none")),
+            "unknown" => Value::Null,
+        }).is_err());
+        assert!(AuthToken::from_map(map! {
+            "scheme" => Value::I64(0),
+        }).is_err());
+    }
 }
 
 mod summary_message {
@@ -138,10 +484,10 @@ mod summary_message {
         ser_de::<SummaryMessage>(BYTES);
 
         de_ser(Success {
-            metadata: HashMap::new(),
+            metadata: Map::new(),
         });
         de_ser(SummaryMessage::Success(Success {
-                metadata: HashMap::new(),
+                metadata: Map::new(),
         }));
 
         de_err::<Success>(&BYTES[0..(BYTES.len() - 1)]);
@@ -169,10 +515,10 @@ mod summary_message {
         ser_de::<SummaryMessage>(BYTES);
 
         de_ser(Failure {
-            metadata: HashMap::new(),
+            metadata: Map::new(),
         });
         de_ser(SummaryMessage::Failure(Failure {
-            metadata: HashMap::new(),
+            metadata: Map::new(),
         }));
 
         de_err::<Failure>(&[TINY_STRUCT + 1, Failure::SIG + 1, TINY_MAP]);
@@ -185,7 +531,7 @@ fn record() {
 
     ser_de::<Record>(BYTES);
 
-    de_ser(Record { fields: Vec::new() });
+    de_ser(Record { fields: Vec::<Value>::new() });
 
     de_err::<Record>(&BYTES[0..(BYTES.len() - 1)]);
 }
@@ -1,9 +1,18 @@
 use super::*;
 
+mod canonical;
 mod deserialize;
+mod enum_repr;
+mod integer_policy;
 mod message;
 mod packstream;
+mod raw_value;
+mod schema;
 mod serialize;
+mod spatial;
 mod structure;
+mod temporal;
+mod text;
 mod value_deserializer;
 mod value_serializer;
+mod value_walk;
@@ -0,0 +1,68 @@
+use super::*;
+use packstream_serde::constants::marker::*;
+use packstream_serde::message::Record;
+use packstream_serde::{from_value, to_value, RawValue, RawValueBuf};
+
+#[test]
+fn borrowed() {
+    // [1, { a: [true, "b"] }, Node]
+    const BYTES: &[u8] = &[
+        TINY_LIST + 3,
+        1,
+        TINY_MAP + 1, TINY_STRING + 1, b'a', TINY_LIST + 2, TRUE, TINY_STRING + 1, b'b',
+        TINY_STRUCT + 3, 0x4E, 0, TINY_LIST, TINY_MAP,
+    ];
+
+    let (id, map, node): (i64, RawValue, RawValue) = from_bytes(BYTES).unwrap();
+
+    assert_eq!(id, 1);
+    assert_eq!(map.as_bytes(), &BYTES[2..9]);
+    assert_eq!(node.as_bytes(), &BYTES[9..]);
+    assert!(matches!(map.deserialize::<Value>(), Ok(Value::Map(_))));
+    assert!(matches!(node.deserialize::<Value>(), Ok(Value::Structure(_))));
+
+    ser((id, map, node), BYTES);
+}
+
+#[test]
+fn record_field() {
+    // Record { fields: [1, "skip"] }
+    const BYTES: &[u8] = &[
+        TINY_STRUCT + 1, 0x71, TINY_LIST + 2, 1, TINY_STRING + 4, b's', b'k', b'i', b'p',
+    ];
+
+    let record: Record<RawValue> = from_bytes(BYTES).unwrap();
+
+    assert_eq!(record.fields[0].deserialize::<i64>().unwrap(), 1);
+    assert_eq!(record.fields[1].as_bytes(), &BYTES[4..]);
+    assert_eq!(to_bytes(&record).unwrap(), BYTES);
+}
+
+#[test]
+fn owned() {
+    let raw: RawValueBuf = from_value(Value::List(vec![Value::I64(1), Value::Null])).unwrap();
+
+    assert_eq!(raw.as_bytes(), &[TINY_LIST + 2, 1, NULL]);
+    assert_eq!(
+        to_value(&raw).unwrap(),
+        Value::List(vec![Value::I64(1), Value::Null]),
+    );
+
+    let owned: RawValueBuf = from_bytes(&[TINY_STRING + 1, b'a']).unwrap();
+    assert_eq!(owned.to_string(), "a");
+    assert_eq!(owned.as_raw_value().deserialize::<&str>().unwrap(), "a");
+    assert_eq!(to_bytes(&owned).unwrap(), &[TINY_STRING + 1, b'a']);
+
+    assert!(from_value::<RawValueBuf>(Value::Null).is_ok());
+    assert!(RawValueBuf::from_vec(vec![NULL, NULL]).is_err());
+}
+
+#[test]
+fn invalid() {
+    assert!(RawValue::from_bytes(&[]).is_err());
+    assert!(RawValue::from_bytes(&[NULL, NULL]).is_err());
+    assert!(RawValue::from_bytes(&[TINY_LIST + 2, 1]).is_err());
+    assert!(RawValue::from_bytes(&[LIST_STREAM, END_OF_STREAM]).is_err());
+    assert!(from_bytes::<RawValue>(&[TINY_MAP + 1, TINY_STRING + 1, b'a']).is_err());
+    assert!(from_value::<RawValueBuf>(Value::Null).map(|raw| raw.into_vec()).unwrap() == vec![NULL]);
+}
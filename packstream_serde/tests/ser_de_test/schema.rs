@@ -0,0 +1,93 @@
+use super::*;
+use packstream_serde::from_text;
+use packstream_serde::schema::{Schema, Violation};
+
+fn violations(schema: &Schema, text: &str) -> Vec<String> {
+    schema
+        .validate(&from_text(text).unwrap())
+        .unwrap_err()
+        .iter()
+        .map(Violation::to_string)
+        .collect()
+}
+
+#[test]
+fn scalars() {
+    let value = |text: &str| from_text(text).unwrap();
+
+    assert!(Schema::boolean().validate(&value("true")).is_ok());
+    assert!(Schema::integer().validate(&value("1")).is_ok());
+    assert!(Schema::float().validate(&value("1.5")).is_ok());
+    assert!(Schema::bytes().validate(&value("Bytes([1])")).is_ok());
+    assert!(Schema::any().validate(&value("null")).is_ok());
+    assert!(Schema::integer().nullable().validate(&value("null")).is_ok());
+
+    assert_eq!(violations(&Schema::integer(), "null"), ["$: expected integer, got null instead"]);
+    assert_eq!(violations(&Schema::float(), "1"), ["$: expected float, got integer instead"]);
+}
+
+#[test]
+fn lengths() {
+    let schema = Schema::string().min_len(2).max_len(3);
+    assert!(schema.validate(&Value::String(String::from("żół"))).is_ok());
+    assert_eq!(violations(&schema, r#""a""#), ["$: expected at least 2 characters, got 1 instead"]);
+    assert_eq!(violations(&schema, r#""abcd""#), ["$: expected at most 3 characters, got 4 instead"]);
+
+    let schema = Schema::list(Schema::integer()).max_len(1);
+    assert_eq!(
+        violations(&schema, r#"[1, "a"]"#),
+        ["$[1]: expected integer, got string instead", "$: expected at most 1 items, got 2 instead"],
+    );
+
+    let schema = Schema::bytes().min_len(1);
+    assert_eq!(violations(&schema, "Bytes([])"), ["$: expected at least 1 bytes, got 0 instead"]);
+}
+
+#[test]
+fn map() {
+    let schema = Schema::map()
+        .required("id", Schema::integer())
+        .required("name", Schema::string())
+        .optional("friends", Schema::list(Schema::map().required("id", Schema::integer())));
+
+    assert!(schema.validate(&from_text(r#"{id: 1, name: "a", extra: 1}"#).unwrap()).is_ok());
+    assert_eq!(
+        violations(&schema, r#"{id: "1", friends: [{id: 2}, {}, {id: null}]}"#),
+        [
+            "$.id: expected integer, got string instead",
+            "$: missing required key 'name'",
+            "$.friends[1]: missing required key 'id'",
+            "$.friends[2].id: expected integer, got null instead",
+        ],
+    );
+
+    let schema = Schema::map().optional("a", Schema::any()).deny_unknown_keys();
+    assert_eq!(violations(&schema, "{a: 1, c: 1, b: 1}"), ["$: unknown key 'b'", "$: unknown key 'c'"]);
+    assert_eq!(violations(&schema, "[]"), ["$: expected map, got list instead"]);
+}
+
+#[test]
+fn node() {
+    let schema = Schema::node()
+        .labels(["Person", "Admin"])
+        .required("name", Schema::string());
+
+    assert!(schema
+        .validate(&from_text(r#"Node(1, ["Person"], {name: "a"})"#).unwrap())
+        .is_ok());
+    assert_eq!(
+        violations(&schema, r#"Node(1, ["Person", "Robot", "Cat"], {name: 1})"#),
+        [
+            "$: label 'Robot' is not allowed",
+            "$: label 'Cat' is not allowed",
+            "$.name: expected string, got integer instead",
+        ],
+    );
+    assert_eq!(violations(&schema, "{name: 1}"), ["$: expected node, got map instead"]);
+
+    let params = Schema::map().required("people", Schema::list(schema));
+    assert_eq!(
+        violations(&params, r#"{people: [Node(1, ["Cat"], {name: "a"})]}"#),
+        ["$.people[0]: label 'Cat' is not allowed"],
+    );
+}
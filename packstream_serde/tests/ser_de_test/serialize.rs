@@ -125,7 +125,7 @@ fn map() {
     use std::collections::HashMap;
 
     ser(HashMap::<(), ()>::new(), &[TINY_MAP]);
-    ser(Value::Map(HashMap::new()), &[TINY_MAP]);
+    ser(Value::Map(packstream_serde::Map::new()), &[TINY_MAP]);
     ser(
         Value::Map(map! { "has" => Value::Null }),
         &bytes!([TINY_MAP + 1, TINY_STRING + 3], b"has".to_vec(), [NULL]),
@@ -0,0 +1,73 @@
+use packstream_serde::value::structure::*;
+
+fn assert_close(actual: Option<f64>, expected: f64) {
+    let actual = actual.unwrap();
+    assert!((actual - expected).abs() < 1e-3, "{} != {}", actual, expected);
+}
+
+#[test]
+fn crs() {
+    for crs in [Crs::Wgs84, Crs::Wgs84_3D, Crs::Cartesian, Crs::Cartesian3D] {
+        assert_eq!(Crs::from_srid(crs.srid()), Some(crs));
+    }
+    assert_eq!(Crs::from_srid(0), None);
+    assert_eq!(Crs::Wgs84_3D.to_string(), "wgs-84-3d");
+    assert_eq!(Crs::Cartesian3D.dimension(), 3);
+    assert!(Crs::Wgs84.is_geographic());
+    assert!(!Crs::Cartesian.is_geographic());
+
+    assert_eq!(Point2D { srid: 4326, x: 0.0, y: 0.0 }.crs().unwrap(), Crs::Wgs84);
+    assert_eq!(Point3D { srid: 9157, x: 0.0, y: 0.0, z: 0.0 }.crs().unwrap(), Crs::Cartesian3D);
+    assert!(Point2D { srid: 4979, x: 0.0, y: 0.0 }.crs().is_err());
+    assert!(Point3D { srid: 7203, x: 0.0, y: 0.0, z: 0.0 }.crs().is_err());
+    assert!(Point2D { srid: 1, x: 0.0, y: 0.0 }.crs().is_err());
+}
+
+#[test]
+fn validation() {
+    assert_eq!(
+        Point2D::new(Crs::Cartesian, 1.0, 2.0).unwrap(),
+        Point2D { srid: 7203, x: 1.0, y: 2.0 },
+    );
+    assert!(Point2D::new(Crs::Cartesian3D, 1.0, 2.0).is_err());
+    assert!(Point3D::new(Crs::Wgs84, 1.0, 2.0, 3.0).is_err());
+    assert!(Point2D::new(Crs::Wgs84, 181.0, 0.0).is_err());
+    assert!(Point2D::new(Crs::Wgs84, 0.0, -90.5).is_err());
+    assert!(Point3D::new(Crs::Wgs84_3D, 0.0, 0.0, f64::NAN).is_err());
+    assert!(Point2D::new(Crs::Cartesian, 1000.0, -1000.0).is_ok());
+}
+
+#[test]
+fn distance() {
+    let cartesian = Point2D::new(Crs::Cartesian, 0.0, 0.0).unwrap();
+    assert_close(cartesian.distance(&Point2D::new(Crs::Cartesian, 3.0, 4.0).unwrap()), 5.0);
+
+    let cartesian_3d = Point3D::new(Crs::Cartesian3D, 1.0, 1.0, 1.0).unwrap();
+    assert_close(cartesian_3d.distance(&Point3D::new(Crs::Cartesian3D, 3.0, 4.0, 7.0).unwrap()), 7.0);
+
+    let malmo = Point2D::new(Crs::Wgs84, 12.994341, 55.611784).unwrap();
+    let copenhagen = Point2D::new(Crs::Wgs84, 12.564590, 55.672874).unwrap();
+    assert_close(malmo.distance(&copenhagen), 27_841.920_013);
+
+    let equator = Point2D::new(Crs::Wgs84, 0.0, 0.0).unwrap();
+    let quarter = Point2D::new(Crs::Wgs84, 90.0, 0.0).unwrap();
+    assert_close(equator.distance(&quarter), 6_378_140.0 * std::f64::consts::FRAC_PI_2);
+
+    let low = Point3D::new(Crs::Wgs84_3D, 0.0, 0.0, 0.0).unwrap();
+    let high = Point3D::new(Crs::Wgs84_3D, 0.0, 0.0, 100.0).unwrap();
+    assert_close(low.distance(&high), 100.0);
+
+    assert_eq!(cartesian.distance(&equator), None);
+    assert_eq!(Point2D { srid: 1, x: 0.0, y: 0.0 }.distance(&Point2D { srid: 1, x: 0.0, y: 0.0 }), None);
+}
+
+#[cfg(feature = "geo-types")]
+#[test]
+fn geo_types() {
+    let point = Point2D::new(Crs::Wgs84, 12.5, 55.5).unwrap();
+    let geo: geo_types::Point<f64> = point.into();
+
+    assert_eq!(geo, geo_types::Point::new(12.5, 55.5));
+    assert_eq!(Point2D::from_geo(geo, Crs::Wgs84).unwrap(), point);
+    assert!(Point2D::from_geo(geo_types::Point::new(200.0, 0.0), Crs::Wgs84).is_err());
+}
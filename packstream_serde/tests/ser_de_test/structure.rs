@@ -1,7 +1,7 @@
 use super::*;
 use packstream_serde::constants::marker::*;
 use packstream_serde::value::structure::*;
-use std::collections::HashMap;
+use packstream_serde::Map;
 
 #[test]
 fn node() {
@@ -14,17 +14,17 @@ fn node() {
     de_ser(Node {
         id: 0,
         labels: Vec::new(),
-        properties: HashMap::new(),
+        properties: Map::new(),
     });
     de_ser(Structure::Node(Node {
         id: 0,
         labels: Vec::new(),
-        properties: HashMap::new(),
+        properties: Map::new(),
     }));
     de_ser(Value::Structure(Structure::Node(Node {
         id: 0,
         labels: Vec::new(),
-        properties: HashMap::new(),
+        properties: Map::new(),
     })));
 
     de_err::<Node>(&BYTES[0..(BYTES.len() - 1)]);
@@ -84,21 +84,21 @@ fn relationship() {
         start_node_id: 0,
         end_node_id: 0,
         r#type: String::new(),
-        properties: HashMap::new(),
+        properties: Map::new(),
     });
     de_ser(Structure::Relationship(Relationship {
         id: 0,
         start_node_id: 0,
         end_node_id: 0,
         r#type: String::new(),
-        properties: HashMap::new(),
+        properties: Map::new(),
     }));
     de_ser(Value::Structure(Structure::Relationship(Relationship {
         id: 0,
         start_node_id: 0,
         end_node_id: 0,
         r#type: String::new(),
-        properties: HashMap::new(),
+        properties: Map::new(),
     })));
 
     de_err::<Relationship>(&BYTES[0..(BYTES.len() - 1)]);
@@ -121,17 +121,17 @@ fn unbound_relationship() {
     de_ser(UnboundRelationship {
         id: 0,
         r#type: String::new(),
-        properties: HashMap::new(),
+        properties: Map::new(),
     });
     de_ser(Structure::UnboundRelationship(UnboundRelationship {
         id: 0,
         r#type: String::new(),
-        properties: HashMap::new(),
+        properties: Map::new(),
     }));
     de_ser(Value::Structure(Structure::UnboundRelationship(UnboundRelationship {
         id: 0,
         r#type: String::new(),
-        properties: HashMap::new(),
+        properties: Map::new(),
     })));
 
     de_err::<UnboundRelationship>(&BYTES[0..(BYTES.len() - 1)]);
@@ -0,0 +1,140 @@
+use packstream_serde::value::structure::*;
+
+// Asserts that `text` parses into `value` and that `value` formats into `text`.
+fn iso<T>(text: &str, value: T)
+where
+    T: std::str::FromStr + std::fmt::Display + std::fmt::Debug + PartialEq,
+    T::Err: std::fmt::Debug,
+{
+    assert_eq!(text.parse::<T>().unwrap(), value);
+    assert_eq!(value.to_string(), text);
+}
+
+#[test]
+fn date() {
+    assert_eq!(Date::from_ymd(1970, 1, 1).unwrap(), Date { days: 0 });
+    assert_eq!(Date::from_ymd(2000, 3, 1).unwrap(), Date { days: 11_017 });
+    assert_eq!(Date::from_ymd(1969, 12, 31).unwrap(), Date { days: -1 });
+    assert_eq!(Date::from_ymd(2024, 2, 29).unwrap().to_ymd(), (2024, 2, 29));
+    assert_eq!(Date { days: -719_528 }.to_ymd(), (0, 1, 1));
+
+    assert!(Date::from_ymd(2023, 2, 29).is_err());
+    assert!(Date::from_ymd(2023, 13, 1).is_err());
+    assert!(Date::from_ymd(2023, 4, 31).is_err());
+    assert!(Date::from_ymd(1_000_000_000, 1, 1).is_err());
+    assert!(Date::new(i64::MAX).is_err());
+    // Decoded values are not validated, but still format.
+    assert_eq!(Date { days: i64::MAX }.to_string(), "+25252734927768524-07-27");
+    assert!(!Date { days: i64::MIN }.to_string().is_empty());
+
+    iso("1970-01-01", Date { days: 0 });
+    iso("2024-02-29", Date::from_ymd(2024, 2, 29).unwrap());
+    iso("-0001-12-31", Date::from_ymd(-1, 12, 31).unwrap());
+    iso("+10000-01-01", Date::from_ymd(10_000, 1, 1).unwrap());
+
+    assert!("2024-2-29".parse::<Date>().is_err());
+    assert!("2024-02-30".parse::<Date>().is_err());
+    assert!("2024-02-29T".parse::<Date>().is_err());
+}
+
+#[test]
+fn time() {
+    let time = LocalTime::from_hms_nano(12, 30, 15, 500_000_000).unwrap();
+    assert_eq!(time.nanoseconds, 45_015_500_000_000);
+    assert_eq!(time.to_hms_nano(), (12, 30, 15, 500_000_000));
+
+    assert!(LocalTime::from_hms_nano(24, 0, 0, 0).is_err());
+    assert!(LocalTime::from_hms_nano(0, 0, 0, 1_000_000_000).is_err());
+    assert!(LocalTime::new(-1).is_err());
+    assert!(Time::new(0, 18 * 3_600 + 1).is_err());
+
+    iso("12:30:15.5", time);
+    iso("00:00:00", LocalTime { nanoseconds: 0 });
+    iso("23:59:59.000000001", LocalTime::new(86_399_000_000_001).unwrap());
+    assert_eq!("12:30".parse::<LocalTime>().unwrap().to_string(), "12:30:00");
+
+    iso("12:30:15.5Z", Time::new(time.nanoseconds, 0).unwrap());
+    iso("01:00:00+02:00", Time::new(3_600_000_000_000, 7_200).unwrap());
+    iso("01:00:00-05:30:15", Time::new(3_600_000_000_000, -19_815).unwrap());
+
+    assert!("12:60:00".parse::<LocalTime>().is_err());
+    assert!("12:00:00".parse::<Time>().is_err());
+    assert!("12:00:00+19:00".parse::<Time>().is_err());
+}
+
+#[test]
+fn date_time() {
+    let date = Date::from_ymd(2020, 1, 31).unwrap();
+    let time = LocalTime::from_hms_nano(10, 0, 0, 5).unwrap();
+    let local = LocalDateTime::from_date_time(&date, &time).unwrap();
+
+    assert_eq!(local.date(), date);
+    assert_eq!(local.time(), time);
+    iso("2020-01-31T10:00:00.000000005", local);
+    iso("1969-12-31T23:59:59", LocalDateTime::new(-1, 0).unwrap());
+
+    iso("2020-01-31T10:00:00+01:00", DateTime::new(1_580_464_800, 0, 3_600).unwrap());
+    iso(
+        "2020-01-31T10:00:00[Europe/Warsaw]",
+        DateTimeZoneId::new(1_580_464_800, 0, "Europe/Warsaw").unwrap(),
+    );
+
+    assert!(LocalDateTime::new(0, -1).is_err());
+    assert!(DateTime::new(0, 0, 100_000).is_err());
+    assert!(DateTimeZoneId::new(0, 0, "").is_err());
+    let extreme = DateTime { seconds: i64::MAX, nanoseconds: i64::MAX, tz_offset_seconds: i64::MIN };
+    assert!(extreme.to_string().ends_with("-2562047788015215:30:08"));
+    assert!("2020-01-31 10:00:00".parse::<LocalDateTime>().is_err());
+    assert!("2020-01-31T10:00:00".parse::<DateTime>().is_err());
+    assert!("2020-01-31T10:00:00[Europe/Warsaw".parse::<DateTimeZoneId>().is_err());
+}
+
+#[test]
+fn duration() {
+    iso("PT0S", Duration::new(0, 0, 0, 0).unwrap());
+    iso("P1Y2M3DT4.5S", Duration::new(14, 3, 4, 500_000_000).unwrap());
+    iso("P14DT1H2M3.000000004S", Duration::new(0, 14, 3_723, 4).unwrap());
+    iso("PT-0.5S", Duration::new(0, 0, 0, -500_000_000).unwrap());
+    iso("P-1Y-2MT-1H", Duration::new(-14, 0, -3_600, 0).unwrap());
+
+    assert_eq!("P2W".parse::<Duration>().unwrap(), Duration::new(0, 14, 0, 0).unwrap());
+    assert_eq!("PT90M".parse::<Duration>().unwrap().to_string(), "PT1H30M");
+    assert_eq!(
+        Duration::new(0, 0, 1, 1_500_000_000).unwrap(),
+        Duration { months: 0, days: 0, seconds: 2, nanoseconds: 500_000_000 },
+    );
+    assert_eq!(-Duration::new(1, 2, 3, 4).unwrap(), Duration::new(-1, -2, -3, -4).unwrap());
+
+    assert!("P".parse::<Duration>().is_err());
+    assert!("PT".parse::<Duration>().is_err());
+    assert!("1D".parse::<Duration>().is_err());
+    assert!("P1H".parse::<Duration>().is_err());
+    assert!("PT1.5H".parse::<Duration>().is_err());
+    assert!("P1.5D".parse::<Duration>().is_err());
+}
+
+#[test]
+fn arithmetic() {
+    let date = |text: &str| text.parse::<Date>().unwrap();
+    let duration = |text: &str| text.parse::<Duration>().unwrap();
+    let local = |text: &str| text.parse::<LocalDateTime>().unwrap();
+
+    // Day is clamped to the last day of the month.
+    assert_eq!(date("2020-01-31") + duration("P1M"), date("2020-02-29"));
+    assert_eq!(date("2021-01-31") + duration("P1M"), date("2021-02-28"));
+    assert_eq!(date("2020-02-29") + duration("P1Y"), date("2021-02-28"));
+    // Months are added before days.
+    assert_eq!(date("2020-01-31") + duration("P1M1D"), date("2020-03-01"));
+    // Time is added as whole days.
+    assert_eq!(date("2020-01-01") + duration("PT47H"), date("2020-01-02"));
+    assert_eq!(date("2020-01-01") + duration("PT-23H"), date("2020-01-01"));
+    assert_eq!(date("2020-03-31") - duration("P1M"), date("2020-02-29"));
+
+    assert_eq!(local("2020-01-31T23:00:00") + duration("P1MT2H"), local("2020-03-01T01:00:00"));
+    assert_eq!(local("2020-01-01T00:00:00") + duration("PT-0.5S"), local("2019-12-31T23:59:59.5"));
+    assert_eq!(local("2020-03-01T00:00:00") - duration("P1D"), local("2020-02-29T00:00:00"));
+
+    assert!(date("+999999999-12-31").add_duration(&duration("P1D")).is_err());
+    assert!(LocalDateTime::from_date_time(&Date { days: i64::MAX / 86_400 }, &LocalTime { nanoseconds: i64::MAX }).is_err());
+    assert!(Date { days: 0 }.add_duration(&Duration::new(i64::MAX, 0, 0, 0).unwrap()).is_err());
+}
@@ -0,0 +1,135 @@
+use super::*;
+use packstream_serde::value::structure::*;
+use packstream_serde::{from_text, to_text};
+use serde_bytes::ByteBuf;
+use packstream_serde::Map;
+
+// Asserts that the `text` parses into `value` and that printed `value` parses back to it.
+fn text(text: &str, value: Value) {
+    assert_eq!(from_text(text).unwrap(), value);
+    assert_eq!(from_text(&to_text(&value)).unwrap(), value);
+}
+
+#[test]
+fn scalars() {
+    text("null", Value::Null);
+    text("true", Value::Bool(true));
+    text(" false ", Value::Bool(false));
+    text("-17", Value::I64(-17));
+    text("9223372036854775807", Value::I64(i64::MAX));
+    text("2.5", Value::F64(2.5));
+    text("-1e-3", Value::F64(-0.001));
+    text("inf", Value::F64(f64::INFINITY));
+    text("-inf", Value::F64(f64::NEG_INFINITY));
+    text(r#""a \"quoted\"\n\\ \u{1F600}""#, Value::String(String::from("a \"quoted\"\n\\ \u{1F600}")));
+    text("Bytes([0, 16, 255])", Value::Bytes(ByteBuf::from(vec![0, 16, 255])));
+
+    assert!(matches!(from_text("NaN"), Ok(Value::F64(v)) if v.is_nan()));
+
+    assert_eq!(to_text(&Value::F64(1.0)), "1.0");
+    assert_eq!(to_text(&Value::String(String::from("\u{1}"))), r#""\u{1}""#);
+}
+
+#[test]
+fn collections() {
+    text("[]", Value::List(Vec::new()));
+    text("{}", Value::Map(Map::new()));
+    text(
+        r#"{name: "x", tags: [1, 2.5, null], "with space": {}, }"#,
+        Value::Map(map! {
+            "name" => Value::String(String::from("x")),
+            "tags" => Value::List(vec![Value::I64(1), Value::F64(2.5), Value::Null]),
+            "with space" => Value::Map(Map::new()),
+        }),
+    );
+
+    assert_eq!(
+        to_text(&Value::Map(map! {
+            "b" => Value::List(vec![Value::Bool(true)]),
+            "a" => Value::Null,
+            "c d" => Value::I64(1),
+        })),
+        r#"{a: null, b: [true], "c d": 1}"#,
+    );
+}
+
+#[test]
+fn structures() {
+    text(
+        r#"{n: Node(1, ["A"], {})}"#,
+        Value::Map(map! {
+            "n" => Value::Structure(Structure::Node(Node {
+                id: 1,
+                labels: vec![String::from("A")],
+                properties: Map::new(),
+            })),
+        }),
+    );
+    text(
+        r#"Path([Node(1, [], {}), Node(2, [], {})], [UnboundRelationship(3, "KNOWS", {since: 2000})], [1, 1])"#,
+        Value::Structure(Structure::Path(Path {
+            nodes: vec![
+                Node { id: 1, labels: Vec::new(), properties: Map::new() },
+                Node { id: 2, labels: Vec::new(), properties: Map::new() },
+            ],
+            relationships: vec![UnboundRelationship {
+                id: 3,
+                r#type: String::from("KNOWS"),
+                properties: map! { "since" => Value::I64(2000) },
+            }],
+            sequence: vec![1, 1],
+        })),
+    );
+    text(
+        r#"Relationship(1, 2, 3, "T", {})"#,
+        Value::Structure(Structure::Relationship(Relationship {
+            id: 1,
+            start_node_id: 2,
+            end_node_id: 3,
+            r#type: String::from("T"),
+            properties: Map::new(),
+        })),
+    );
+    text("Date(1)", Value::Structure(Structure::Date(Date { days: 1 })));
+    text(
+        r#"DateTimeZoneId(1, 2, "Europe/Warsaw")"#,
+        Value::Structure(Structure::DateTimeZoneId(DateTimeZoneId {
+            seconds: 1,
+            nanoseconds: 2,
+            tz_id: String::from("Europe/Warsaw"),
+        })),
+    );
+    text(
+        "Duration(1, 2, 3, 4)",
+        Value::Structure(Structure::Duration(Duration { months: 1, days: 2, seconds: 3, nanoseconds: 4 })),
+    );
+    text(
+        "Point3D(4979, 1.0, 2.5, -3.0)",
+        Value::Structure(Structure::Point3D(Point3D { srid: 4979, x: 1.0, y: 2.5, z: -3.0 })),
+    );
+
+    assert_eq!(to_text(&from_text("Time(10,  -3600)").unwrap()), "Time(10, -3600)");
+}
+
+#[test]
+fn invalid() {
+    assert!(from_text("").is_err());
+    assert!(from_text("nul").is_err());
+    assert!(from_text("[1, 2").is_err());
+    assert!(from_text("[1 2]").is_err());
+    assert!(from_text("{a 1}").is_err());
+    assert!(from_text("{a: 1, a: 2}").is_err());
+    assert!(from_text(r#""unterminated"#).is_err());
+    assert!(from_text(r#""\q""#).is_err());
+    assert!(from_text("1.2.3").is_err());
+    assert!(from_text("99999999999999999999").is_err());
+    assert!(from_text("null null").is_err());
+    assert!(from_text("Bytes([256])").is_err());
+    assert!(from_text("Unknown(1)").is_err());
+    assert!(from_text("Date()").is_err());
+    assert!(from_text(r#"Date("1")"#).is_err());
+    assert!(from_text("Point2D(1, 2, 3)").is_err());
+
+    let error = from_text("[1, ?]").unwrap_err();
+    assert!(format!("{}", error).contains("position 4"));
+}
@@ -0,0 +1,107 @@
+use super::*;
+use packstream_serde::value::structure::*;
+use packstream_serde::value::ValueVisitor;
+use packstream_serde::Map;
+
+fn node(id: i64, properties: Map<String, Value>) -> Node {
+    Node {
+        id,
+        labels: vec![String::from("Person")],
+        properties,
+    }
+}
+
+fn unbound_relationship(id: i64) -> UnboundRelationship {
+    UnboundRelationship {
+        id,
+        r#type: String::from("KNOWS"),
+        properties: map! { "since" => Value::I64(2000) },
+    }
+}
+
+fn graph() -> Value {
+    Value::List(vec![
+        Value::Structure(Structure::Node(node(1, map! { "age" => Value::I64(30) }))),
+        Value::Map(map! {
+            "relationship" => Value::Structure(Structure::Relationship(Relationship {
+                id: 10,
+                start_node_id: 1,
+                end_node_id: 2,
+                r#type: String::from("KNOWS"),
+                properties: Map::new(),
+            })),
+        }),
+        Value::Structure(Structure::Path(Path {
+            nodes: vec![node(2, Map::new()), node(3, map! { "age" => Value::I64(40) })],
+            relationships: vec![unbound_relationship(11)],
+            sequence: vec![1, 1],
+        })),
+    ])
+}
+
+#[test]
+fn walk() {
+    let value = graph();
+    let mut count = 0;
+    value.walk(&mut |_: &Value| count += 1);
+
+    // List, Node, age, Map, Relationship, Path, age, since.
+    assert_eq!(count, 8);
+
+    #[derive(Default)]
+    struct Ints(Vec<i64>);
+
+    impl<'a> ValueVisitor<'a> for Ints {
+        fn visit_value(&mut self, value: &'a Value) {
+            if let Value::I64(v) = value {
+                self.0.push(*v);
+            }
+        }
+    }
+
+    let mut ints = Ints::default();
+    value.walk(&mut ints);
+    ints.0.sort_unstable();
+
+    assert_eq!(ints.0, vec![30, 40, 2000]);
+}
+
+#[test]
+fn map() {
+    let value = graph().map(|value| match value {
+        Value::I64(v) => Value::String(v.to_string()),
+        value => value,
+    });
+
+    let nodes = value.nodes();
+    assert_eq!(nodes[0].properties.get("age"), Some(&Value::String(String::from("30"))));
+    assert_eq!(nodes[2].properties.get("age"), Some(&Value::String(String::from("40"))));
+    assert_eq!(
+        value.unbound_relationships()[0].properties.get("since"),
+        Some(&Value::String(String::from("2000"))),
+    );
+
+    let redacted = Value::Map(map! {
+        "password" => Value::String(String::from("secret")),
+    }).map(|value| match value {
+        Value::Map(mut map) => {
+            map.remove("password");
+            Value::Map(map)
+        }
+        value => value,
+    });
+
+    assert_eq!(redacted, Value::Map(Map::new()));
+}
+
+#[test]
+fn collectors() {
+    let value = graph();
+
+    assert_eq!(value.nodes().iter().map(|n| n.id).collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(value.relationships().iter().map(|r| r.id).collect::<Vec<_>>(), vec![10]);
+    assert_eq!(value.unbound_relationships().iter().map(|r| r.id).collect::<Vec<_>>(), vec![11]);
+
+    assert!(Value::Null.nodes().is_empty());
+    assert!(Value::I64(1).relationships().is_empty());
+}
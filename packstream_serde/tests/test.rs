@@ -18,7 +18,7 @@ macro_rules! bytes {
 macro_rules! map {
    ($($key:literal => $value:expr),* $(,)*) => {
       {
-         let mut __map = std::collections::HashMap::new();
+         let mut __map = packstream_serde::Map::new();
          $(__map.insert(String::from($key), $value);)*
          __map
       }